@@ -1,18 +1,103 @@
 const COMMANDS: &[&str] = &[
     "spawn_overlay",
     "close_overlay",
+    "overlay_duplicate",
     "overlay_set_click_through",
+    "overlay_set_click_through_all",
+    "overlay_update_geometry_animated",
+    "overlay_set_position",
+    "overlay_set_size",
+    "overlay_set_zoom",
+    "overlay_set_opacity",
+    "overlay_set_decorations",
+    "overlay_set_show_on_fullscreen",
+    "overlay_set_hide_on_fullscreen",
+    "overlay_set_desktop_level",
+    "overlay_set_schedule",
+    "overlay_set_background_color",
+    "overlay_set_chrome",
+    "overlay_inject_css",
+    "overlay_reconfigure",
+    "overlay_set_interactive_regions",
+    "overlay_capture_thumbnail",
+    "overlay_set_group",
+    "overlay_set_locked",
+    "overlay_group_set_visible",
+    "overlay_group_move",
+    "list_monitors",
+    "overlay_move_to_monitor",
+    "compute_preset_position",
+    "overlay_move_to_cursor_monitor",
+    "overlay_set_visible_all",
+    "overlay_ready",
+    "overlay_flash",
+    "overlay_message",
+    "overlay_set_exclude_from_bulk_toggle",
     "list_overlays",
     "service_register",
     "service_unregister",
     "service_list_providers",
+    "service_list_subscribers",
+    "service_list_all_subscriptions",
     "service_subscribe",
     "service_unsubscribe",
     "service_broadcast",
+    "service_broadcast_batch",
+    "service_set_rate_limit",
+    "service_request",
+    "service_respond",
+    "service_get_last_value",
+    "service_bridge_status",
+    "service_bridge_configure",
+    "service_bridge_reset_token",
+    "service_get_binary",
     "plugin_list",
+    "plugin_get_all",
     "plugin_get",
+    "plugin_settings_get_schema",
+    "plugin_store_path",
+    "plugin_secret_set",
+    "plugin_secret_get",
+    "plugin_secret_delete",
+    "plugin_load_errors",
     "plugin_install_local",
+    "registry_fetch_index",
+    "plugin_validate",
     "plugin_uninstall",
+    "plugin_reveal_in_explorer",
+    "plugin_verify_integrity",
+    "plugin_read_file",
+    "plugin_list_files",
+    "open_external",
+    "refresh_plugins",
+    "native_plugin_broadcast",
+    "native_plugin_load_dev",
+    "native_plugin_signing_policy",
+    "native_plugin_set_require_signed",
+    "native_plugin_add_trusted_key",
+    "native_plugin_remove_trusted_key",
+    "yaof_status",
+    "restart_app",
+    "open_settings",
+    "focus_settings",
+    "close_settings",
+    "yaof_set_log_level",
+    "get_log_path",
+    "system_notifications_set_enabled",
+    "system_notifications_enabled",
+    "system_idle_set_threshold",
+    "system_idle_threshold",
+    "system_media_artwork",
+    "system_set_tick_interval",
+    "system_get_tick_interval",
+    "autostart_get_stagger",
+    "autostart_set_stagger",
+    "check_accessibility_permission",
+    "request_accessibility_permission",
+    "system_processes_configure",
+    "process_kill",
+    "process_focus",
+    "network_get_interfaces",
 ];
 
 fn main() {
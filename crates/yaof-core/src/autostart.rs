@@ -1,16 +1,76 @@
-use std::path::Path;
+use std::collections::HashSet;
+use std::time::Duration;
 
+use serde::Serialize;
 use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
 
 use crate::{
     OverlayState, PluginState,
-    manifest::OverlayDefinition,
-    overlay::{OverlayConfig, manager::OverlayManager},
-    settings::{
-        OverlaySettings, ScreenInfo, calculate_position_from_preset, position_preset_to_string,
-    },
+    manifest::{DevConfig, OverlayDefinition},
+    overlay::{OverlayConfig, manager::OverlayManager, monitor::MonitorBounds},
+    settings::{OverlaySettings, Padding, position_preset_to_string},
 };
 
+/// Store holding the startup spawn settings
+const AUTOSTART_STORE: &str = "autostart.json";
+
+/// Overlays spawned at once on startup before pausing
+pub const DEFAULT_SPAWN_BATCH_SIZE: usize = 4;
+
+/// Pause between startup spawn batches
+pub const DEFAULT_SPAWN_BATCH_DELAY_MS: u64 = 150;
+
+/// How startup spawning is staggered. Overlays are created `batch_size` at a time
+/// with `batch_delay_ms` between batches, so setups with few overlays start at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutostartSettings {
+    pub batch_size: usize,
+    pub batch_delay_ms: u64,
+}
+
+impl Default for AutostartSettings {
+    fn default() -> Self {
+        Self {
+            batch_size: DEFAULT_SPAWN_BATCH_SIZE,
+            batch_delay_ms: DEFAULT_SPAWN_BATCH_DELAY_MS,
+        }
+    }
+}
+
+impl AutostartSettings {
+    /// Load the spawn settings from the store, falling back to the defaults
+    pub fn load(app: &AppHandle) -> Result<Self, tauri_plugin_store::Error> {
+        let store = app.store(AUTOSTART_STORE)?;
+
+        let batch_size = store
+            .get("batchSize")
+            .and_then(|v| v.as_u64())
+            .and_then(|n| usize::try_from(n).ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(DEFAULT_SPAWN_BATCH_SIZE);
+        let batch_delay_ms = store
+            .get("batchDelayMs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_SPAWN_BATCH_DELAY_MS);
+
+        Ok(Self {
+            batch_size,
+            batch_delay_ms,
+        })
+    }
+
+    /// Persist the spawn settings. A batch size of 0 is stored as 1.
+    pub fn save(&self, app: &AppHandle) -> Result<(), tauri_plugin_store::Error> {
+        let store = app.store(AUTOSTART_STORE)?;
+
+        store.set("batchSize", self.batch_size.max(1));
+        store.set("batchDelayMs", self.batch_delay_ms);
+        store.save()
+    }
+}
+
 /// Information about an overlay to be spawned
 #[derive(Debug)]
 struct OverlaySpawnInfo {
@@ -20,6 +80,8 @@ struct OverlaySpawnInfo {
     is_core: bool,
     definition: OverlayDefinition,
     settings: Option<OverlaySettings>,
+    /// Dev server config, only set when running in dev mode
+    dev_server: Option<DevConfig>,
 }
 
 /// Autostart manager that handles spawning enabled overlays on startup
@@ -28,51 +90,139 @@ pub struct AutostartManager;
 impl AutostartManager {
     /// Spawn all enabled overlays from installed plugins.
     /// This should be called after the app is fully initialized.
-    pub fn spawn_enabled_overlays(app: &AppHandle) -> Result<Vec<String>, String> {
+    ///
+    /// Overlays are spawned in batches per `AutostartSettings`, releasing the overlay
+    /// manager between batches, so a large setup doesn't create every window at once.
+    pub async fn spawn_enabled_overlays(app: &AppHandle) -> Result<Vec<String>, String> {
+        // Collect all overlays to spawn
+        let mut overlays_to_spawn = Self::collect_overlays_to_spawn(app)?;
+
+        // Get the primary monitor for position calculations
+        let monitor = Self::primary_monitor(app);
+
+        let stagger = AutostartSettings::load(app).unwrap_or_else(|e| {
+            tracing::warn!("Failed to load autostart settings, using defaults: {}", e);
+            AutostartSettings::default()
+        });
+        let batch_size = stagger.batch_size.max(1);
+
+        // Spawn each enabled overlay, a batch at a time
         let mut spawned_ids = Vec::new();
+        while !overlays_to_spawn.is_empty() {
+            let rest = overlays_to_spawn.split_off(batch_size.min(overlays_to_spawn.len()));
+            let batch = std::mem::replace(&mut overlays_to_spawn, rest);
+
+            {
+                let overlay_state = app.state::<OverlayState>();
+                let mut manager = overlay_state
+                    .0
+                    .lock()
+                    .map_err(|e| format!("Failed to lock overlay manager: {}", e))?;
+
+                spawned_ids.extend(Self::spawn_missing(&mut manager, batch, &monitor));
+            }
 
-        // Get app data directory for reading settings
-        let app_data_dir = app
-            .path()
-            .app_data_dir()
-            .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+            if !overlays_to_spawn.is_empty() && stagger.batch_delay_ms > 0 {
+                tokio::time::sleep(Duration::from_millis(stagger.batch_delay_ms)).await;
+            }
+        }
 
-        // Collect all overlays to spawn
-        let overlays_to_spawn = Self::collect_overlays_to_spawn(app, &app_data_dir)?;
+        Ok(spawned_ids)
+    }
 
-        // Get screen info for position calculations
-        let screen = Self::get_primary_screen_info(app);
+    /// Re-evaluate autostart against the currently installed plugins without a restart.
+    /// Spawns enabled overlays that aren't open yet and closes overlays whose plugin
+    /// is no longer installed. Returns the (spawned, closed) overlay IDs.
+    pub fn refresh_overlays(app: &AppHandle) -> Result<(Vec<String>, Vec<String>), String> {
+        // Rescans plugins, so the installed set below is up to date
+        let overlays_to_spawn = Self::collect_overlays_to_spawn(app)?;
+
+        let installed: HashSet<String> = {
+            let plugin_state = app.state::<PluginState>();
+            let loader = plugin_state
+                .0
+                .lock()
+                .map_err(|e| format!("Failed to lock plugin loader: {}", e))?;
+            loader
+                .list_plugins()
+                .iter()
+                .map(|p| p.manifest.id.clone())
+                .collect()
+        };
+
+        let monitor = Self::primary_monitor(app);
 
-        // Spawn each enabled overlay
         let overlay_state = app.state::<OverlayState>();
         let mut manager = overlay_state
             .0
             .lock()
             .map_err(|e| format!("Failed to lock overlay manager: {}", e))?;
 
-        for info in overlays_to_spawn {
-            match Self::spawn_overlay(&mut manager, &info, &screen) {
+        // Close overlays belonging to plugins that were uninstalled
+        let stale: Vec<String> = manager
+            .list_overlays()
+            .iter()
+            .filter(|o| !installed.contains(&o.plugin_id))
+            .map(|o| o.config.id.clone())
+            .collect();
+
+        let mut closed_ids = Vec::new();
+        for id in stale {
+            match manager.close_overlay(&id) {
+                Ok(()) => {
+                    tracing::info!("Closed overlay of uninstalled plugin: {}", id);
+                    closed_ids.push(id);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to close overlay {}: {}", id, e);
+                }
+            }
+        }
+
+        let spawned_ids = Self::spawn_missing(&mut manager, overlays_to_spawn, &monitor);
+
+        Ok((spawned_ids, closed_ids))
+    }
+
+    /// Spawn each overlay that isn't already open, returning the spawned IDs
+    fn spawn_missing(
+        manager: &mut OverlayManager,
+        overlays: Vec<OverlaySpawnInfo>,
+        monitor: &Option<MonitorBounds>,
+    ) -> Vec<String> {
+        let mut spawned_ids = Vec::new();
+
+        for info in overlays {
+            if manager.has_overlay(&Self::window_id(&info)) {
+                continue;
+            }
+
+            match Self::spawn_overlay(manager, &info, monitor) {
                 Ok(id) => {
-                    println!("[Autostart] Spawned overlay: {}", id);
+                    tracing::info!("Spawned overlay: {}", id);
                     spawned_ids.push(id);
                 }
                 Err(e) => {
-                    eprintln!(
-                        "[Autostart] Failed to spawn overlay {}/{}: {}",
-                        info.plugin_id, info.overlay_id, e
+                    tracing::error!(
+                        "Failed to spawn overlay {}/{}: {}",
+                        info.plugin_id,
+                        info.overlay_id,
+                        e
                     );
                 }
             }
         }
 
-        Ok(spawned_ids)
+        spawned_ids
+    }
+
+    /// Window label used for an autostarted overlay
+    fn window_id(info: &OverlaySpawnInfo) -> String {
+        format!("{}-{}", info.plugin_id, info.overlay_id)
     }
 
     /// Collect all overlays that should be spawned based on settings
-    fn collect_overlays_to_spawn(
-        app: &AppHandle,
-        app_data_dir: &Path,
-    ) -> Result<Vec<OverlaySpawnInfo>, String> {
+    fn collect_overlays_to_spawn(app: &AppHandle) -> Result<Vec<OverlaySpawnInfo>, String> {
         let plugin_state = app.state::<PluginState>();
         let mut loader = plugin_state
             .0
@@ -96,11 +246,19 @@ impl AutostartManager {
                 continue;
             }
 
+            // Safe mode starts without third-party overlays so a broken one can be disabled
+            if crate::is_safe_mode() {
+                continue;
+            }
+
             // Iterate through each overlay defined in the manifest
             for (overlay_id, definition) in &manifest.overlays {
                 // Load persisted settings if they exist, using manifest definition as fallback for defaults
-                let settings =
-                    OverlaySettings::load(app_data_dir, &manifest.id, overlay_id, definition);
+                let store_path = crate::plugin_store_path(
+                    app,
+                    &format!("{}-{}-overlay.json", manifest.id, overlay_id),
+                );
+                let settings = OverlaySettings::load(&store_path, definition);
 
                 // Determine if this overlay should be spawned
                 let should_spawn = match &settings {
@@ -115,6 +273,7 @@ impl AutostartManager {
                         is_core: manifest.is_valid_core_plugin(),
                         definition: definition.clone(),
                         settings,
+                        dev_server: manifest.dev.clone().filter(|_| crate::is_dev_mode()),
                     });
                 }
             }
@@ -127,7 +286,7 @@ impl AutostartManager {
     fn spawn_overlay(
         manager: &mut OverlayManager,
         info: &OverlaySpawnInfo,
-        screen: &Option<ScreenInfo>,
+        monitor: &Option<MonitorBounds>,
     ) -> Result<String, String> {
         // Determine dimensions - use settings if available, otherwise manifest defaults
         let width = info
@@ -143,7 +302,9 @@ impl AutostartManager {
             .unwrap_or(info.definition.height);
 
         // Determine position
-        let (x, y) = Self::calculate_position(info, screen, width, height);
+        let padding = Self::padding(info);
+        let (x, y) = Self::calculate_position(info, monitor, width, height, padding);
+        let position_preset = Self::position_preset(info);
 
         // Determine click-through setting
         let click_through = info
@@ -152,19 +313,65 @@ impl AutostartManager {
             .map(|s| s.click_through)
             .unwrap_or(info.definition.click_through);
 
+        let zoom = info.settings.as_ref().map(|s| s.zoom).unwrap_or(1.0);
+
+        let opacity = info.settings.as_ref().map(|s| s.opacity).unwrap_or(100.0);
+        let hover_opacity = info.settings.as_ref().and_then(|s| s.hover_opacity);
+
+        let group = info.settings.as_ref().and_then(|s| s.group.clone());
+        let locked = info.settings.as_ref().is_some_and(|s| s.locked);
+        let exclude_from_bulk_toggle = info
+            .settings
+            .as_ref()
+            .is_some_and(|s| s.exclude_from_bulk_toggle);
+        let show_on_fullscreen = info.settings.as_ref().is_none_or(|s| s.show_on_fullscreen);
+        let hide_on_fullscreen = info.settings.as_ref().is_some_and(|s| s.hide_on_fullscreen);
+        let always_on_top = info.settings.as_ref().is_none_or(|s| s.always_on_top);
+        let desktop_level = info
+            .settings
+            .as_ref()
+            .map(|s| s.desktop_level)
+            .unwrap_or(info.definition.desktop_level);
+        let background_color = info
+            .settings
+            .as_ref()
+            .map(|s| s.background_color)
+            .unwrap_or_default();
+        let corner_radius = info
+            .settings
+            .as_ref()
+            .map(|s| s.corner_radius)
+            .unwrap_or_default();
+        let border = info.settings.as_ref().and_then(|s| s.border);
+        let inject_css = info.settings.as_ref().and_then(|s| s.inject_css.clone());
+        let schedule = info
+            .settings
+            .as_ref()
+            .map(|s| s.schedule.clone())
+            .unwrap_or_default();
+
+        let frameless = info
+            .settings
+            .as_ref()
+            .map(|s| s.frameless)
+            .unwrap_or(info.definition.frameless);
+
         // Build the entry point URL with route hash for HashRouter support
+        // - In dev mode, plugins declaring `dev.port` load from their local dev server (HMR)
         // - Core plugins (validated via is_valid_core_plugin) use bundled assets (WebviewUrl::App)
         // - Other plugins use the custom yaof-plugin:// protocol for dynamic loading
         // - Route is appended as hash fragment for client-side routing
         let route = info.definition.route.as_deref().unwrap_or("/");
-        let entry_point = if info.is_core {
+        let entry_point = if let Some(dev) = &info.dev_server {
+            dev.entry_url(route)
+        } else if info.is_core {
             format!("index.html#{}", route) // Uses WebviewUrl::App (bundled via frontendDist)
         } else {
             format!("yaof-plugin://{}/index.html#{}", info.plugin_id, route) // Custom protocol
         };
 
         // Create overlay ID
-        let overlay_id = format!("{}-{}", info.plugin_id, info.overlay_id);
+        let overlay_id = Self::window_id(info);
 
         let config = OverlayConfig {
             id: overlay_id,
@@ -175,12 +382,49 @@ impl AutostartManager {
             x,
             y,
             click_through,
-            frameless: info.definition.frameless,
+            frameless,
+            always_on_top,
+            desktop_level,
+            zoom,
+            opacity,
+            hover_opacity,
+            group,
+            locked,
+            exclude_from_bulk_toggle,
+            show_on_fullscreen,
+            hide_on_fullscreen,
+            background_color,
+            corner_radius,
+            border,
+            padding,
+            inject_css,
+            schedule,
+            position_preset,
+            ready_timeout_ms: info.definition.ready_timeout_ms.unwrap_or(0),
         };
 
-        println!("{:?}", config);
+        tracing::debug!("{:?}", config);
 
-        manager.spawn_overlay(config).map_err(|e| e.to_string())
+        let id = manager.spawn_overlay(config).map_err(|e| e.to_string())?;
+        if let Some(settings) = &info.settings {
+            Self::apply_settings(manager, &id, settings).map_err(|e| e.to_string())?;
+        }
+        Ok(id)
+    }
+
+    /// Bring a freshly spawned overlay in line with its saved settings, so it starts
+    /// exactly as it was left: click-through, always on top and opacity. Overlays
+    /// outside their schedule are already kept hidden by `spawn_overlay`.
+    fn apply_settings(
+        manager: &mut OverlayManager,
+        id: &str,
+        settings: &OverlaySettings,
+    ) -> Result<(), crate::Error> {
+        manager.set_click_through(id, settings.click_through)?;
+        manager.set_always_on_top(id, settings.always_on_top)?;
+        manager.set_opacity(id, settings.opacity, settings.hover_opacity)?;
+
+        Ok(())
     }
 
     /// Calculate the position for an overlay based on settings or manifest defaults
@@ -193,9 +437,10 @@ impl AutostartManager {
     /// 5. Ultimate fallback - (100, 100)
     fn calculate_position(
         info: &OverlaySpawnInfo,
-        screen: &Option<ScreenInfo>,
+        monitor: &Option<MonitorBounds>,
         width: f64,
         height: f64,
+        padding: Padding,
     ) -> (f64, f64) {
         // First check if we have explicit x/y coordinates in stored settings
         // If user has saved specific coordinates, use them directly
@@ -207,14 +452,8 @@ impl AutostartManager {
 
             // Check for position preset in settings (no explicit x/y stored)
             if let Some(preset) = &settings.position_preset {
-                if let Some(screen_info) = screen {
-                    return calculate_position_from_preset(
-                        preset,
-                        screen_info,
-                        width,
-                        height,
-                        20.0,
-                    );
+                if let Some(monitor) = monitor {
+                    return monitor.position_for_preset(preset, width, height, padding);
                 }
             }
         }
@@ -225,26 +464,56 @@ impl AutostartManager {
         }
 
         // Then use manifest's default position preset
-        if let Some(screen_info) = screen {
+        if let Some(monitor) = monitor {
             let preset_str = position_preset_to_string(&info.definition.default_position);
-            return calculate_position_from_preset(&preset_str, screen_info, width, height, 20.0);
+            return monitor.position_for_preset(&preset_str, width, height, padding);
         }
 
         // Ultimate fallback - center of a typical screen
         (100.0, 100.0)
     }
 
-    /// Get primary screen information for position calculations
-    fn get_primary_screen_info(_app: &AppHandle) -> Option<ScreenInfo> {
-        // Try to get monitor info from Tauri
-        // Note: This is a simplified approach - in production you might want to
-        // use the actual monitor API when available
-
-        // For now, we'll use reasonable defaults that work for most screens
-        // The overlay will adjust itself via the SDK's usePosition hook after loading
-        Some(ScreenInfo {
-            width: 1920.0,
-            height: 1080.0,
-        })
+    /// Get the edge padding for position presets, settings first, then the manifest
+    fn padding(info: &OverlaySpawnInfo) -> Padding {
+        info.settings
+            .as_ref()
+            .and_then(|s| s.padding)
+            .unwrap_or(info.definition.padding)
+    }
+
+    /// Get the preset the position was derived from, following the same
+    /// priority order as `calculate_position`
+    fn position_preset(info: &OverlaySpawnInfo) -> Option<String> {
+        if let Some(settings) = &info.settings {
+            if settings.x.is_some() && settings.y.is_some() {
+                return None;
+            }
+            if let Some(preset) = &settings.position_preset {
+                return Some(preset.clone());
+            }
+        }
+
+        if info.definition.x.is_some() && info.definition.y.is_some() {
+            return None;
+        }
+
+        Some(position_preset_to_string(&info.definition.default_position))
+    }
+
+    /// Get the primary monitor's bounds for position calculations, in logical pixels.
+    /// Only falls back to 1920x1080 at the origin if the primary monitor can't be queried.
+    fn primary_monitor(app: &AppHandle) -> Option<MonitorBounds> {
+        match crate::overlay::monitor::primary_monitor_bounds(app) {
+            Some(bounds) => Some(bounds),
+            None => {
+                tracing::warn!("Could not query the primary monitor, assuming 1920x1080");
+                Some(MonitorBounds {
+                    x: 0.0,
+                    y: 0.0,
+                    width: 1920.0,
+                    height: 1080.0,
+                })
+            }
+        }
     }
 }
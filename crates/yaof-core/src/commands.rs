@@ -1,10 +1,12 @@
 use std::sync::Mutex;
-use tauri::{AppHandle, State, WebviewWindow, command};
+use tauri::{AppHandle, Emitter, Manager, Runtime, State, WebviewWindow, command};
 
 use crate::{
+    CommandError,
     loader::PluginLoader,
     manifest::PluginManifest,
-    overlay::{OverlayConfig, manager::OverlayManager},
+    overlay::{OverlayConfig, ScheduleWindow, manager::OverlayManager},
+    services::system::InterfaceInfo,
 };
 
 pub struct OverlayState(pub Mutex<OverlayManager>);
@@ -13,16 +15,34 @@ pub struct PluginState(pub Mutex<PluginLoader>);
 #[command]
 pub fn spawn_overlay(
     state: State<'_, OverlayState>,
-    config: OverlayConfig,
-) -> Result<String, String> {
-    let mut manager = state.0.lock().map_err(|e| e.to_string())?;
-    manager.spawn_overlay(config).map_err(|e| e.to_string())
+    plugin_state: State<'_, PluginState>,
+    mut config: OverlayConfig,
+) -> Result<String, CommandError> {
+    // In dev mode, redirect yaof-plugin:// entry points to the plugin's dev server
+    let protocol_prefix = format!("yaof-plugin://{}", config.plugin_id);
+    if config.entry_point.starts_with(&protocol_prefix) {
+        let route = config
+            .entry_point
+            .split_once('#')
+            .map(|(_, route)| route)
+            .unwrap_or("/");
+        let loader = plugin_state.0.lock()?;
+        if let Some(url) = loader
+            .get_plugin(&config.plugin_id)
+            .and_then(|p| p.manifest.dev_entry_point(route))
+        {
+            config.entry_point = url;
+        }
+    }
+
+    let mut manager = state.0.lock()?;
+    manager.spawn_overlay(config).map_err(CommandError::from)
 }
 
 #[command]
-pub fn close_overlay(state: State<'_, OverlayState>, id: String) -> Result<(), String> {
-    let mut manager = state.0.lock().map_err(|e| e.to_string())?;
-    manager.close_overlay(&id).map_err(|e| e.to_string())
+pub fn close_overlay(state: State<'_, OverlayState>, id: String) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager.close_overlay(&id).map_err(CommandError::from)
 }
 
 #[command]
@@ -30,11 +50,23 @@ pub fn overlay_set_click_through(
     state: State<'_, OverlayState>,
     id: String,
     enabled: bool,
-) -> Result<(), String> {
-    let manager = state.0.lock().map_err(|e| e.to_string())?;
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
     manager
         .set_click_through(&id, enabled)
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
+}
+
+/// Make every overlay click-through at once, or restore each overlay's own state
+#[command]
+pub fn overlay_set_click_through_all(
+    state: State<'_, OverlayState>,
+    enabled: bool,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager
+        .set_click_through_all(enabled)
+        .map_err(CommandError::from)
 }
 
 #[command]
@@ -45,11 +77,123 @@ pub fn overlay_update_geometry(
     y: f64,
     width: f64,
     height: f64,
-) -> Result<(), String> {
-    let mut manager = state.0.lock().map_err(|e| e.to_string())?;
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
     manager
         .update_overlay_geometry(&id, x, y, width, height)
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
+}
+
+/// Like `overlay_update_geometry`, but animates the move and resize over `duration_ms`
+/// (at most 5s). Snaps instantly when the user prefers reduced motion.
+#[command]
+pub fn overlay_update_geometry_animated(
+    state: State<'_, OverlayState>,
+    id: String,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    duration_ms: u64,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager
+        .update_overlay_geometry_animated(&id, x, y, width, height, duration_ms)
+        .map_err(CommandError::from)
+}
+
+#[command]
+pub fn overlay_set_position(
+    state: State<'_, OverlayState>,
+    id: String,
+    x: f64,
+    y: f64,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager
+        .set_overlay_position(&id, x, y)
+        .map_err(CommandError::from)
+}
+
+#[command]
+pub fn overlay_set_size(
+    state: State<'_, OverlayState>,
+    id: String,
+    width: f64,
+    height: f64,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager
+        .set_overlay_size(&id, width, height)
+        .map_err(CommandError::from)
+}
+
+/// List the connected monitors, e.g. to let the user pick where an overlay lives
+#[command]
+pub fn list_monitors(app: AppHandle) -> Result<Vec<crate::MonitorInfo>, CommandError> {
+    Ok(crate::overlay::monitor::list_monitors(&app))
+}
+
+/// Move an overlay to a monitor given by index or name.
+/// Falls back to the primary monitor if the requested one no longer exists.
+#[command]
+pub fn overlay_move_to_monitor(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+    monitor: crate::MonitorSelector,
+) -> Result<(), CommandError> {
+    let target = crate::overlay::monitor::find_monitor(&app, &monitor)
+        .ok_or_else(|| CommandError::not_found("No monitors available"))?;
+
+    let mut manager = state.0.lock()?;
+    manager
+        .move_to_monitor(&id, target)
+        .map(|_| ())
+        .map_err(CommandError::from)
+}
+
+/// Where an overlay of the given size lands for a position preset, using the same
+/// calculation as autostart. Defaults to the primary monitor and the default padding,
+/// pass the overlay's stored padding to match where autostart places it.
+#[command]
+pub fn compute_preset_position(
+    app: AppHandle,
+    preset: String,
+    width: f64,
+    height: f64,
+    monitor: Option<crate::MonitorSelector>,
+    padding: Option<crate::settings::Padding>,
+) -> Result<(f64, f64), CommandError> {
+    serde_json::from_value::<crate::manifest::PositionPreset>(serde_json::Value::String(
+        preset.clone(),
+    ))
+    .map_err(|_| CommandError::invalid_argument(format!("Unknown position preset {}", preset)))?;
+
+    let bounds = match monitor {
+        Some(selector) => crate::overlay::monitor::find_monitor(&app, &selector),
+        None => crate::overlay::monitor::primary_monitor_bounds(&app),
+    }
+    .ok_or_else(|| CommandError::not_found("No monitors available"))?;
+
+    Ok(bounds.position_for_preset(&preset, width, height, padding.unwrap_or_default()))
+}
+
+/// Move an overlay to the monitor the cursor is currently on
+#[command]
+pub fn overlay_move_to_cursor_monitor(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+) -> Result<(), CommandError> {
+    let target = crate::overlay::monitor::cursor_monitor(&app)
+        .ok_or_else(|| CommandError::not_found("No monitors available"))?;
+
+    let mut manager = state.0.lock()?;
+    manager
+        .move_to_monitor(&id, target)
+        .map(|_| ())
+        .map_err(CommandError::from)
 }
 
 #[command]
@@ -57,16 +201,361 @@ pub fn overlay_set_always_on_top(
     state: State<'_, OverlayState>,
     id: String,
     enabled: bool,
-) -> Result<(), String> {
-    let manager = state.0.lock().map_err(|e| e.to_string())?;
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
     manager
         .set_always_on_top(&id, enabled)
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
+}
+
+/// Reapply an overlay's platform configuration after its settings changed
+#[command]
+pub fn overlay_reconfigure(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+    settings: crate::OverlaySettings,
+) -> Result<(), CommandError> {
+    {
+        let mut manager = state.0.lock()?;
+        manager.reconfigure(&id, &settings)?;
+    }
+
+    // The schedule may have changed, apply it right away like overlay_set_schedule
+    crate::overlay::sync_scheduled_visibility(&app);
+    Ok(())
+}
+
+#[command]
+pub fn overlay_set_zoom(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+    zoom: f64,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager.set_zoom(&id, zoom)?;
+
+    persist_overlay_setting(&app, &id, "zoom", serde_json::json!(zoom))
+}
+
+/// Set an overlay's resting opacity and the opacity it fades to while hovered (0 - 100).
+/// Pass null for `hover_opacity` to disable the hover fade.
+#[command]
+pub fn overlay_set_opacity(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+    opacity: f64,
+    hover_opacity: Option<f64>,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager.set_opacity(&id, opacity, hover_opacity)?;
+
+    persist_overlay_setting(&app, &id, "opacity", serde_json::json!(opacity))?;
+    persist_overlay_setting(&app, &id, "hoverOpacity", serde_json::json!(hover_opacity))
+}
+
+#[command]
+pub fn overlay_set_decorations(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+    enabled: bool,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager.set_decorations(&id, enabled)?;
+
+    persist_overlay_setting(&app, &id, "frameless", serde_json::json!(!enabled))
+}
+
+/// Set an overlay's background color as RGBA, `[0, 0, 0, 0]` is fully transparent
+#[command]
+pub fn overlay_set_background_color(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+    color: [u8; 4],
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager.set_background_color(&id, color)?;
+
+    persist_overlay_setting(&app, &id, "backgroundColor", serde_json::json!(color))
+}
+
+/// Set an overlay's corner radius and border. Applied natively on macOS and Windows 11,
+/// and always exposed to the webview as `--yaof-corner-radius`/`--yaof-border-*` CSS variables.
+#[command]
+pub fn overlay_set_chrome(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+    corner_radius: f64,
+    border: Option<crate::OverlayBorder>,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager.set_chrome(&id, corner_radius, border)?;
+
+    persist_overlay_setting(&app, &id, "cornerRadius", serde_json::json!(corner_radius))?;
+    persist_overlay_setting(&app, &id, "border", serde_json::json!(border))
+}
+
+/// Inject user CSS (or the contents of a .css file path) into one overlay, replacing
+/// what was injected before. None removes it. Reapplied whenever the overlay is spawned.
+/// Not part of the default permission set; the runtime grants it to the settings window.
+#[command]
+pub fn overlay_inject_css(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+    css: Option<String>,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager.inject_css(&id, css.clone())?;
+
+    persist_overlay_setting(&app, &id, "injectCss", serde_json::json!(css))
+}
+
+/// Desktop-only mode: hide an overlay whenever the focused app is fullscreen
+#[command]
+pub fn overlay_set_hide_on_fullscreen(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+    enabled: bool,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager.set_hide_on_fullscreen(&id, enabled)?;
+
+    persist_overlay_setting(&app, &id, "hideOnFullscreen", serde_json::json!(enabled))
+}
+
+/// Pin an overlay to the desktop behind all windows, or float it again with None
+#[command]
+pub fn overlay_set_desktop_level(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+    level: Option<crate::DesktopLevel>,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager.set_desktop_level(&id, level)?;
+
+    persist_overlay_setting(&app, &id, "desktopLevel", serde_json::json!(level))
+}
+
+/// Only show an overlay inside the given local time windows, always when empty
+#[command]
+pub fn overlay_set_schedule(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+    schedule: Vec<ScheduleWindow>,
+) -> Result<(), CommandError> {
+    {
+        let mut manager = state.0.lock()?;
+        manager.set_schedule(&id, schedule.clone())?;
+    }
+
+    // Apply right away instead of waiting for the next scheduled check
+    crate::overlay::sync_scheduled_visibility(&app);
+
+    persist_overlay_setting(&app, &id, "schedule", serde_json::json!(schedule))
+}
+
+/// Show or hide an overlay over fullscreen apps (macOS only)
+#[command]
+pub fn overlay_set_show_on_fullscreen(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+    enabled: bool,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager.set_show_on_fullscreen(&id, enabled)?;
+
+    persist_overlay_setting(&app, &id, "showOnFullscreen", serde_json::json!(enabled))
+}
+
+#[command]
+pub fn overlay_set_interactive_regions(
+    state: State<'_, OverlayState>,
+    id: String,
+    regions: Vec<crate::Rect>,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager
+        .set_interactive_regions(&id, regions)
+        .map_err(CommandError::from)
+}
+
+#[command]
+pub fn overlay_set_group(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+    group: Option<String>,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager.set_group(&id, group.clone())?;
+
+    persist_overlay_setting(&app, &id, "group", serde_json::json!(group))
+}
+
+#[command]
+pub fn overlay_group_set_visible(
+    state: State<'_, OverlayState>,
+    group: String,
+    visible: bool,
+) -> Result<(), CommandError> {
+    let manager = state.0.lock()?;
+    manager
+        .set_group_visible(&group, visible)
+        .map_err(CommandError::from)
+}
+
+/// Lock an overlay in place (or unlock it) for group moves
+#[command]
+pub fn overlay_set_locked(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+    locked: bool,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager.set_locked(&id, locked)?;
+
+    persist_overlay_setting(&app, &id, "locked", serde_json::json!(locked))
+}
+
+/// Move every unlocked overlay in a group by (dx, dy), returning the moved overlay IDs
+#[command]
+pub fn overlay_group_move(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    group: String,
+    dx: f64,
+    dy: f64,
+) -> Result<Vec<String>, CommandError> {
+    let mut manager = state.0.lock()?;
+    let moved = manager.move_group(&group, dx, dy)?;
+
+    for id in &moved {
+        if let Some(overlay) = manager.get_overlay(id) {
+            persist_overlay_setting(&app, id, "x", serde_json::json!(overlay.config.x))?;
+            persist_overlay_setting(&app, id, "y", serde_json::json!(overlay.config.y))?;
+        }
+    }
+
+    Ok(moved)
+}
+
+/// Capture a PNG thumbnail of an overlay's current contents, base64 encoded
+#[command]
+pub async fn overlay_capture_thumbnail(
+    state: State<'_, OverlayState>,
+    id: String,
+) -> Result<String, CommandError> {
+    use base64::Engine;
+
+    // Clone the window so the manager isn't locked while capturing
+    let window = {
+        let manager = state.0.lock()?;
+        manager
+            .get_overlay(&id)
+            .map(|o| o.window.clone())
+            .ok_or_else(|| crate::Error::WindowNotFound(id.clone()))?
+    };
+
+    let png = tauri::async_runtime::spawn_blocking(move || {
+        crate::overlay::capture::capture_thumbnail(&window)
+    })
+    .await??;
+    Ok(base64::engine::general_purpose::STANDARD.encode(png))
+}
+
+/// Spawn a copy of an overlay and give it its own settings store, so the two can diverge.
+/// Returns the new overlay id.
+#[command]
+pub fn overlay_duplicate(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+) -> Result<String, CommandError> {
+    let config = {
+        let mut manager = state.0.lock()?;
+        manager.duplicate_overlay(&id)?
+    };
+
+    persist_overlay_config(&app, &config)?;
+    Ok(config.id)
+}
+
+/// Write every persisted setting of an overlay config to the overlay's settings store
+fn persist_overlay_config(app: &AppHandle, config: &OverlayConfig) -> Result<(), CommandError> {
+    use tauri_plugin_store::StoreExt;
+
+    let store_path = crate::plugin_store_path(app, &format!("{}-overlay.json", config.id));
+    let store = app.store(&store_path)?;
+
+    store.set("enabled", true);
+    store.set("x", config.x);
+    store.set("y", config.y);
+    store.set("width", config.width);
+    store.set("height", config.height);
+    store.set("opacity", config.opacity);
+    store.set("hoverOpacity", serde_json::json!(config.hover_opacity));
+    store.set("clickThrough", config.click_through);
+    store.set("alwaysOnTop", config.always_on_top);
+    store.set("desktopLevel", serde_json::json!(config.desktop_level));
+    store.set("zoom", config.zoom);
+    store.set("frameless", config.frameless);
+    store.set("group", serde_json::json!(config.group));
+    store.set("locked", config.locked);
+    store.set("excludeFromBulkToggle", config.exclude_from_bulk_toggle);
+    store.set("showOnFullscreen", config.show_on_fullscreen);
+    store.set("hideOnFullscreen", config.hide_on_fullscreen);
+    store.set(
+        "backgroundColor",
+        serde_json::json!(config.background_color),
+    );
+    store.set("cornerRadius", config.corner_radius);
+    store.set("border", serde_json::json!(config.border));
+    store.set("padding", serde_json::json!(config.padding));
+    store.set("injectCss", serde_json::json!(config.inject_css));
+    store.set("schedule", serde_json::json!(config.schedule));
+    store.save().map_err(CommandError::from)
+}
+
+/// Persist an overlay's logical size after the user resized it
+pub(crate) fn persist_overlay_size(app: &AppHandle, id: &str, width: f64, height: f64) {
+    let result = persist_overlay_setting(app, id, "width", serde_json::json!(width))
+        .and_then(|_| persist_overlay_setting(app, id, "height", serde_json::json!(height)));
+    if let Err(e) = result {
+        tracing::warn!("Failed to persist size of overlay {}: {}", id, e);
+    }
+}
+
+/// Write a value to the overlay's settings store so it is reapplied on next spawn
+fn persist_overlay_setting(
+    app: &AppHandle,
+    id: &str,
+    key: &str,
+    value: serde_json::Value,
+) -> Result<(), CommandError> {
+    use tauri_plugin_store::StoreExt;
+
+    // Overlay IDs are "{plugin_id}-{overlay_id}", matching OverlaySettings::load
+    let store_path = crate::plugin_store_path(app, &format!("{}-overlay.json", id));
+    let store = app.store(&store_path)?;
+
+    store.set(key, value);
+    store.save().map_err(CommandError::from)
 }
 
 #[command]
-pub fn overlay_exists(state: State<'_, OverlayState>, id: String) -> Result<bool, String> {
-    let manager = state.0.lock().map_err(|e| e.to_string())?;
+pub fn overlay_exists(state: State<'_, OverlayState>, id: String) -> Result<bool, CommandError> {
+    let manager = state.0.lock()?;
     Ok(manager.has_overlay(&id))
 }
 
@@ -75,14 +564,119 @@ pub fn overlay_set_visible(
     state: State<'_, OverlayState>,
     id: String,
     visible: bool,
-) -> Result<(), String> {
-    let manager = state.0.lock().map_err(|e| e.to_string())?;
-    manager.set_visible(&id, visible).map_err(|e| e.to_string())
+) -> Result<(), CommandError> {
+    let manager = state.0.lock()?;
+    manager
+        .set_visible(&id, visible)
+        .map_err(CommandError::from)
+}
+
+/// Send a message straight to another overlay, which receives it on
+/// `yaof:overlay:{target}:message`. Lighter than a service for simple coordination.
+#[command]
+pub fn overlay_message(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    window: WebviewWindow,
+    target_overlay_id: String,
+    msg_type: String,
+    payload: serde_json::Value,
+) -> Result<(), CommandError> {
+    {
+        let manager = state.0.lock()?;
+        if !manager.has_overlay(&target_overlay_id) {
+            return Err(crate::Error::WindowNotFound(target_overlay_id).into());
+        }
+    }
+
+    let message = crate::OverlayMessage {
+        from: window.label().to_string(),
+        msg_type,
+        payload,
+    };
+    app.emit_to(
+        target_overlay_id.as_str(),
+        &crate::overlay_message_event(&target_overlay_id),
+        message,
+    )
+    .map_err(CommandError::from)
+}
+
+/// Called by an overlay's SDK once its page has loaded, so the window can be
+/// shown without a flash of empty content. Returns whether the overlay was waiting.
+#[command]
+pub fn overlay_ready(state: State<'_, OverlayState>, id: String) -> Result<bool, CommandError> {
+    let mut manager = state.0.lock()?;
+    manager.mark_content_ready(&id).map_err(CommandError::from)
+}
+
+/// Longest an overlay can be flashed for
+const MAX_FLASH_DURATION_MS: u64 = 60_000;
+
+/// Raise an overlay above everything, fullscreen apps included, for `duration_ms`.
+/// Hidden and click-through overlays are shown and made clickable for the
+/// duration, then restored.
+#[command]
+pub fn overlay_flash(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+    duration_ms: u64,
+) -> Result<(), CommandError> {
+    let flash_id = {
+        let mut manager = state.0.lock()?;
+        manager.start_flash(&id)?
+    };
+
+    let duration = std::time::Duration::from_millis(duration_ms.min(MAX_FLASH_DURATION_MS));
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(duration).await;
+
+        let state = app.state::<OverlayState>();
+        let Ok(mut manager) = state.0.lock() else {
+            return;
+        };
+        if let Err(e) = manager.end_flash(&id, flash_id) {
+            tracing::warn!("Failed to restore overlay {} after flashing: {}", id, e);
+        }
+    });
+
+    Ok(())
+}
+
+/// Show or hide every overlay except those excluded from bulk toggles,
+/// returning the IDs of the overlays that were changed
+#[command]
+pub fn overlay_set_visible_all(
+    state: State<'_, OverlayState>,
+    visible: bool,
+) -> Result<Vec<String>, CommandError> {
+    let manager = state.0.lock()?;
+    manager.set_visible_all(visible).map_err(CommandError::from)
+}
+
+/// Leave an overlay out of "Show All" / "Hide All"
+#[command]
+pub fn overlay_set_exclude_from_bulk_toggle(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    id: String,
+    excluded: bool,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager.set_exclude_from_bulk_toggle(&id, excluded)?;
+
+    persist_overlay_setting(
+        &app,
+        &id,
+        "excludeFromBulkToggle",
+        serde_json::json!(excluded),
+    )
 }
 
 #[command]
-pub fn list_overlays(state: State<'_, OverlayState>) -> Result<Vec<String>, String> {
-    let manager = state.0.lock().map_err(|e| e.to_string())?;
+pub fn list_overlays(state: State<'_, OverlayState>) -> Result<Vec<String>, CommandError> {
+    let manager = state.0.lock()?;
     Ok(manager
         .list_overlays()
         .iter()
@@ -90,70 +684,413 @@ pub fn list_overlays(state: State<'_, OverlayState>) -> Result<Vec<String>, Stri
         .collect())
 }
 
+/// Register a service provider. Another plugin's provider for the same service is only
+/// replaced with `force`; either way a `yaof:service:conflict` event is emitted.
+/// The provider is unregistered when the calling window is destroyed or reloaded.
 #[command]
 pub fn service_register(
+    app: AppHandle,
+    window: WebviewWindow,
     state: State<'_, OverlayState>,
     service_id: String,
     plugin_id: String,
     schema: serde_json::Value,
-) -> Result<(), String> {
-    let mut manager = state.0.lock().map_err(|e| e.to_string())?;
-    manager
-        .registry_mut()
-        .register_provider(service_id, plugin_id, schema)
+    force: Option<bool>,
+) -> Result<(), CommandError> {
+    let force = force.unwrap_or(false);
+    let mut manager = state.0.lock()?;
+    let registry = manager.registry_mut();
+
+    if let Some(conflict) = registry.provider_conflict(&service_id, &plugin_id, force) {
+        let _ = app.emit(crate::SERVICE_CONFLICT_EVENT, &conflict);
+    }
+
+    registry
+        .register_provider(
+            service_id.clone(),
+            plugin_id,
+            schema,
+            Some(window.label().to_string()),
+            force,
+        )
+        .map_err(CommandError::service)?;
+
+    let _ = app.emit(&crate::service_registered_event(&service_id), &service_id);
+    Ok(())
+}
+
+#[command]
+pub fn service_list_subscribers(
+    state: State<'_, OverlayState>,
+    service_id: String,
+) -> Result<Vec<String>, CommandError> {
+    let manager = state.0.lock()?;
+    Ok(manager.registry().subscribers(&service_id))
+}
+
+#[command]
+pub fn service_list_all_subscriptions(
+    state: State<'_, OverlayState>,
+) -> Result<std::collections::HashMap<String, Vec<String>>, CommandError> {
+    let manager = state.0.lock()?;
+    Ok(manager.registry().all_subscriptions())
+}
+
+#[command]
+pub fn service_list_providers(
+    state: State<'_, OverlayState>,
+) -> Result<Vec<crate::ProviderInfo>, CommandError> {
+    let manager = state.0.lock()?;
+    Ok(manager.registry().list_providers())
+}
+
+#[command]
+pub fn plugin_list(state: State<'_, PluginState>) -> Result<Vec<PluginManifest>, CommandError> {
+    let mut loader = state.0.lock()?;
+    loader.scan_plugins().map_err(CommandError::from)
+}
+
+/// Rescan and list installed plugins with their path, source and native/UI capabilities
+#[command]
+pub fn plugin_get_all(
+    state: State<'_, PluginState>,
+) -> Result<Vec<crate::InstalledPluginInfo>, CommandError> {
+    let mut loader = state.0.lock()?;
+    loader.scan_plugins()?;
+
+    let mut plugins: Vec<crate::InstalledPluginInfo> =
+        loader.list_plugins().into_iter().map(Into::into).collect();
+    plugins.sort_by(|a, b| a.manifest.id.cmp(&b.manifest.id));
+    Ok(plugins)
+}
+
+#[command]
+pub fn plugin_get(
+    state: State<'_, PluginState>,
+    id: String,
+) -> Result<Option<PluginManifest>, CommandError> {
+    let loader = state.0.lock()?;
+    Ok(loader.get_plugin(&id).map(|p| p.manifest.clone()))
+}
+
+/// List plugins the last scan skipped (invalid manifest, incompatible yaof version, ...)
+#[command]
+pub fn plugin_load_errors(
+    state: State<'_, PluginState>,
+) -> Result<Vec<crate::PluginLoadError>, CommandError> {
+    let loader = state.0.lock()?;
+    Ok(loader.load_errors().to_vec())
+}
+
+/// Fetch the plugin index of a registry for browsing, cached locally for offline use.
+/// `refresh` bypasses a still-fresh cache.
+#[command]
+pub async fn registry_fetch_index(
+    url: String,
+    refresh: Option<bool>,
+) -> Result<Vec<crate::RegistryEntry>, CommandError> {
+    crate::plugin::registry::fetch_index(&url, refresh.unwrap_or(false))
+        .await
+        .map_err(CommandError::from)
+}
+
+#[command]
+pub fn plugin_install_local(
+    app: AppHandle,
+    state: State<'_, PluginState>,
+    path: String,
+    symlink: bool,
+) -> Result<PluginManifest, CommandError> {
+    let manifest = {
+        let mut loader = state.0.lock()?;
+        loader.install_local(std::path::Path::new(&path), symlink)?
+    };
+
+    notify_plugins_changed(&app);
+    Ok(manifest)
+}
+
+/// Result of validating a plugin source without installing it
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginValidationResponse {
+    pub manifest: PluginManifest,
+    /// Identifier of the platform the check ran on (e.g. "darwin-arm64")
+    pub platform: String,
+    pub has_native: bool,
+    /// Whether a native library exists for this platform (false when there's no native component)
+    pub native_supported: bool,
+    pub permissions: Vec<String>,
+    pub provides: Vec<String>,
+    pub consumes: Vec<String>,
+}
+
+/// Check a plugin source directory for installation without touching the plugins directory
+#[command]
+pub fn plugin_validate(
+    state: State<'_, PluginState>,
+    path: String,
+) -> Result<PluginValidationResponse, CommandError> {
+    let source_path = std::path::PathBuf::from(&path);
+    let manifest = {
+        let loader = state.0.lock()?;
+        loader.validate_source(&source_path)?
+    };
+
+    // Resolve native libraries against the source directory as if it were installed there
+    let candidate = crate::InstalledPlugin {
+        manifest: manifest.clone(),
+        path: source_path.clone(),
+        source: crate::PluginSource::Local(source_path),
+    };
+
+    Ok(PluginValidationResponse {
+        platform: crate::NativeConfig::current_platform(),
+        has_native: manifest.has_native(),
+        native_supported: candidate.can_load_native(),
+        permissions: manifest.permissions.clone(),
+        provides: manifest.provides.iter().map(|s| s.id.clone()).collect(),
+        consumes: manifest.consumes.clone(),
+        manifest,
+    })
+}
+
+/// Uninstall a plugin: close its overlays and unload its native components first,
+/// so nothing keeps serving from (or mapping) the deleted files
+#[command]
+pub async fn plugin_uninstall(
+    app: AppHandle,
+    state: State<'_, PluginState>,
+    overlay_state: State<'_, OverlayState>,
+    native_state: State<'_, NativePluginState>,
+    id: String,
+) -> Result<(), CommandError> {
+    {
+        let mut manager = overlay_state.0.lock()?;
+        let overlays: Vec<String> = manager
+            .list_overlays()
+            .iter()
+            .filter(|o| o.plugin_id == id)
+            .map(|o| o.config.id.clone())
+            .collect();
+        for overlay_id in overlays {
+            manager.close_overlay(&overlay_id)?;
+        }
+    }
+
+    let unloaded = native_state.0.write().await.unload_plugin_components(&id);
+    if !unloaded.is_empty() {
+        tracing::info!("Unloaded native plugin(s) of {}: {:?}", id, unloaded);
+    }
+
+    {
+        let mut loader = state.0.lock()?;
+        loader.uninstall(&id)?;
+    }
+
+    // The keychain outlives the plugin's files, so clear its secrets out too
+    let plugin_id = id.clone();
+    match tauri::async_runtime::spawn_blocking(move || crate::delete_plugin_secrets(&plugin_id))
+        .await?
+    {
+        Ok(0) => {}
+        Ok(removed) => tracing::info!("Removed {} secret(s) of {}", removed, id),
+        Err(e) => tracing::warn!("Failed to remove the secrets of {}: {}", id, e),
+    }
+
+    // Drop the plugin's services and any schemas only they were using
+    {
+        let mut manager = overlay_state.0.lock()?;
+        let registry = manager.registry_mut();
+        registry.unregister_plugin(&id, &app);
+        registry.clear_validator_cache();
+    }
+
+    notify_plugins_changed(&app);
+    Ok(())
+}
+
+/// Tell the tray and settings UI that the installed plugins changed
+fn notify_plugins_changed(app: &AppHandle) {
+    if let Err(e) = app.emit(crate::PLUGINS_CHANGED_EVENT, ()) {
+        tracing::warn!("Failed to emit plugins changed event: {}", e);
+    }
+}
+
+/// Recompute an installed plugin's file hashes and report any that differ from install time
+#[command]
+pub fn plugin_verify_integrity(
+    state: State<'_, PluginState>,
+    id: String,
+) -> Result<crate::IntegrityReport, CommandError> {
+    let loader = state.0.lock()?;
+    loader.verify_integrity(&id).map_err(CommandError::from)
+}
+
+#[command]
+pub fn plugin_reveal_in_explorer(
+    state: State<'_, PluginState>,
+    plugin_id: String,
+) -> Result<(), CommandError> {
+    let loader = state.0.lock()?;
+    let dir = loader.resolve_plugin_dir(&plugin_id)?;
+    open_in_file_manager(&dir).map_err(CommandError::from)
+}
+
+/// Read a file from a plugin's directory, delivered to JS as an `ArrayBuffer`.
+/// The path is relative to the plugin root and can't escape it.
+/// Not part of the default permission set; the runtime grants it to the settings window.
+#[command]
+pub fn plugin_read_file(
+    state: State<'_, PluginState>,
+    plugin_id: String,
+    relative_path: String,
+) -> Result<tauri::ipc::Response, CommandError> {
+    let loader = state.0.lock()?;
+    let data = loader.read_plugin_file(&plugin_id, &relative_path)?;
+    Ok(tauri::ipc::Response::new(data))
 }
 
-#[command]
-pub fn service_list_providers(
-    state: State<'_, OverlayState>,
-) -> Result<Vec<crate::ProviderInfo>, String> {
-    let manager = state.0.lock().map_err(|e| e.to_string())?;
-    Ok(manager.registry().list_providers())
+/// List a plugin's files relative to its directory.
+/// Not part of the default permission set; the runtime grants it to the settings window.
+#[command]
+pub fn plugin_list_files(
+    state: State<'_, PluginState>,
+    plugin_id: String,
+) -> Result<Vec<String>, CommandError> {
+    let loader = state.0.lock()?;
+    loader
+        .list_plugin_files(&plugin_id)
+        .map_err(CommandError::from)
+}
+
+/// URL schemes overlays may hand to the OS opener
+const EXTERNAL_URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Open a link in the default browser (or mail client) instead of navigating the overlay
+#[command]
+pub fn open_external(url: String) -> Result<(), CommandError> {
+    let parsed = tauri::Url::parse(&url)
+        .map_err(|e| CommandError::invalid_argument(format!("Invalid URL {}: {}", url, e)))?;
+    if !EXTERNAL_URL_SCHEMES.contains(&parsed.scheme()) {
+        return Err(CommandError::invalid_argument(format!(
+            "Refusing to open {} URL, only {} are allowed",
+            parsed.scheme(),
+            EXTERNAL_URL_SCHEMES.join("/")
+        )));
+    }
+
+    open_with_os(parsed.as_str()).map_err(CommandError::from)
+}
+
+/// Open a URL with the platform's default handler
+fn open_with_os(target: &str) -> Result<(), crate::Error> {
+    #[cfg(target_os = "macos")]
+    std::process::Command::new("open").arg(target).spawn()?;
+    // Not `cmd /C start`, which would interpret `&` in the URL as a command separator
+    #[cfg(target_os = "windows")]
+    std::process::Command::new("rundll32")
+        .args(["url.dll,FileProtocolHandler", target])
+        .spawn()?;
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    std::process::Command::new("xdg-open").arg(target).spawn()?;
+
+    Ok(())
+}
+
+/// Open a directory with the platform file manager
+fn open_in_file_manager(path: &std::path::Path) -> Result<(), crate::Error> {
+    #[cfg(target_os = "macos")]
+    let program = "open";
+    #[cfg(target_os = "windows")]
+    let program = "explorer";
+    // Explorer doesn't understand the verbatim prefix produced by canonicalize
+    #[cfg(target_os = "windows")]
+    let path = std::path::PathBuf::from(path.to_string_lossy().trim_start_matches(r"\\?\"));
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let program = "xdg-open";
+
+    std::process::Command::new(program).arg(path).spawn()?;
+    Ok(())
+}
+
+/// Subscribe the calling window to a service. With `replay`, the last value
+/// broadcast on the service is sent to the window right away.
+#[command]
+pub fn service_subscribe(
+    state: State<'_, OverlayState>,
+    window: WebviewWindow,
+    provider_id: String,
+    replay: Option<bool>,
+) -> Result<(), CommandError> {
+    let last_value = {
+        let mut manager = state.0.lock()?;
+        let registry = manager.registry_mut();
+        registry
+            .subscribe(&provider_id, window.label())
+            .map_err(CommandError::service)?;
+        registry.last_value(&provider_id).cloned()
+    };
+
+    if let (Some(true), Some(value)) = (replay, last_value) {
+        let event_name = format!("yaof:service:{}", provider_id);
+        window.emit_to(window.label(), &event_name, value)?;
+    }
+
+    Ok(())
 }
 
+/// Get the last value broadcast on a service, if any
 #[command]
-pub fn plugin_list(state: State<'_, PluginState>) -> Result<Vec<PluginManifest>, String> {
-    let mut loader = state.0.lock().map_err(|e| e.to_string())?;
-    loader.scan_plugins().map_err(|e| e.to_string())
+pub fn service_get_last_value(
+    state: State<'_, OverlayState>,
+    service_id: String,
+) -> Result<Option<serde_json::Value>, CommandError> {
+    let manager = state.0.lock()?;
+    Ok(manager.registry().last_value(&service_id).cloned())
 }
 
+/// Get the latest raw payload emitted on a service, delivered to JS as an `ArrayBuffer`
 #[command]
-pub fn plugin_get(
-    state: State<'_, PluginState>,
-    id: String,
-) -> Result<Option<PluginManifest>, String> {
-    let loader = state.0.lock().map_err(|e| e.to_string())?;
-    Ok(loader.get_plugin(&id).map(|p| p.manifest.clone()))
+pub fn service_get_binary(
+    state: State<'_, OverlayState>,
+    service_id: String,
+) -> Result<tauri::ipc::Response, CommandError> {
+    let manager = state.0.lock()?;
+    let data = manager
+        .registry()
+        .binary_value(&service_id)
+        .ok_or_else(|| {
+            CommandError::not_found(format!("Service {} has no binary payload", service_id))
+        })?;
+    Ok(tauri::ipc::Response::new(data.as_ref().clone()))
 }
 
+/// Get the external service bridge settings, including its token.
+/// This command is not part of the default permission set and must be granted explicitly.
 #[command]
-pub fn plugin_install_local(
-    state: State<'_, PluginState>,
-    path: String,
-    symlink: bool,
-) -> Result<PluginManifest, String> {
-    let mut loader = state.0.lock().map_err(|e| e.to_string())?;
-    loader
-        .install_local(std::path::Path::new(&path), symlink)
-        .map_err(|e| e.to_string())
+pub fn service_bridge_status(
+    app: AppHandle,
+) -> Result<crate::services::bridge::ServiceBridgeStatus, CommandError> {
+    crate::services::bridge::status(&app).map_err(CommandError::from)
 }
 
+/// Enable or disable the local socket bridge for external programs.
+/// This command is not part of the default permission set and must be granted explicitly.
 #[command]
-pub fn plugin_uninstall(state: State<'_, PluginState>, id: String) -> Result<(), String> {
-    let mut loader = state.0.lock().map_err(|e| e.to_string())?;
-    loader.uninstall(&id).map_err(|e| e.to_string())
+pub fn service_bridge_configure(
+    app: AppHandle,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<(), CommandError> {
+    crate::services::bridge::configure(&app, enabled, port).map_err(CommandError::from)
 }
 
+/// Generate a new bridge token, returning it.
+/// This command is not part of the default permission set and must be granted explicitly.
 #[command]
-pub fn service_subscribe(
-    state: State<'_, OverlayState>,
-    window: WebviewWindow,
-    provider_id: String,
-) -> Result<(), String> {
-    let mut manager = state.0.lock().map_err(|e| e.to_string())?;
-    manager
-        .registry_mut()
-        .subscribe(&provider_id, window.label())
+pub fn service_bridge_reset_token(app: AppHandle) -> Result<String, CommandError> {
+    crate::services::bridge::reset_token(&app).map_err(CommandError::from)
 }
 
 #[command]
@@ -161,8 +1098,8 @@ pub fn service_unsubscribe(
     state: State<'_, OverlayState>,
     window: WebviewWindow,
     provider_id: String,
-) -> Result<(), String> {
-    let mut manager = state.0.lock().map_err(|e| e.to_string())?;
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
     manager
         .registry_mut()
         .unsubscribe(&provider_id, window.label());
@@ -171,11 +1108,14 @@ pub fn service_unsubscribe(
 
 #[command]
 pub fn service_unregister(
+    app: AppHandle,
     state: State<'_, OverlayState>,
     service_id: String,
-) -> Result<(), String> {
-    let mut manager = state.0.lock().map_err(|e| e.to_string())?;
-    manager.registry_mut().unregister_provider(&service_id);
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager
+        .registry_mut()
+        .unregister_provider(&service_id, &app);
     Ok(())
 }
 
@@ -185,25 +1125,204 @@ pub fn service_broadcast(
     app: AppHandle,
     service_id: String,
     data: serde_json::Value,
-) -> Result<(), String> {
-    let manager = state.0.lock().map_err(|e| e.to_string())?;
-    manager.registry().broadcast(&service_id, data, &app)
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager
+        .registry_mut()
+        .broadcast(&service_id, data, &app)
+        .map_err(CommandError::service)
+}
+
+/// Broadcast several service updates with a single IPC call
+#[command]
+pub fn service_broadcast_batch(
+    state: State<'_, OverlayState>,
+    app: AppHandle,
+    updates: Vec<(String, serde_json::Value)>,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager
+        .registry_mut()
+        .broadcast_batch(updates, &app)
+        .map_err(CommandError::service)
+}
+
+/// Set how many broadcasts per second each service may emit before further ones
+/// are coalesced into the latest value (0 disables the limit). Not in the default
+/// permission set, so overlays can't turn the limit off for everyone.
+#[command]
+pub fn service_set_rate_limit(
+    state: State<'_, OverlayState>,
+    max_per_sec: u32,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.lock()?;
+    manager.registry_mut().broadcast_rate_limit = max_per_sec;
+    Ok(())
+}
+
+/// Default time to wait for a provider to answer a service request
+const SERVICE_REQUEST_TIMEOUT_MS: u64 = 5000;
+
+/// Send a one-shot request to a service's provider and wait for its reply.
+///
+/// Native providers receive it through `handle_message`, frontend providers
+/// through a `yaof:service-request:{service_id}` event answered with `service_respond`.
+#[command]
+pub async fn service_request(
+    app: AppHandle,
+    state: State<'_, OverlayState>,
+    native_state: State<'_, NativePluginState>,
+    service_id: String,
+    method: String,
+    params: Option<serde_json::Value>,
+    timeout_ms: Option<u64>,
+) -> Result<serde_json::Value, CommandError> {
+    let (provider, request, receiver) = {
+        let mut manager = state.0.lock()?;
+        let provider = manager
+            .registry()
+            .provider(&service_id)
+            .map(|p| p.plugin_id.clone())
+            .ok_or_else(|| {
+                CommandError::not_found(format!(
+                    "No provider registered for service {}",
+                    service_id
+                ))
+            })?;
+        let (request, receiver) = manager
+            .registry_mut()
+            .begin_request(
+                &service_id,
+                method,
+                params.unwrap_or(serde_json::Value::Null),
+            )
+            .map_err(CommandError::service)?;
+        (provider, request, receiver)
+    };
+
+    let request_id = request.request_id.clone();
+    let cancel = |error: CommandError| {
+        if let Ok(mut manager) = state.0.lock() {
+            manager.registry_mut().cancel_request(&request_id);
+        }
+        error
+    };
+
+    let is_native = native_state.0.read().await.is_loaded(&provider);
+    if is_native {
+        let payload = serde_json::to_vec(&request).map_err(|e| cancel(e.into()))?;
+        let result = native_state
+            .0
+            .write()
+            .await
+            .send_message(&provider, crate::SERVICE_REQUEST_MESSAGE_TYPE, &payload)
+            .map_err(|e| cancel(e.into()))?;
+        if result != 0 {
+            return Err(cancel(CommandError::service(format!(
+                "Plugin {} rejected request to {} ({})",
+                provider, service_id, result
+            ))));
+        }
+    } else {
+        app.emit(&request.event_name(), &request)
+            .map_err(|e| cancel(e.into()))?;
+    }
+
+    let timeout =
+        tokio::time::Duration::from_millis(timeout_ms.unwrap_or(SERVICE_REQUEST_TIMEOUT_MS));
+    match tokio::time::timeout(timeout, receiver).await {
+        Ok(Ok(response)) => response.map_err(CommandError::service),
+        Ok(Err(_)) => Err(CommandError::service(format!(
+            "Request to {} was dropped",
+            service_id
+        ))),
+        Err(_) => Err(cancel(CommandError::new(
+            "timeout",
+            format!(
+                "Request to {} timed out after {}ms",
+                service_id,
+                timeout.as_millis()
+            ),
+        ))),
+    }
+}
+
+/// Answer a service request received through a `yaof:service-request:*` event.
+/// Only an overlay of the provider's plugin may answer.
+#[command]
+pub fn service_respond(
+    window: WebviewWindow,
+    state: State<'_, OverlayState>,
+    request_id: String,
+    result: Option<serde_json::Value>,
+    error: Option<String>,
+) -> Result<(), CommandError> {
+    let response = match error {
+        Some(error) => Err(error),
+        None => Ok(result.unwrap_or(serde_json::Value::Null)),
+    };
+
+    let mut manager = state.0.lock()?;
+    let responder = manager
+        .get_overlay(window.label())
+        .map(|o| o.config.plugin_id.clone())
+        .ok_or_else(|| {
+            CommandError::permission_denied(format!(
+                "{} is not an overlay and can't answer service requests",
+                window.label()
+            ))
+        })?;
+
+    manager
+        .registry_mut()
+        .resolve_request(&request_id, &responder, response)
+        .map_err(CommandError::service)
 }
 
 // ============================================
 // Plugin Settings Commands
 // ============================================
 
+/// A plugin's settings schema together with the defaults it declares
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginSettingsSchemaResponse {
+    #[serde(flatten)]
+    pub config: crate::PluginSettingsConfig,
+    pub defaults: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Get everything needed to render a plugin's settings form, None if it has no settings
+#[command]
+pub fn plugin_settings_get_schema(
+    state: State<'_, PluginState>,
+    plugin_id: String,
+) -> Result<Option<PluginSettingsSchemaResponse>, CommandError> {
+    let loader = state.0.lock()?;
+    let plugin = loader
+        .get_plugin(&plugin_id)
+        .ok_or_else(|| crate::Error::PluginNotFound(plugin_id.clone()))?;
+
+    Ok(plugin
+        .manifest
+        .settings
+        .clone()
+        .map(|config| PluginSettingsSchemaResponse {
+            defaults: config.defaults(),
+            config,
+        }))
+}
+
 #[command]
 pub fn plugin_settings_get(
     app: AppHandle,
     plugin_id: String,
     key: String,
-) -> Result<Option<serde_json::Value>, String> {
+) -> Result<Option<serde_json::Value>, CommandError> {
     use tauri_plugin_store::StoreExt;
 
-    let store_path = format!("{}-settings.json", plugin_id);
-    let store = app.store(&store_path).map_err(|e| e.to_string())?;
+    let store_path = crate::plugin_store_path(&app, &format!("{}-settings.json", plugin_id));
+    let store = app.store(&store_path)?;
 
     Ok(store.get(&key))
 }
@@ -214,27 +1333,29 @@ pub fn plugin_settings_set(
     plugin_id: String,
     key: String,
     value: serde_json::Value,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     use tauri_plugin_store::StoreExt;
 
-    let store_path = format!("{}-settings.json", plugin_id);
-    let store = app.store(&store_path).map_err(|e| e.to_string())?;
+    let store_path = crate::plugin_store_path(&app, &format!("{}-settings.json", plugin_id));
+    let store = app.store(&store_path)?;
 
     store.set(&key, value);
-    store.save().map_err(|e| e.to_string())?;
+    store.save()?;
 
+    notify_plugin_settings_changed(&app, &plugin_id, Some(vec![key]));
     Ok(())
 }
 
+/// All of a plugin's stored settings. Secrets live in the OS keychain and are never included
 #[command]
 pub fn plugin_settings_get_all(
     app: AppHandle,
     plugin_id: String,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, CommandError> {
     use tauri_plugin_store::StoreExt;
 
-    let store_path = format!("{}-settings.json", plugin_id);
-    let store = app.store(&store_path).map_err(|e| e.to_string())?;
+    let store_path = crate::plugin_store_path(&app, &format!("{}-settings.json", plugin_id));
+    let store = app.store(&store_path)?;
 
     // Get all keys and values
     let mut result = serde_json::Map::new();
@@ -252,19 +1373,21 @@ pub fn plugin_settings_set_all(
     app: AppHandle,
     plugin_id: String,
     values: serde_json::Value,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     use tauri_plugin_store::StoreExt;
 
-    let store_path = format!("{}-settings.json", plugin_id);
-    let store = app.store(&store_path).map_err(|e| e.to_string())?;
+    let store_path = crate::plugin_store_path(&app, &format!("{}-settings.json", plugin_id));
+    let store = app.store(&store_path)?;
 
     if let serde_json::Value::Object(map) = values {
+        let keys: Vec<String> = map.keys().cloned().collect();
         for (key, value) in map {
             store.set(&key, value);
         }
-        store.save().map_err(|e| e.to_string())?;
+        store.save()?;
+        notify_plugin_settings_changed(&app, &plugin_id, Some(keys));
     } else {
-        return Err("values must be an object".to_string());
+        return Err(CommandError::invalid_argument("values must be an object"));
     }
 
     Ok(())
@@ -275,31 +1398,335 @@ pub fn plugin_settings_delete(
     app: AppHandle,
     plugin_id: String,
     key: String,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     use tauri_plugin_store::StoreExt;
 
-    let store_path = format!("{}-settings.json", plugin_id);
-    let store = app.store(&store_path).map_err(|e| e.to_string())?;
+    let store_path = crate::plugin_store_path(&app, &format!("{}-settings.json", plugin_id));
+    let store = app.store(&store_path)?;
 
     let existed = store.delete(&key);
-    store.save().map_err(|e| e.to_string())?;
+    store.save()?;
 
+    if existed {
+        notify_plugin_settings_changed(&app, &plugin_id, Some(vec![key]));
+    }
     Ok(existed)
 }
 
 #[command]
-pub fn plugin_settings_clear(app: AppHandle, plugin_id: String) -> Result<(), String> {
+pub fn plugin_settings_clear(app: AppHandle, plugin_id: String) -> Result<(), CommandError> {
     use tauri_plugin_store::StoreExt;
 
-    let store_path = format!("{}-settings.json", plugin_id);
-    let store = app.store(&store_path).map_err(|e| e.to_string())?;
+    let store_path = crate::plugin_store_path(&app, &format!("{}-settings.json", plugin_id));
+    let store = app.store(&store_path)?;
 
     store.clear();
-    store.save().map_err(|e| e.to_string())?;
+    store.save()?;
+
+    notify_plugin_settings_changed(&app, &plugin_id, None);
+    Ok(())
+}
+
+/// Resolve where a plugin store file (e.g. `my-plugin-settings.json`) lives, for
+/// frontends opening it with `@tauri-apps/plugin-store`
+#[command]
+pub fn plugin_store_path(app: AppHandle, name: String) -> Result<String, CommandError> {
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(CommandError::invalid_argument(format!(
+            "Invalid store name: {}",
+            name
+        )));
+    }
+
+    Ok(crate::plugin_store_path(&app, &name)
+        .to_string_lossy()
+        .to_string())
+}
 
+/// Store a credential for a plugin in the OS keychain rather than its settings file.
+/// Only the plugin's own windows can reach its secrets.
+#[command]
+pub async fn plugin_secret_set(
+    window: WebviewWindow,
+    state: State<'_, OverlayState>,
+    plugin_id: String,
+    key: String,
+    value: String,
+) -> Result<(), CommandError> {
+    check_secret_access(&window, &state, &plugin_id)?;
+    // Keychain calls can block on an access prompt, keep them off the main thread
+    tauri::async_runtime::spawn_blocking(move || crate::set_secret(&plugin_id, &key, &value))
+        .await??;
     Ok(())
 }
 
+/// Read a credential stored with `plugin_secret_set`, None if it was never set
+#[command]
+pub async fn plugin_secret_get(
+    window: WebviewWindow,
+    state: State<'_, OverlayState>,
+    plugin_id: String,
+    key: String,
+) -> Result<Option<String>, CommandError> {
+    check_secret_access(&window, &state, &plugin_id)?;
+    let value =
+        tauri::async_runtime::spawn_blocking(move || crate::get_secret(&plugin_id, &key)).await??;
+    Ok(value)
+}
+
+/// Remove a plugin's credential from the keychain, returns whether it existed
+#[command]
+pub async fn plugin_secret_delete(
+    window: WebviewWindow,
+    state: State<'_, OverlayState>,
+    plugin_id: String,
+    key: String,
+) -> Result<bool, CommandError> {
+    check_secret_access(&window, &state, &plugin_id)?;
+    let existed =
+        tauri::async_runtime::spawn_blocking(move || crate::delete_secret(&plugin_id, &key))
+            .await??;
+    Ok(existed)
+}
+
+/// Reject secret access from windows that don't belong to `plugin_id`.
+/// Components (`<plugin-id>:<component>`) count as their plugin.
+fn check_secret_access(
+    window: &WebviewWindow,
+    state: &State<'_, OverlayState>,
+    plugin_id: &str,
+) -> Result<(), CommandError> {
+    let base_id = |id: &str| id.split(':').next().unwrap_or(id).to_string();
+
+    let caller = match state.0.lock()?.get_overlay(window.label()) {
+        Some(overlay) => Some(overlay.config.plugin_id.clone()),
+        // Not an overlay, fall back to the plugin whose assets the window loaded
+        None => window.url().ok().and_then(|url| {
+            (url.scheme() == "yaof-plugin")
+                .then(|| url.host_str().map(str::to_string))
+                .flatten()
+        }),
+    };
+
+    match caller {
+        Some(caller) if base_id(&caller) == base_id(plugin_id) => Ok(()),
+        Some(caller) => Err(CommandError::permission_denied(format!(
+            "{} can't access the secrets of {}",
+            caller, plugin_id
+        ))),
+        None => Err(CommandError::permission_denied(format!(
+            "{} doesn't belong to a plugin and can't access secrets",
+            window.label()
+        ))),
+    }
+}
+
+/// Tell a plugin's overlays and native components that its settings were written,
+/// so they can pick the new values up without a restart
+fn notify_plugin_settings_changed(app: &AppHandle, plugin_id: &str, keys: Option<Vec<String>>) {
+    let change = crate::PluginSettingsChanged {
+        plugin_id: plugin_id.to_string(),
+        keys,
+    };
+
+    if let Err(e) = app.emit(&crate::plugin_settings_changed_event(plugin_id), &change) {
+        tracing::warn!("Failed to emit settings change for {}: {}", plugin_id, e);
+    }
+
+    // Native plugins are behind an async lock, don't block the command on it
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let Some(native_state) = app.try_state::<NativePluginState>() else {
+            return;
+        };
+        let Ok(payload) = serde_json::to_vec(&change) else {
+            return;
+        };
+        native_state.0.write().await.send_message_to_components(
+            &change.plugin_id,
+            crate::SETTINGS_CHANGED_MESSAGE_TYPE,
+            &payload,
+        );
+    });
+}
+
+/// Result of re-running plugin discovery and autostart
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshReport {
+    pub native_plugins_loaded: Vec<String>,
+    pub overlays_spawned: Vec<String>,
+    pub overlays_closed: Vec<String>,
+}
+
+/// Rescan installed plugins, load new native components, spawn newly enabled
+/// overlays and close overlays of uninstalled plugins - without an app restart
+#[command]
+pub async fn refresh_plugins(
+    app: AppHandle,
+    plugin_state: State<'_, PluginState>,
+    native_state: State<'_, NativePluginState>,
+) -> Result<RefreshReport, CommandError> {
+    let native_plugins_loaded = {
+        let (mut native_manager, mut loader) =
+            native_state.0.write_with_loader(&plugin_state.0).await;
+        native_manager.load_from_installed_plugins(&mut loader)?
+    };
+
+    let (overlays_spawned, overlays_closed) = crate::AutostartManager::refresh_overlays(&app)
+        .map_err(|e| CommandError::new("internal", e))?;
+    notify_plugins_changed(&app);
+
+    Ok(RefreshReport {
+        native_plugins_loaded,
+        overlays_spawned,
+        overlays_closed,
+    })
+}
+
+// ============================================
+// Status Commands
+// ============================================
+
+/// Summary of an installed plugin for status reports
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledPluginStatus {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub path: String,
+}
+
+/// Snapshot of the runtime state for troubleshooting
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusReport {
+    pub installed_plugins: Vec<InstalledPluginStatus>,
+    pub native_plugins: Vec<NativePluginInfoResponse>,
+    pub overlays: Vec<String>,
+    pub service_providers: Vec<crate::ProviderInfo>,
+    pub system_tick_interval_ms: u64,
+    /// Whether yaof was started in safe mode (no native plugins, no autostarted overlays)
+    pub safe_mode: bool,
+    /// Whether startup finished (see the `yaof:ready` event)
+    pub ready: bool,
+    /// Whether the Accessibility permission window detection needs is granted (macOS)
+    pub accessibility_trusted: bool,
+}
+
+/// Relaunch the whole app in the current mode, shutting native plugins down cleanly first.
+/// Callers are expected to confirm with the user beforehand. Not in the default
+/// permission set, so overlays can't restart the app on their own.
+#[command]
+pub async fn restart_app(
+    app: AppHandle,
+    native_state: State<'_, NativePluginState>,
+) -> Result<(), CommandError> {
+    tracing::info!("Restarting yaof");
+
+    // Exiting from here requests an exit, so RunEvent::Exit runs (and finds nothing
+    // left to shut down). If that request fails it exits directly without one, so
+    // shut plugins down here either way.
+    native_state.0.write().await.shutdown_all();
+
+    crate::relaunch(&app, crate::is_safe_mode()).map_err(CommandError::from)
+}
+
+/// Open the settings window, e.g. from a widget's gear button
+#[command]
+pub fn open_settings<R: Runtime>(app: AppHandle<R>) -> Result<(), CommandError> {
+    crate::open_settings_window(&app).map_err(CommandError::from)
+}
+
+/// Bring the settings window to the front, opening it if needed
+#[command]
+pub fn focus_settings<R: Runtime>(app: AppHandle<R>) -> Result<(), CommandError> {
+    crate::open_settings_window(&app).map_err(CommandError::from)
+}
+
+/// Hide the settings window if it is open
+#[command]
+pub fn close_settings<R: Runtime>(app: AppHandle<R>) -> Result<(), CommandError> {
+    crate::hide_settings_window(&app).map_err(CommandError::from)
+}
+
+#[command]
+pub async fn yaof_status(
+    plugin_state: State<'_, PluginState>,
+    overlay_state: State<'_, OverlayState>,
+    native_state: State<'_, NativePluginState>,
+    system_state: State<'_, crate::SystemServiceState>,
+) -> Result<StatusReport, CommandError> {
+    // Collect everything behind std mutexes before awaiting the native manager
+    let installed_plugins = {
+        let loader = plugin_state.0.lock()?;
+        loader
+            .list_plugins()
+            .iter()
+            .map(|p| InstalledPluginStatus {
+                id: p.manifest.id.clone(),
+                name: p.manifest.name.clone(),
+                version: p.manifest.version.clone(),
+                path: p.path.to_string_lossy().to_string(),
+            })
+            .collect()
+    };
+
+    let (overlays, service_providers) = {
+        let manager = overlay_state.0.lock()?;
+        let overlays = manager
+            .list_overlays()
+            .iter()
+            .map(|o| o.config.id.clone())
+            .collect();
+        (overlays, manager.registry().list_providers())
+    };
+
+    let native_plugins = {
+        let manager = native_state.0.read().await;
+        manager
+            .list_plugins()
+            .iter()
+            .map(|p| NativePluginInfoResponse {
+                id: p.id.clone(),
+                path: p.path.to_string_lossy().to_string(),
+                tick_interval_ms: p.tick_interval_ms,
+                tick_disabled: manager.is_tick_disabled(&p.id),
+            })
+            .collect()
+    };
+
+    Ok(StatusReport {
+        installed_plugins,
+        native_plugins,
+        overlays,
+        service_providers,
+        system_tick_interval_ms: system_state.0.tick_interval_ms(),
+        safe_mode: crate::is_safe_mode(),
+        ready: crate::is_ready(),
+        accessibility_trusted: crate::services::system::accessibility::is_trusted(),
+    })
+}
+
+// ============================================
+// Logging Commands
+// ============================================
+
+/// Change the active log filter, using the same syntax as the `YAOF_LOG` env var
+#[command]
+pub fn yaof_set_log_level(level: String) -> Result<(), CommandError> {
+    crate::logging::set_level(&level).map_err(CommandError::from)
+}
+
+/// Get the path of the log file that native plugin logs are written to
+#[command]
+pub fn get_log_path() -> Result<String, CommandError> {
+    crate::logging::log_path()
+        .map(|p| p.to_string_lossy().to_string())
+        .ok_or_else(|| CommandError::not_found("Could not find home directory"))
+}
+
 // ============================================
 // Native Plugin Commands
 // ============================================
@@ -312,12 +1739,14 @@ pub struct NativePluginInfoResponse {
     pub id: String,
     pub path: String,
     pub tick_interval_ms: u64,
+    /// Ticking was disabled after repeated tick errors
+    pub tick_disabled: bool,
 }
 
 #[command]
 pub async fn native_plugin_list(
     state: State<'_, NativePluginState>,
-) -> Result<Vec<NativePluginInfoResponse>, String> {
+) -> Result<Vec<NativePluginInfoResponse>, CommandError> {
     let manager = state.0.read().await;
     let plugins = manager
         .list_plugins()
@@ -326,6 +1755,7 @@ pub async fn native_plugin_list(
             id: p.id.clone(),
             path: p.path.to_string_lossy().to_string(),
             tick_interval_ms: p.tick_interval_ms,
+            tick_disabled: manager.is_tick_disabled(&p.id),
         })
         .collect();
     Ok(plugins)
@@ -335,18 +1765,229 @@ pub async fn native_plugin_list(
 pub async fn native_plugin_load(
     state: State<'_, NativePluginState>,
     path: String,
-) -> Result<String, String> {
+) -> Result<String, CommandError> {
     let mut manager = state.0.write().await;
     manager
         .load_plugin(std::path::Path::new(&path))
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
+}
+
+/// Load a native library from its build output as a dev plugin, replacing the build
+/// loaded from that path before. In dev mode it is reloaded whenever it is rebuilt.
+#[command]
+pub async fn native_plugin_load_dev(
+    state: State<'_, NativePluginState>,
+    path: String,
+) -> Result<String, CommandError> {
+    let mut manager = state.0.write().await;
+    manager
+        .load_dev_plugin(std::path::Path::new(&path))
+        .map_err(CommandError::from)
+}
+
+/// Send a message to every loaded native plugin (e.g. a theme change).
+/// Returns each plugin's result code, 0 meaning it handled the message.
+/// Message types reserved for the host (`__`-prefixed, `tray:`) are refused.
+/// This command is not part of the default permission set and must be granted explicitly.
+#[command]
+pub async fn native_plugin_broadcast(
+    state: State<'_, NativePluginState>,
+    msg_type: String,
+    payload: serde_json::Value,
+) -> Result<std::collections::HashMap<String, i32>, CommandError> {
+    if crate::is_reserved_message_type(&msg_type) {
+        return Err(CommandError::invalid_argument(format!(
+            "{} is reserved for messages from the host",
+            msg_type
+        )));
+    }
+
+    let payload = serde_json::to_vec(&payload)?;
+    let mut manager = state.0.write().await;
+    Ok(manager.broadcast_message(&msg_type, &payload))
 }
 
 #[command]
 pub async fn native_plugin_unload(
     state: State<'_, NativePluginState>,
     plugin_id: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
+    let mut manager = state.0.write().await;
+    manager
+        .unload_plugin(&plugin_id)
+        .map_err(CommandError::from)
+}
+
+/// Get whether unsigned native plugins are refused and which keys are trusted
+#[command]
+pub fn native_plugin_signing_policy(app: AppHandle) -> Result<crate::SigningPolicy, CommandError> {
+    crate::SigningPolicy::load(&app).map_err(CommandError::from)
+}
+
+/// Refuse to load native plugins not signed by a trusted key.
+/// This command is not part of the default permission set and must be granted explicitly.
+#[command]
+pub fn native_plugin_set_require_signed(app: AppHandle, enabled: bool) -> Result<(), CommandError> {
+    crate::SigningPolicy::set_require_signed(&app, enabled).map_err(CommandError::from)
+}
+
+/// Trust a base64-encoded ed25519 public key for native plugin signatures.
+/// This command is not part of the default permission set and must be granted explicitly.
+#[command]
+pub fn native_plugin_add_trusted_key(
+    app: AppHandle,
+    public_key: String,
+) -> Result<(), CommandError> {
+    crate::SigningPolicy::add_trusted_key(&app, &public_key).map_err(CommandError::from)
+}
+
+/// Stop trusting a native plugin signing key.
+/// This command is not part of the default permission set and must be granted explicitly.
+#[command]
+pub fn native_plugin_remove_trusted_key(
+    app: AppHandle,
+    public_key: String,
+) -> Result<(), CommandError> {
+    crate::SigningPolicy::remove_trusted_key(&app, &public_key).map_err(CommandError::from)
+}
+
+// ============================================
+// System Service Commands
+// ============================================
+
+use crate::SystemServiceState;
+
+/// Enable or disable mirroring of OS notifications to `yaof:system:notification`.
+/// This command is not part of the default permission set and must be granted explicitly.
+#[command]
+pub async fn system_notifications_set_enabled(
+    state: State<'_, SystemServiceState>,
+    enabled: bool,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.write().await;
+    manager.set_notifications_enabled(enabled);
+    Ok(())
+}
+
+#[command]
+pub async fn system_notifications_enabled(
+    state: State<'_, SystemServiceState>,
+) -> Result<bool, CommandError> {
+    let manager = state.0.read().await;
+    Ok(manager.notifications_enabled())
+}
+
+#[command]
+pub async fn system_idle_set_threshold(
+    state: State<'_, SystemServiceState>,
+    threshold_secs: u64,
+) -> Result<(), CommandError> {
+    let mut manager = state.0.write().await;
+    manager.set_idle_threshold_secs(threshold_secs);
+    Ok(())
+}
+
+#[command]
+pub async fn system_idle_threshold(
+    state: State<'_, SystemServiceState>,
+) -> Result<u64, CommandError> {
+    let manager = state.0.read().await;
+    Ok(manager.idle_threshold_secs())
+}
+
+/// Get the artwork of the current track, which status events only carry when it changes
+#[command]
+pub async fn system_media_artwork(
+    state: State<'_, SystemServiceState>,
+) -> Result<Option<String>, CommandError> {
+    let manager = state.0.read().await;
+    Ok(manager.media_artwork())
+}
+
+/// Change how often system services poll and emit, clamped between 100ms and 60s.
+/// Not in the default permission set, a short interval costs CPU for every overlay.
+/// Returns the interval actually used.
+#[command]
+pub fn system_set_tick_interval(
+    state: State<'_, SystemServiceState>,
+    interval_ms: u64,
+) -> Result<u64, CommandError> {
+    Ok(state.0.set_tick_interval_ms(interval_ms))
+}
+
+#[command]
+pub fn system_get_tick_interval(state: State<'_, SystemServiceState>) -> Result<u64, CommandError> {
+    Ok(state.0.tick_interval_ms())
+}
+
+/// Get how enabled overlays are staggered when spawned on startup
+#[command]
+pub fn autostart_get_stagger(app: AppHandle) -> Result<crate::AutostartSettings, CommandError> {
+    Ok(crate::AutostartSettings::load(&app)?)
+}
+
+/// Spawn enabled overlays `batch_size` at a time on startup, waiting `batch_delay_ms`
+/// between batches. Takes effect on next launch. Returns the settings actually stored.
+#[command]
+pub fn autostart_set_stagger(
+    app: AppHandle,
+    batch_size: usize,
+    batch_delay_ms: u64,
+) -> Result<crate::AutostartSettings, CommandError> {
+    let settings = crate::AutostartSettings {
+        batch_size: batch_size.max(1),
+        batch_delay_ms,
+    };
+    settings.save(&app)?;
+    Ok(settings)
+}
+
+/// Terminate a process.
+/// This command is not part of the default permission set and must be granted explicitly.
+#[command]
+pub async fn process_kill(pid: u32) -> Result<(), CommandError> {
+    crate::services::system::kill_process(pid).map_err(CommandError::service)
+}
+
+/// Bring a process's main window to the front
+#[command]
+pub async fn process_focus(pid: u32) -> Result<(), CommandError> {
+    crate::services::system::focus_process(pid).map_err(CommandError::service)
+}
+
+/// Get details of every network interface: addresses, default gateway and WiFi SSID.
+/// `yaof:system:network` only carries a summary, this is for the full picture.
+#[command]
+pub async fn network_get_interfaces() -> Result<Vec<InterfaceInfo>, CommandError> {
+    // Gateway and SSID lookups shell out, keep them off the async runtime
+    let interfaces =
+        tauri::async_runtime::spawn_blocking(crate::services::system::get_interfaces).await?;
+    Ok(interfaces)
+}
+
+/// Check whether yaof has the Accessibility permission the window and desktop
+/// services need on macOS. Always true elsewhere.
+#[command]
+pub fn check_accessibility_permission() -> Result<bool, CommandError> {
+    Ok(crate::services::system::accessibility::is_trusted())
+}
+
+/// Open the System Settings pane where the Accessibility permission is granted
+#[command]
+pub fn request_accessibility_permission() -> Result<(), CommandError> {
+    crate::services::system::accessibility::open_settings().map_err(CommandError::from)
+}
+
+/// Configure `yaof:system:processes`: how many processes, ranked by `cpu` or `memory`,
+/// and how often they are refreshed. Omitted options are left unchanged.
+#[command]
+pub async fn system_processes_configure(
+    state: State<'_, SystemServiceState>,
+    limit: Option<usize>,
+    sort_by: Option<crate::services::system::ProcessSort>,
+    refresh_interval_ms: Option<u64>,
+) -> Result<(), CommandError> {
     let mut manager = state.0.write().await;
-    manager.unload_plugin(&plugin_id).map_err(|e| e.to_string())
+    manager.configure_processes(limit, sort_by, refresh_interval_ms);
+    Ok(())
 }
@@ -1,5 +1,7 @@
 use std::io;
+use std::sync::PoisonError;
 
+use serde::Serialize;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -8,12 +10,138 @@ pub enum Error {
     WindowCreation(String),
     #[error("Window {0} not found")]
     WindowNotFound(String),
-    #[error("Plugin not found")]
+    #[error("Plugin not found: {0}")]
     PluginNotFound(String),
     #[error("Failed to parse manifest: {0}")]
     ManifestParse(String),
     #[error("Tauri error: {0}")]
     TauriError(#[from] tauri::Error),
+    #[error("Logging error: {0}")]
+    Logging(String),
+    #[error("Capture failed: {0}")]
+    Capture(String),
+    #[error("Signature check failed: {0}")]
+    Signature(String),
+    #[error("Incompatible plugin: {0}")]
+    Incompatible(String),
+    #[error("Failed to load native plugin: {0}")]
+    NativeLoad(String),
+    #[error("Plugin {0} is already loaded")]
+    AlreadyLoaded(String),
+    #[error("Registry error: {0}")]
+    Registry(String),
+    #[error("Service bridge error: {0}")]
+    Bridge(String),
+    #[error("Secret store error: {0}")]
+    Secret(String),
     #[error("IO error: {0}")]
     IoError(#[from] io::Error),
 }
+
+impl Error {
+    /// Stable identifier of the kind of error, for the frontend to branch on
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::WindowCreation(_) => "window_creation",
+            Error::WindowNotFound(_) => "window_not_found",
+            Error::PluginNotFound(_) => "plugin_not_found",
+            Error::ManifestParse(_) => "manifest_parse",
+            Error::TauriError(_) => "tauri",
+            Error::Logging(_) => "logging",
+            Error::Capture(_) => "capture",
+            Error::Signature(_) => "signature",
+            Error::Incompatible(_) => "incompatible",
+            Error::NativeLoad(_) => "native_load",
+            Error::AlreadyLoaded(_) => "already_loaded",
+            Error::Registry(_) => "registry",
+            Error::Bridge(_) => "bridge",
+            Error::Secret(_) => "secret",
+            Error::IoError(_) => "io",
+        }
+    }
+}
+
+/// Error returned by commands, `{ code, message }` on the frontend.
+///
+/// `code` is one of [`Error::code`], or for failures outside that enum
+/// `invalid_argument`, `not_found`, `permission_denied`, `service`, `timeout`,
+/// `lock_poisoned`, `store`, `json` or `internal`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandError {
+    pub code: String,
+    /// Human-readable description, safe to show to the user
+    pub message: String,
+}
+
+impl CommandError {
+    pub fn new(code: &str, message: impl Into<String>) -> Self {
+        Self {
+            code: code.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// The command was called with a value it can't use
+    pub fn invalid_argument(message: impl Into<String>) -> Self {
+        Self::new("invalid_argument", message)
+    }
+
+    /// Something the command needs, other than a plugin or window, doesn't exist
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new("not_found", message)
+    }
+
+    /// The calling window isn't allowed to act on the target
+    pub fn permission_denied(message: impl Into<String>) -> Self {
+        Self::new("permission_denied", message)
+    }
+
+    /// A service provider refused or failed the call
+    pub fn service(message: impl Into<String>) -> Self {
+        Self::new("service", message)
+    }
+}
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl From<Error> for CommandError {
+    fn from(error: Error) -> Self {
+        Self::new(error.code(), error.to_string())
+    }
+}
+
+impl From<tauri::Error> for CommandError {
+    fn from(error: tauri::Error) -> Self {
+        Error::from(error).into()
+    }
+}
+
+impl From<io::Error> for CommandError {
+    fn from(error: io::Error) -> Self {
+        Error::from(error).into()
+    }
+}
+
+impl From<tauri_plugin_store::Error> for CommandError {
+    fn from(error: tauri_plugin_store::Error) -> Self {
+        Self::new("store", error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for CommandError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::new("json", error.to_string())
+    }
+}
+
+impl<T> From<PoisonError<T>> for CommandError {
+    fn from(error: PoisonError<T>) -> Self {
+        Self::new("lock_poisoned", error.to_string())
+    }
+}
@@ -1,10 +1,12 @@
 pub mod autostart;
 pub mod commands;
 pub mod error;
+pub mod logging;
 pub mod overlay;
 pub mod plugin;
 pub mod services;
 pub mod settings;
+pub mod settings_window;
 
 pub use autostart::*;
 pub use commands::*;
@@ -13,16 +15,151 @@ pub use overlay::*;
 pub use plugin::*;
 pub use services::*;
 pub use settings::*;
+pub use settings_window::*;
 
-use std::sync::{Arc, Mutex};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
 use tauri::{
-    Manager, Wry,
+    Emitter, Manager, RunEvent, WindowEvent, Wry,
     plugin::{Builder, TauriPlugin},
 };
 
 use services::system::{SystemServiceHandle, SystemServiceManager};
 
+/// Environment variable that enables development mode
+pub const DEV_ENV_VAR: &str = "YAOF_DEV";
+
+/// Check if we're running in development mode (YAOF_DEV=1)
+pub fn is_dev_mode() -> bool {
+    std::env::var(DEV_ENV_VAR)
+        .map(|v| v == "1")
+        .unwrap_or(false)
+}
+
+/// Environment variable that starts yaof in safe mode
+pub const SAFE_MODE_ENV_VAR: &str = "YAOF_SAFE_MODE";
+
+/// Check if we're running in safe mode (YAOF_SAFE_MODE=1): no native plugins are
+/// loaded and no overlays are autostarted, so a broken plugin can be disabled
+pub fn is_safe_mode() -> bool {
+    std::env::var(SAFE_MODE_ENV_VAR)
+        .map(|v| v == "1")
+        .unwrap_or(false)
+}
+
+/// Command-line flag that starts yaof in safe mode, like `YAOF_SAFE_MODE=1`
+pub const SAFE_MODE_ARG: &str = "--safe-mode";
+
+/// Relaunch yaof with the same arguments, in or out of safe mode.
+/// The new process is started first, exiting then shuts native plugins down.
+pub fn relaunch(app: &tauri::AppHandle, safe_mode: bool) -> Result<(), Error> {
+    let exe = std::env::current_exe()?;
+
+    let mut args: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| arg != SAFE_MODE_ARG)
+        .collect();
+    if safe_mode {
+        args.push(SAFE_MODE_ARG.to_string());
+    }
+
+    std::process::Command::new(exe)
+        .args(args)
+        .env_remove(SAFE_MODE_ENV_VAR)
+        .spawn()?;
+    app.exit(0);
+    Ok(())
+}
+
+/// Emitted once native plugins and system services are up and overlays have been spawned
+pub const READY_EVENT: &str = "yaof:ready";
+
+static READY: AtomicBool = AtomicBool::new(false);
+
+/// Record that startup finished and tell the settings UI
+pub fn mark_ready(app: &tauri::AppHandle) {
+    READY.store(true, Ordering::Relaxed);
+    let _ = app.emit(READY_EVENT, ());
+}
+
+/// Check whether startup has finished, for UIs that missed the `yaof:ready` event
+pub fn is_ready() -> bool {
+    READY.load(Ordering::Relaxed)
+}
+
+static NEXT_WINDOW_INSTANCE: AtomicU64 = AtomicU64::new(0);
+
+/// Instance id of each open window, by label. A label can be reused as soon as its
+/// window is destroyed, so deferred cleanup for a window checks it by instance.
+static WINDOW_INSTANCES: LazyLock<Mutex<HashMap<String, u64>>> = LazyLock::new(Default::default);
+
+/// Environment variable that overrides the yaof home directory (default `~/.yaof`)
+pub const HOME_ENV_VAR: &str = "YAOF_HOME";
+
+/// Get the yaof home directory, honoring `YAOF_HOME`
+pub fn yaof_home() -> Option<PathBuf> {
+    match std::env::var_os(HOME_ENV_VAR) {
+        Some(home) if !home.is_empty() => Some(PathBuf::from(home)),
+        _ => dirs::home_dir().map(|h| h.join(".yaof")),
+    }
+}
+
+/// Get the directory plugins are installed in (`<yaof home>/plugins`)
+pub fn yaof_plugins_dir() -> Option<PathBuf> {
+    yaof_home().map(|h| h.join("plugins"))
+}
+
+/// Get the directory plugin stores are kept in (`<yaof home>/stores`)
+pub fn yaof_stores_dir() -> Option<PathBuf> {
+    yaof_home().map(|h| h.join("stores"))
+}
+
+/// Resolve a plugin store file (plugin settings, overlay settings, native plugin
+/// store) under [`yaof_stores_dir`], so each yaof home keeps its own.
+///
+/// Stores used to live in the app data dir; one left there by an older version is
+/// moved over the first time it's resolved. Without a home directory this falls
+/// back to the bare name, which `tauri-plugin-store` puts in the app data dir.
+pub fn plugin_store_path(app: &tauri::AppHandle, name: &str) -> PathBuf {
+    let Some(dir) = yaof_stores_dir() else {
+        return PathBuf::from(name);
+    };
+    let path = dir.join(name);
+    if path.exists() {
+        return path;
+    }
+
+    let legacy = match app.path().app_data_dir() {
+        Ok(data_dir) => data_dir.join(name),
+        Err(_) => return path,
+    };
+    if legacy.exists() {
+        let moved = std::fs::create_dir_all(&dir).and_then(|_| {
+            std::fs::rename(&legacy, &path).or_else(|_| std::fs::copy(&legacy, &path).map(|_| ()))
+        });
+        if let Err(e) = moved {
+            tracing::warn!(
+                "Failed to move store {} into {}: {}",
+                name,
+                dir.display(),
+                e
+            );
+        }
+    }
+
+    path
+}
+
 /// State wrapper for native plugin manager
+///
+/// Lock order, when more than one of these is needed at a time:
+/// native plugin manager, then plugin loader (`PluginState`), then overlay manager
+/// (`OverlayState`). Native plugins lock the overlay manager from their callbacks
+/// while the native manager is held, and the std mutexes can't be held across an
+/// await, so the async native manager always comes first.
+/// `NativePluginManagerHandle::write_with_loader` takes the first two in order.
 pub struct NativePluginState(pub Arc<NativePluginManagerHandle>);
 
 /// State wrapper for system services
@@ -33,40 +170,134 @@ pub fn init() -> TauriPlugin<Wry> {
         .invoke_handler(tauri::generate_handler![
             commands::spawn_overlay,
             commands::close_overlay,
+            commands::overlay_duplicate,
             commands::overlay_set_click_through,
+            commands::overlay_set_click_through_all,
             commands::overlay_update_geometry,
+            commands::overlay_update_geometry_animated,
+            commands::overlay_set_position,
+            commands::overlay_set_size,
             commands::overlay_set_always_on_top,
+            commands::overlay_reconfigure,
+            commands::list_monitors,
+            commands::overlay_move_to_monitor,
+            commands::compute_preset_position,
+            commands::overlay_move_to_cursor_monitor,
+            commands::overlay_set_zoom,
+            commands::overlay_set_opacity,
+            commands::overlay_set_decorations,
+            commands::overlay_set_show_on_fullscreen,
+            commands::overlay_set_hide_on_fullscreen,
+            commands::overlay_set_desktop_level,
+            commands::overlay_set_schedule,
+            commands::overlay_set_background_color,
+            commands::overlay_set_chrome,
+            commands::overlay_inject_css,
+            commands::overlay_set_interactive_regions,
+            commands::overlay_capture_thumbnail,
+            commands::overlay_set_group,
+            commands::overlay_set_locked,
+            commands::overlay_group_set_visible,
+            commands::overlay_group_move,
             commands::overlay_exists,
             commands::overlay_set_visible,
+            commands::overlay_set_visible_all,
+            commands::overlay_ready,
+            commands::overlay_flash,
+            commands::overlay_message,
+            commands::overlay_set_exclude_from_bulk_toggle,
             commands::list_overlays,
             // Service Commands
             commands::service_register,
             commands::service_unregister,
             commands::service_list_providers,
+            commands::service_list_subscribers,
+            commands::service_list_all_subscriptions,
             commands::service_subscribe,
             commands::service_unsubscribe,
             commands::service_broadcast,
+            commands::service_broadcast_batch,
+            commands::service_set_rate_limit,
+            commands::service_request,
+            commands::service_respond,
+            commands::service_get_last_value,
+            commands::service_get_binary,
+            commands::service_bridge_status,
+            commands::service_bridge_configure,
+            commands::service_bridge_reset_token,
             // Plugin Commands
             commands::plugin_list,
+            commands::plugin_get_all,
             commands::plugin_get,
+            commands::plugin_load_errors,
             commands::plugin_install_local,
+            commands::registry_fetch_index,
+            commands::plugin_validate,
             commands::plugin_uninstall,
+            commands::plugin_reveal_in_explorer,
+            commands::plugin_verify_integrity,
+            commands::plugin_read_file,
+            commands::plugin_list_files,
+            commands::open_external,
+            commands::refresh_plugins,
             // Native Plugin Commands
             commands::native_plugin_list,
             commands::native_plugin_load,
+            commands::native_plugin_load_dev,
             commands::native_plugin_unload,
+            commands::native_plugin_broadcast,
+            commands::native_plugin_signing_policy,
+            commands::native_plugin_set_require_signed,
+            commands::native_plugin_add_trusted_key,
+            commands::native_plugin_remove_trusted_key,
             // Plugin Settings Commands
+            commands::plugin_settings_get_schema,
             commands::plugin_settings_get,
             commands::plugin_settings_set,
             commands::plugin_settings_get_all,
             commands::plugin_settings_set_all,
             commands::plugin_settings_delete,
-            commands::plugin_settings_clear
+            commands::plugin_settings_clear,
+            commands::plugin_store_path,
+            commands::plugin_secret_set,
+            commands::plugin_secret_get,
+            commands::plugin_secret_delete,
+            // Status Commands
+            commands::yaof_status,
+            commands::restart_app,
+            commands::open_settings,
+            commands::focus_settings,
+            commands::close_settings,
+            // Logging Commands
+            commands::yaof_set_log_level,
+            commands::get_log_path,
+            // System Service Commands
+            commands::system_notifications_set_enabled,
+            commands::system_notifications_enabled,
+            commands::system_idle_set_threshold,
+            commands::system_idle_threshold,
+            commands::system_media_artwork,
+            commands::system_set_tick_interval,
+            commands::system_get_tick_interval,
+            commands::autostart_get_stagger,
+            commands::autostart_set_stagger,
+            commands::check_accessibility_permission,
+            commands::request_accessibility_permission,
+            commands::system_processes_configure,
+            commands::process_kill,
+            commands::process_focus,
+            commands::network_get_interfaces
         ])
         .setup(|app, _api| {
             let manager = overlay::manager::OverlayManager::new(app.app_handle().clone());
             app.manage(OverlayState(Mutex::new(manager)));
 
+            // Track the cursor for interactive regions and hover fading
+            overlay::start_hit_test_loop(app.app_handle().clone());
+
+            // Bring overlays back on screen when a monitor is unplugged
+            overlay::start_monitor_watch(app.app_handle().clone());
+
             let loader = PluginLoader::with_default_dir().expect("Failed to create plugin loader");
             app.manage(PluginState(Mutex::new(loader)));
 
@@ -81,31 +312,42 @@ pub fn init() -> TauriPlugin<Wry> {
             let system_handle = Arc::new(SystemServiceHandle::new(system_manager));
             app.manage(SystemServiceState(system_handle.clone()));
 
+            // Start the external service bridge if the user enabled it
+            app.manage(services::bridge::ServiceBridgeState::default());
+            if !is_safe_mode()
+                && let Err(e) = services::bridge::restart(app.app_handle())
+            {
+                tracing::error!("Failed to start service bridge: {}", e);
+            }
+
             // Start background tasks
             let app_handle = app.app_handle().clone();
             let app_handle_for_system = app.app_handle().clone();
             tauri::async_runtime::spawn(async move {
-                // Small delay to ensure everything is initialized
-                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-
                 // Discover and load native plugins
                 {
                     let mut manager = native_handle.write().await;
                     match manager.discover_and_load() {
                         Ok(loaded) => {
                             if !loaded.is_empty() {
-                                println!(
-                                    "[YAOF] Loaded {} native plugin(s): {:?}",
+                                tracing::info!(
+                                    "Loaded {} native plugin(s): {:?}",
                                     loaded.len(),
                                     loaded
                                 );
                             }
                         }
                         Err(e) => {
-                            eprintln!("[YAOF] Failed to discover native plugins: {}", e);
+                            tracing::error!("Failed to discover native plugins: {}", e);
                         }
                     }
+
+                    // Libraries under development, loaded from their build output
+                    if !is_safe_mode() {
+                        manager.load_dev_plugins_from_env();
+                    }
                 }
+                native_handle.mark_ready();
 
                 // Start the native plugin tick loop (1 second interval)
                 native_handle.start_tick_loop(1000);
@@ -113,10 +355,7 @@ pub fn init() -> TauriPlugin<Wry> {
 
             // Start system services tick loop
             tauri::async_runtime::spawn(async move {
-                // Small delay to ensure everything is initialized
-                tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
-
-                println!("[YAOF] Starting system services...");
+                tracing::info!("Starting system services...");
 
                 // Start the system services tick loop (1 second interval)
                 system_handle.start_tick_loop(app_handle_for_system, 1000);
@@ -124,5 +363,58 @@ pub fn init() -> TauriPlugin<Wry> {
 
             Ok(())
         })
+        .on_window_ready(|window| {
+            let instance = NEXT_WINDOW_INSTANCE.fetch_add(1, Ordering::Relaxed);
+            if let Ok(mut instances) = WINDOW_INSTANCES.lock() {
+                instances.insert(window.label().to_string(), instance);
+            }
+        })
+        .on_event(|app, event| match event {
+            RunEvent::WindowEvent {
+                label,
+                event: WindowEvent::Destroyed,
+                ..
+            } => {
+                // Closed (or crashed) windows must not linger as service subscribers or providers.
+                // Deferred because close_overlay destroys windows while holding the overlay lock.
+                let instance = WINDOW_INSTANCES
+                    .lock()
+                    .ok()
+                    .and_then(|mut instances| instances.remove(label));
+                let app = app.clone();
+                let label = label.clone();
+                tauri::async_runtime::spawn(async move {
+                    // A window respawned under the same label in the meantime (hot reload,
+                    // close then reopen) has subscribed on its own - leave it be
+                    let reused = WINDOW_INSTANCES
+                        .lock()
+                        .map(|instances| {
+                            instances
+                                .get(&label)
+                                .is_some_and(|current| Some(*current) != instance)
+                        })
+                        .unwrap_or(false);
+                    if reused {
+                        return;
+                    }
+
+                    if let Some(state) = app.try_state::<OverlayState>()
+                        && let Ok(mut manager) = state.0.lock()
+                    {
+                        let registry = manager.registry_mut();
+                        registry.unsubscribe_window(&label);
+                        registry.unregister_window(&label, &app);
+                    }
+                });
+            }
+            RunEvent::Exit => {
+                // Shut native plugins down explicitly - managed state isn't dropped on exit
+                let native_state = app.state::<NativePluginState>();
+                tauri::async_runtime::block_on(async {
+                    native_state.0.write().await.shutdown_all();
+                });
+            }
+            _ => {}
+        })
         .build()
 }
@@ -0,0 +1,190 @@
+//! Structured logging for yaof
+//!
+//! Installs a `tracing` subscriber whose console filter can be changed at runtime.
+//! The initial filter is read from the `YAOF_LOG` environment variable using
+//! `EnvFilter` syntax (e.g. `YAOF_LOG=debug` or `YAOF_LOG=info,yaof_core=trace`)
+//! and defaults to `info`.
+//!
+//! Native plugin logs are also persisted to a size-capped, rotating file at
+//! `~/.yaof/logs/yaof.log`. Set `YAOF_LOG_FILE=all` to capture the runtime's
+//! own logs in that file as well.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use tracing_subscriber::{
+    EnvFilter, Layer, Registry,
+    filter::{LevelFilter, Targets},
+    fmt::{self, MakeWriter},
+    layer::SubscriberExt,
+    reload,
+    util::SubscriberInitExt,
+};
+
+use crate::Error;
+
+/// Environment variable used to configure the initial log filter
+pub const LOG_ENV_VAR: &str = "YAOF_LOG";
+
+/// Environment variable controlling what is written to the log file ("plugins" or "all")
+pub const LOG_FILE_ENV_VAR: &str = "YAOF_LOG_FILE";
+
+/// Filter used when `YAOF_LOG` is unset or invalid
+const DEFAULT_FILTER: &str = "info";
+
+/// Target used for log messages coming from native plugins
+pub const NATIVE_PLUGIN_TARGET: &str = "yaof::native_plugin";
+
+/// Maximum size of the active log file before it is rotated
+const MAX_LOG_FILE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Number of rotated log files to keep (yaof.log.1 ... yaof.log.N)
+const MAX_ROTATED_FILES: u32 = 3;
+
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Install the global subscriber. Safe to call more than once.
+pub fn init() {
+    if FILTER_HANDLE.get().is_some() {
+        return;
+    }
+
+    let filter =
+        EnvFilter::try_from_env(LOG_ENV_VAR).unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let (filter, handle) = reload::Layer::new(filter);
+
+    // Only native plugin logs go to the file unless YAOF_LOG_FILE=all
+    let file_layer = log_path()
+        .and_then(|path| RotatingWriter::open(path).ok())
+        .map(|writer| {
+            let capture_all = std::env::var(LOG_FILE_ENV_VAR)
+                .map(|v| v == "all")
+                .unwrap_or(false);
+            let targets = if capture_all {
+                Targets::new().with_default(LevelFilter::TRACE)
+            } else {
+                Targets::new().with_target(NATIVE_PLUGIN_TARGET, LevelFilter::TRACE)
+            };
+
+            fmt::layer()
+                .with_ansi(false)
+                .with_writer(writer)
+                .with_filter(targets)
+        });
+
+    // Filters are per layer, so the console level doesn't drop plugin logs from the file
+    if tracing_subscriber::registry()
+        .with(fmt::layer().with_filter(filter))
+        .with(file_layer)
+        .try_init()
+        .is_ok()
+    {
+        let _ = FILTER_HANDLE.set(handle);
+    }
+}
+
+/// Replace the console log filter (e.g. "debug" or "warn,yaof::native_plugin=trace")
+pub fn set_level(level: &str) -> Result<(), Error> {
+    let handle = FILTER_HANDLE
+        .get()
+        .ok_or_else(|| Error::Logging("Logging has not been initialized".to_string()))?;
+
+    let filter = EnvFilter::try_new(level)
+        .map_err(|e| Error::Logging(format!("Invalid log level {}: {}", level, e)))?;
+
+    handle
+        .reload(filter)
+        .map_err(|e| Error::Logging(e.to_string()))
+}
+
+/// Get the path of the active log file (<yaof home>/logs/yaof.log)
+pub fn log_path() -> Option<PathBuf> {
+    crate::yaof_home().map(|h| h.join("logs").join("yaof.log"))
+}
+
+/// Log file that rotates `yaof.log` -> `yaof.log.1` -> ... once it exceeds the size cap
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFile {
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        for index in (1..MAX_ROTATED_FILES).rev() {
+            let from = rotated_path(&self.path, index);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.path, index + 1))?;
+            }
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1))?;
+
+        self.file = File::create(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+fn rotated_path(path: &std::path::Path, index: u32) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// Shared writer handed to the file logging layer
+#[derive(Clone)]
+struct RotatingWriter(Arc<Mutex<RotatingFile>>);
+
+impl RotatingWriter {
+    fn open(path: PathBuf) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self(Arc::new(Mutex::new(RotatingFile {
+            path,
+            file,
+            size,
+        }))))
+    }
+}
+
+impl Write for RotatingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut inner = self
+            .0
+            .lock()
+            .map_err(|_| io::Error::other("log file lock poisoned"))?;
+
+        if inner.size > 0 && inner.size + buf.len() as u64 > MAX_LOG_FILE_BYTES {
+            inner.rotate()?;
+        }
+
+        let written = inner.file.write(buf)?;
+        inner.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut inner = self
+            .0
+            .lock()
+            .map_err(|_| io::Error::other("log file lock poisoned"))?;
+        inner.file.flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for RotatingWriter {
+    type Writer = RotatingWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
@@ -0,0 +1,177 @@
+//! Overlay thumbnail capture
+//!
+//! Grabs the current contents of an overlay window for previews in the settings UI.
+//! This is platform-specific:
+//! - macOS: `screencapture -l <windowNumber>`
+//! - Windows: `PrintWindow` with `PW_RENDERFULLCONTENT` (includes WebView2 content)
+//! - Linux: Not supported yet
+
+use std::io::Cursor;
+
+use image::{DynamicImage, ImageFormat};
+use tauri::WebviewWindow;
+
+use crate::Error;
+
+/// Largest width/height of a captured thumbnail, aspect ratio is preserved
+const THUMBNAIL_MAX_SIZE: u32 = 320;
+
+/// Capture an overlay window and return a downscaled PNG.
+/// Blocks while the platform capture runs, call it off the async runtime.
+pub fn capture_thumbnail(window: &WebviewWindow) -> Result<Vec<u8>, Error> {
+    // A hidden window would capture as a blank image
+    let visible = window.is_visible().unwrap_or(false);
+    let minimized = window.is_minimized().unwrap_or(false);
+    if !visible || minimized {
+        return Err(Error::Capture(format!(
+            "Overlay {} is hidden",
+            window.label()
+        )));
+    }
+
+    let image = capture_window(window)?;
+
+    let (width, height) = (image.width(), image.height());
+    let scale = (THUMBNAIL_MAX_SIZE as f64 / width.max(height) as f64).min(1.0);
+    let thumbnail = image.thumbnail(
+        ((width as f64 * scale).round() as u32).max(1),
+        ((height as f64 * scale).round() as u32).max(1),
+    );
+
+    let mut png = Vec::new();
+    thumbnail
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| Error::Capture(format!("Failed to encode thumbnail: {}", e)))?;
+
+    Ok(png)
+}
+
+#[cfg(target_os = "macos")]
+fn capture_window(window: &WebviewWindow) -> Result<DynamicImage, Error> {
+    use std::process::Command;
+
+    let ns_window_ptr = window
+        .ns_window()
+        .map_err(|e| Error::Capture(format!("Failed to get NSWindow handle: {}", e)))?;
+
+    // windowNumber is a plain property read, safe to do off the main thread
+    let window_number = unsafe {
+        let ns_window = &*(ns_window_ptr as *const objc2_app_kit::NSWindow);
+        ns_window.windowNumber()
+    };
+
+    // Randomly named and only readable by us, removed when dropped
+    let file = tempfile::Builder::new()
+        .prefix("yaof-capture-")
+        .suffix(".png")
+        .tempfile()?;
+    let path = file.path();
+
+    // -x: no sound, -o: no window shadow
+    let output = Command::new("screencapture")
+        .args(["-x", "-o", "-t", "png", "-l"])
+        .arg(window_number.to_string())
+        .arg(path)
+        .output()?;
+
+    if !output.status.success() {
+        return Err(Error::Capture(format!(
+            "screencapture failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let bytes = std::fs::read(path)?;
+
+    image::load_from_memory(&bytes)
+        .map_err(|e| Error::Capture(format!("Failed to decode capture: {}", e)))
+}
+
+#[cfg(target_os = "windows")]
+fn capture_window(window: &WebviewWindow) -> Result<DynamicImage, Error> {
+    use windows::Win32::Foundation::{HWND, RECT};
+    use windows::Win32::Graphics::Gdi::{
+        BI_RGB, BITMAPINFO, BITMAPINFOHEADER, CreateCompatibleBitmap, CreateCompatibleDC,
+        DIB_RGB_COLORS, DeleteDC, DeleteObject, GetDC, GetDIBits, ReleaseDC, SelectObject,
+    };
+    use windows::Win32::Storage::Xps::{PRINT_WINDOW_FLAGS, PrintWindow};
+    use windows::Win32::UI::WindowsAndMessaging::GetWindowRect;
+
+    // Renders DirectComposition content, without it WebView2 captures as black
+    const PW_RENDERFULLCONTENT: u32 = 0x2;
+
+    let hwnd = window
+        .hwnd()
+        .map_err(|e| Error::Capture(format!("Failed to get HWND handle: {}", e)))?;
+
+    unsafe {
+        let hwnd = HWND(hwnd.0);
+
+        let mut rect = RECT::default();
+        GetWindowRect(hwnd, &mut rect)
+            .map_err(|e| Error::Capture(format!("Failed to get window rect: {}", e)))?;
+
+        let width = rect.right - rect.left;
+        let height = rect.bottom - rect.top;
+        if width <= 0 || height <= 0 {
+            return Err(Error::Capture("Window has no size".to_string()));
+        }
+
+        let screen_dc = GetDC(None);
+        let mem_dc = CreateCompatibleDC(Some(screen_dc));
+        let bitmap = CreateCompatibleBitmap(screen_dc, width, height);
+        let previous = SelectObject(mem_dc, bitmap.into());
+
+        let printed = PrintWindow(hwnd, mem_dc, PRINT_WINDOW_FLAGS(PW_RENDERFULLCONTENT)).as_bool();
+
+        // Negative height requests a top-down bitmap
+        let mut info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: width,
+                biHeight: -height,
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut pixels = vec![0u8; (width * height * 4) as usize];
+        let lines = GetDIBits(
+            mem_dc,
+            bitmap,
+            0,
+            height as u32,
+            Some(pixels.as_mut_ptr() as *mut _),
+            &mut info,
+            DIB_RGB_COLORS,
+        );
+
+        SelectObject(mem_dc, previous);
+        let _ = DeleteObject(bitmap.into());
+        let _ = DeleteDC(mem_dc);
+        ReleaseDC(None, screen_dc);
+
+        if !printed || lines == 0 {
+            return Err(Error::Capture("PrintWindow failed".to_string()));
+        }
+
+        // GDI returns BGRA
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+
+        image::RgbaImage::from_raw(width as u32, height as u32, pixels)
+            .map(DynamicImage::ImageRgba8)
+            .ok_or_else(|| Error::Capture("Invalid capture buffer".to_string()))
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn capture_window(_window: &WebviewWindow) -> Result<DynamicImage, Error> {
+    Err(Error::Capture(
+        "Overlay capture is not supported on this platform".to_string(),
+    ))
+}
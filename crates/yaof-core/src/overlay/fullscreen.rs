@@ -0,0 +1,69 @@
+//! Desktop-only overlays
+//!
+//! Overlays with `hide_on_fullscreen` set are hidden while the focused app is
+//! fullscreen and shown again once it isn't. Driven by the window service's
+//! fullscreen detection in the system services tick loop.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tauri::{AppHandle, Manager, WebviewWindow};
+
+use crate::OverlayState;
+
+/// Whether the focused app was fullscreen at the last check
+static FULLSCREEN: AtomicBool = AtomicBool::new(false);
+
+/// Whether an app is fullscreen right now, for overlays spawned in the meantime
+pub(crate) fn is_fullscreen_active() -> bool {
+    FULLSCREEN.load(Ordering::Relaxed)
+}
+
+/// Snapshot of an overlay that hides while an app is fullscreen
+pub(crate) struct FullscreenTarget {
+    pub id: String,
+    pub window: WebviewWindow,
+    /// Whether the overlay is currently hidden because of a fullscreen app
+    pub hidden: bool,
+    /// Overlays outside their schedule stay hidden when the fullscreen app goes away
+    pub hidden_by_schedule: bool,
+}
+
+/// Hide or restore desktop-only overlays after the focused app entered or left fullscreen
+pub fn sync_fullscreen_visibility(app: &AppHandle, fullscreen: bool) {
+    FULLSCREEN.store(fullscreen, Ordering::Relaxed);
+
+    let Some(state) = app.try_state::<OverlayState>() else {
+        return;
+    };
+
+    // Snapshot and release the lock - visibility queries wait on the main thread
+    let targets = match state.0.lock() {
+        Ok(manager) => manager.fullscreen_targets(),
+        Err(_) => return,
+    };
+
+    let mut changed = Vec::new();
+    for target in targets {
+        if fullscreen && !target.hidden {
+            // Overlays the user already hid stay hidden and aren't brought back later
+            if target.window.is_visible().unwrap_or(false) && target.window.hide().is_ok() {
+                changed.push((target.id, true));
+            }
+        } else if !fullscreen && target.hidden {
+            if !target.hidden_by_schedule {
+                let _ = target.window.show();
+            }
+            changed.push((target.id, false));
+        }
+    }
+
+    if changed.is_empty() {
+        return;
+    }
+
+    if let Ok(mut manager) = state.0.lock() {
+        for (id, hidden) in changed {
+            manager.set_hidden_for_fullscreen(&id, hidden);
+        }
+    }
+}
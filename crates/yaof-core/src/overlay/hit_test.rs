@@ -0,0 +1,112 @@
+//! Region-based hit testing for overlays
+//!
+//! Lets an otherwise click-through overlay capture the mouse inside a set of
+//! interactive rectangles it reports (e.g. a tiny button on a transparent canvas)
+//! while clicks anywhere else pass through to the desktop.
+//!
+//! A window that ignores cursor events never receives hit-test queries
+//! (`WM_NCHITTEST` isn't sent to `WS_EX_TRANSPARENT` windows and macOS skips windows
+//! with `ignoresMouseEvents`), so the cursor position is polled instead and cursor
+//! events are toggled whenever it enters or leaves an interactive region.
+//! The same loop drives the hover fade in [`super::hover`].
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, WebviewWindow};
+
+use crate::{OverlayState, overlay::set_window_alpha};
+
+/// How often the cursor position is checked against interactive regions
+const HIT_TEST_INTERVAL_MS: u64 = 16;
+
+/// Rectangle in CSS pixels relative to the overlay's viewport
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+}
+
+/// Snapshot of an overlay that uses interactive regions
+pub(crate) struct HitTestTarget {
+    pub id: String,
+    pub window: WebviewWindow,
+    pub regions: Vec<Rect>,
+    pub zoom: f64,
+    pub captured: bool,
+}
+
+/// Check whether the cursor is currently inside any of the given regions
+fn cursor_in_regions(window: &WebviewWindow, regions: &[Rect], zoom: f64) -> bool {
+    let Ok(cursor) = window.cursor_position() else {
+        return false;
+    };
+    let Ok(origin) = window.inner_position() else {
+        return false;
+    };
+    let scale = window.scale_factor().unwrap_or(1.0);
+
+    // Physical screen coordinates -> CSS pixels inside the (possibly zoomed) webview
+    let x = (cursor.x - origin.x as f64) / scale / zoom;
+    let y = (cursor.y - origin.y as f64) / scale / zoom;
+
+    regions.iter().any(|r| r.contains(x, y))
+}
+
+/// Start polling the cursor for overlays with interactive regions or hover fading
+pub fn start_hit_test_loop(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval =
+            tokio::time::interval(tokio::time::Duration::from_millis(HIT_TEST_INTERVAL_MS));
+
+        loop {
+            interval.tick().await;
+
+            let state = app.state::<OverlayState>();
+
+            // Window queries are dispatched to the main thread, so don't hold the
+            // manager lock while making them
+            let (targets, hover_targets) = match state.0.lock() {
+                Ok(manager) => (manager.hit_test_targets(), manager.hover_targets()),
+                Err(_) => continue,
+            };
+
+            for target in targets {
+                let inside = cursor_in_regions(&target.window, &target.regions, target.zoom);
+                if inside == target.captured {
+                    continue;
+                }
+
+                if let Err(e) = target.window.set_ignore_cursor_events(!inside) {
+                    tracing::warn!("Failed to update hit testing for {}: {}", target.id, e);
+                    continue;
+                }
+
+                if let Ok(mut manager) = state.0.lock() {
+                    manager.set_cursor_captured(&target.id, inside);
+                }
+            }
+
+            for target in hover_targets {
+                let Some(alpha) = target.next_alpha(HIT_TEST_INTERVAL_MS as f64) else {
+                    continue;
+                };
+
+                if let Err(e) = set_window_alpha(&target.window, alpha) {
+                    tracing::warn!("Failed to update hover opacity for {}: {}", target.id, e);
+                    continue;
+                }
+
+                if let Ok(mut manager) = state.0.lock() {
+                    manager.set_hover_alpha(&target.id, alpha);
+                }
+            }
+        }
+    });
+}
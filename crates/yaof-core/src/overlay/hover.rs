@@ -0,0 +1,63 @@
+//! Hover fade for overlays
+//!
+//! Animates the native window alpha between the overlay's resting `opacity` and its
+//! `hover_opacity` as the cursor enters and leaves the window. Click-through overlays
+//! never count as hovered, so they always stay at their resting opacity.
+//!
+//! Driven by the cursor polling loop in [`super::hit_test`], since overlays don't
+//! receive enter/leave events while they ignore cursor events.
+
+use tauri::WebviewWindow;
+
+/// Time it takes to fade fully between the resting and hover opacity
+const FADE_DURATION_MS: f64 = 150.0;
+
+/// Snapshot of an overlay with hover fading enabled
+pub(crate) struct HoverTarget {
+    pub id: String,
+    pub window: WebviewWindow,
+    /// Resting alpha (0.0 - 1.0)
+    pub resting: f64,
+    /// Alpha while hovered (0.0 - 1.0)
+    pub hover: f64,
+    /// Alpha currently applied to the window
+    pub alpha: f64,
+    pub click_through: bool,
+}
+
+impl HoverTarget {
+    /// Compute the alpha for the next frame, or None if the fade is already complete
+    pub fn next_alpha(&self, frame_ms: f64) -> Option<f64> {
+        let hovered = !self.click_through && cursor_over_window(&self.window);
+        let target = if hovered { self.hover } else { self.resting };
+
+        if (self.alpha - target).abs() < f64::EPSILON {
+            return None;
+        }
+
+        let step = frame_ms / FADE_DURATION_MS;
+        let next = if self.alpha < target {
+            (self.alpha + step).min(target)
+        } else {
+            (self.alpha - step).max(target)
+        };
+
+        Some(next)
+    }
+}
+
+/// Check whether the cursor is inside the window's frame
+fn cursor_over_window(window: &WebviewWindow) -> bool {
+    let (Ok(cursor), Ok(origin), Ok(size)) = (
+        window.cursor_position(),
+        window.outer_position(),
+        window.outer_size(),
+    ) else {
+        return false;
+    };
+
+    let x = cursor.x - origin.x as f64;
+    let y = cursor.y - origin.y as f64;
+
+    x >= 0.0 && y >= 0.0 && x < size.width as f64 && y < size.height as f64
+}
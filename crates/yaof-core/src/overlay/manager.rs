@@ -1,14 +1,83 @@
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::{
-    Error, ServiceRegistry,
-    overlay::{OverlayConfig, OverlayWindow, configure_overlay, set_unconstrained_position},
+    Error, OverlaySettings, OverlayState, ServiceRegistry,
+    overlay::{
+        DesktopLevel, FlashRestore, GeometryAnimation, OverlayBorder, OverlayConfig, OverlayWindow,
+        Rect, configure_overlay,
+        fullscreen::{self, FullscreenTarget},
+        hit_test::HitTestTarget,
+        hover::HoverTarget,
+        monitor::{MonitorBounds, OverlayRelocatedEvent, monitor_bounds},
+        prefers_reduced_motion, raise_for_flash,
+        schedule::{ScheduleTarget, ScheduleWindow, is_scheduled_visible},
+        set_unconstrained_position, set_window_alpha, set_window_frame,
+    },
 };
-use tauri::{AppHandle, WebviewUrl, WebviewWindowBuilder, window::Color};
+use tauri::{
+    AppHandle, Emitter, Manager, PhysicalSize, WebviewUrl, WebviewWindow, WebviewWindowBuilder,
+    WindowEvent,
+    webview::{PageLoadEvent, PageLoadPayload},
+    window::Color,
+};
+
+/// Emitted with the overlay ID after an overlay has been spawned
+pub const OVERLAY_SPAWNED_EVENT: &str = "yaof:overlay:spawned";
+
+/// Emitted with the overlay ID after an overlay has been closed
+pub const OVERLAY_CLOSED_EVENT: &str = "yaof:overlay:closed";
+
+/// Emitted with the new logical size after the user resized an overlay,
+/// once it has been written to the overlay's settings store
+pub const OVERLAY_RESIZED_EVENT: &str = "yaof:overlay:resized";
+
+/// Payload of the `yaof:overlay:resized` event
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayResizedEvent {
+    pub id: String,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Name of the event an overlay receives direct messages from other overlays on:
+/// `yaof:overlay:{id}:message`
+pub fn overlay_message_event(id: &str) -> String {
+    format!("yaof:overlay:{}:message", id)
+}
+
+/// Payload of an overlay message event
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayMessage {
+    /// Window label of the sender
+    pub from: String,
+    #[serde(rename = "type")]
+    pub msg_type: String,
+    pub payload: serde_json::Value,
+}
+
+/// How far (in logical pixels) a duplicated overlay is offset from its source
+const DUPLICATE_OFFSET: f64 = 24.0;
+
+/// Longest a geometry animation can run
+pub const MAX_GEOMETRY_ANIMATION_MS: u64 = 5_000;
+
+/// Time between the steps of a geometry animation (~60fps)
+const GEOMETRY_ANIMATION_FRAME: Duration = Duration::from_millis(16);
+
 pub struct OverlayManager {
     app: AppHandle,
     windows: HashMap<String, OverlayWindow>,
     registry: ServiceRegistry,
+    /// Each overlay's own click-through state while click-through is forced on for all
+    saved_click_through: Option<HashMap<String, bool>>,
+    /// ID handed to the next overlay flash
+    next_flash_id: u64,
+    /// ID handed to the next geometry animation
+    next_animation_id: u64,
 }
 
 impl OverlayManager {
@@ -17,12 +86,21 @@ impl OverlayManager {
             app,
             windows: HashMap::new(),
             registry: ServiceRegistry::new(),
+            saved_click_through: None,
+            next_flash_id: 0,
+            next_animation_id: 0,
         }
     }
 
-    pub fn spawn_overlay(&mut self, config: OverlayConfig) -> Result<String, Error> {
+    pub fn spawn_overlay(&mut self, mut config: OverlayConfig) -> Result<String, Error> {
         let id = config.id.clone();
 
+        // Settings stores can be edited by hand, don't hand bad sizes to the chrome script
+        config.corner_radius = validate_length(config.corner_radius).unwrap_or(0.0);
+        config.border = config
+            .border
+            .filter(|border| validate_length(border.width).is_ok());
+
         // Determine the correct URL type based on the entry_point
         // - yaof-plugin:// URLs use the custom protocol for plugin assets
         // - Relative paths use bundled assets (WebviewUrl::App)
@@ -51,8 +129,8 @@ impl OverlayManager {
         };
 
         // Create the window initially hidden to prevent visual glitches
-        // The window will be made visible by set_unconstrained_position after
-        // it has been positioned correctly (bypassing macOS frame constraining)
+        // The window is shown once its content reports ready (see mark_content_ready),
+        // after it has been positioned correctly (bypassing macOS frame constraining)
         let window = WebviewWindowBuilder::new(&self.app, &id, url)
             .title(&id)
             .inner_size(config.width, config.height)
@@ -60,22 +138,66 @@ impl OverlayManager {
             // Instead, we'll set it via set_unconstrained_position
             .decorations(!config.frameless)
             .transparent(true)
-            .background_color(Color(0, 0, 0, 0))
-            .always_on_top(true)
+            .background_color(background(config.background_color))
+            .initialization_script(config.runtime_script())
+            .initialization_script(config.chrome_script())
+            .initialization_script(config.css_script())
+            .on_page_load(handle_page_load)
+            .always_on_top(config.always_on_top)
             .skip_taskbar(true)
-            .visible(false) // Start hidden, will be shown after positioning
+            .visible(false) // Start hidden, will be shown once the content is ready
             .build()
             .map_err(|e| Error::WindowCreation(e.to_string()))?;
 
+        // Keep the config in sync when the user resizes the overlay
+        let app = self.app.clone();
+        let overlay_id = id.clone();
+        window.on_window_event(move |event| {
+            if let WindowEvent::Resized(size) = event {
+                handle_overlay_resized(&app, &overlay_id, *size);
+            }
+        });
+
         // Apply platform-specific overlay configuration
         // This sets window level, collection behavior (macOS), click-through handling,
         // and ensures the window stays below the menu bar but above normal windows
-        configure_overlay(&window, config.click_through)?;
+        configure_overlay(&window, &config)?;
 
         // Set the window position using unconstrained positioning
         // This bypasses macOS's automatic frame constraining that prevents
-        // windows from being placed in the menu bar/notch area
-        set_unconstrained_position(&window, config.x, config.y, config.width, config.height)?;
+        // windows from being placed in the menu bar/notch area.
+        // Without a ready handshake the window is shown right away, unless it is
+        // outside its schedule or hides while an app is fullscreen and one already is.
+        let awaiting_content = config.ready_timeout_ms > 0;
+        let hidden_by_schedule = !is_scheduled_visible(&config.schedule, &chrono::Local::now());
+        let hidden_for_fullscreen = config.hide_on_fullscreen && fullscreen::is_fullscreen_active();
+        let show_now = !awaiting_content && !hidden_by_schedule && !hidden_for_fullscreen;
+        set_unconstrained_position(
+            &window,
+            config.x,
+            config.y,
+            config.width,
+            config.height,
+            show_now,
+        )?;
+        #[cfg(not(target_os = "macos"))]
+        if show_now {
+            window.show()?;
+        }
+
+        // Scale the overlay UI for plugins that don't handle HiDPI themselves
+        if config.zoom.is_finite() && config.zoom > 0.0 && config.zoom != 1.0 {
+            window.set_zoom(config.zoom)?;
+        }
+
+        // With hover fading the native window alpha carries the resting opacity
+        let hover_alpha = if config.hover_opacity.is_some() {
+            let alpha = validate_opacity(config.opacity).unwrap_or(100.0) / 100.0;
+            set_window_alpha(&window, alpha)?;
+            alpha
+        } else {
+            1.0
+        };
 
         if config.click_through {
             window.set_ignore_cursor_events(true)?;
@@ -87,12 +209,55 @@ impl OverlayManager {
             window,
             plugin_id: config.plugin_id.clone(),
             config,
+            interactive_regions: Vec::new(),
+            cursor_captured: false,
+            hover_alpha,
+            hidden_for_fullscreen,
+            hidden_by_schedule,
+            flash: None,
+            awaiting_content,
+            animation: None,
         };
 
+        if awaiting_content {
+            // Show the overlay anyway if its content never reports ready
+            let app = self.app.clone();
+            let overlay_id = id.clone();
+            let timeout = Duration::from_millis(overlay.config.ready_timeout_ms);
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(timeout).await;
+                if let Some(state) = app.try_state::<OverlayState>()
+                    && let Ok(mut manager) = state.0.lock()
+                    && let Ok(true) = manager.mark_content_ready(&overlay_id)
+                {
+                    tracing::debug!(
+                        "Overlay {} didn't report ready within {:?}, showing it",
+                        overlay_id,
+                        timeout
+                    );
+                }
+            });
+        }
+
         self.windows.insert(id.clone(), overlay);
+
+        let _ = self.app.emit(OVERLAY_SPAWNED_EVENT, &id);
         Ok(id)
     }
 
+    /// Reapply the chrome and injected CSS after a page load. The window's
+    /// initialization scripts still carry the values it was spawned with.
+    pub(crate) fn reapply_page_scripts(&self, id: &str) -> Result<(), Error> {
+        let overlay = self
+            .windows
+            .get(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        overlay.window.eval(overlay.config.chrome_script())?;
+        overlay.window.eval(overlay.config.css_script())?;
+        Ok(())
+    }
+
     pub fn get_overlay(&self, id: &str) -> Option<&OverlayWindow> {
         self.windows.get(id)
     }
@@ -112,6 +277,7 @@ impl OverlayManager {
             .close()
             .map_err(|_e| Error::WindowCreation(id.to_string()))?;
 
+        let _ = self.app.emit(OVERLAY_CLOSED_EVENT, id);
         Ok(())
     }
 
@@ -119,20 +285,85 @@ impl OverlayManager {
         self.windows.values().collect()
     }
 
-    pub fn set_click_through(&self, id: &str, enabled: bool) -> Result<(), Error> {
+    pub fn set_click_through(&mut self, id: &str, enabled: bool) -> Result<(), Error> {
         let overlay = self
             .windows
-            .get(id)
+            .get_mut(id)
             .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
 
-        overlay
-            .window
-            .set_ignore_cursor_events(enabled)
-            .map_err(|e| Error::WindowCreation(e.to_string()))?;
+        overlay.config.click_through = enabled;
+        apply_platform_config(overlay)
+    }
+
+    /// Make every overlay click-through, remembering each one's own state, or
+    /// restore those states. Overlays spawned in between keep their current state.
+    pub fn set_click_through_all(&mut self, enabled: bool) -> Result<(), Error> {
+        if enabled && self.saved_click_through.is_none() {
+            self.saved_click_through = Some(
+                self.windows
+                    .iter()
+                    .map(|(id, overlay)| (id.clone(), overlay.config.click_through))
+                    .collect(),
+            );
+        }
+        let saved = if enabled {
+            None
+        } else {
+            self.saved_click_through.take()
+        };
+
+        for (id, overlay) in self.windows.iter_mut() {
+            let own = saved
+                .as_ref()
+                .and_then(|saved| saved.get(id))
+                .copied()
+                .unwrap_or(overlay.config.click_through);
+            overlay.config.click_through = enabled || own;
+            if let Err(e) = apply_platform_config(overlay) {
+                tracing::warn!("Failed to set click-through for overlay {}: {}", id, e);
+            }
+        }
 
         Ok(())
     }
 
+    /// Check whether click-through is currently forced on for all overlays
+    pub fn is_click_through_all(&self) -> bool {
+        self.saved_click_through.is_some()
+    }
+
+    /// Reapply the full platform configuration of a live overlay from its settings
+    /// (window level, collection behavior, click-through, shadow)
+    pub fn reconfigure(&mut self, id: &str, settings: &OverlaySettings) -> Result<(), Error> {
+        let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        overlay.config.click_through = settings.click_through;
+        overlay.config.always_on_top = settings.always_on_top;
+        overlay.config.desktop_level = settings.desktop_level;
+        overlay.config.show_on_fullscreen = settings.show_on_fullscreen;
+        overlay.config.hide_on_fullscreen = settings.hide_on_fullscreen;
+        overlay.config.exclude_from_bulk_toggle = settings.exclude_from_bulk_toggle;
+        overlay.config.locked = settings.locked;
+        overlay.config.schedule = settings.schedule.clone();
+        overlay.config.corner_radius = validate_length(settings.corner_radius).unwrap_or(0.0);
+        overlay.config.border = settings
+            .border
+            .filter(|border| validate_length(border.width).is_ok());
+        if let Some(padding) = settings.padding {
+            overlay.config.padding = padding;
+        }
+        if overlay.config.inject_css != settings.inject_css {
+            overlay.config.inject_css = settings.inject_css.clone();
+            overlay.window.eval(overlay.config.css_script())?;
+        }
+
+        overlay.window.eval(overlay.config.chrome_script())?;
+        apply_platform_config(overlay)
+    }
+
     /// Update the geometry (position and size) of an existing overlay window
     pub fn update_overlay_geometry(
         &mut self,
@@ -153,27 +384,675 @@ impl OverlayManager {
         overlay.config.width = width;
         overlay.config.height = height;
 
+        // Stop any running animation, this geometry wins
+        overlay.animation = None;
+
         // Apply the new position using unconstrained positioning (for macOS menu bar area)
-        set_unconstrained_position(&overlay.window, x, y, width, height)?;
+        set_unconstrained_position(&overlay.window, x, y, width, height, true)?;
 
         Ok(())
     }
 
-    /// Set always-on-top state for an overlay window
-    pub fn set_always_on_top(&self, id: &str, enabled: bool) -> Result<(), Error> {
+    /// Move and resize an overlay like `update_overlay_geometry`, animating from its
+    /// current frame over `duration_ms`. Snaps right away when the duration is 0 or
+    /// the user prefers reduced motion. Any later geometry change stops the animation.
+    pub fn update_overlay_geometry_animated(
+        &mut self,
+        id: &str,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        duration_ms: u64,
+    ) -> Result<(), Error> {
+        let duration = Duration::from_millis(duration_ms.min(MAX_GEOMETRY_ANIMATION_MS));
+        if duration.is_zero() || prefers_reduced_motion() {
+            return self.update_overlay_geometry(id, x, y, width, height);
+        }
+
+        let animation_id = self.next_animation_id;
         let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        // Pick up from wherever a running animation got to
+        let from = match &overlay.animation {
+            Some(animation) => animation_frame(animation),
+            None => Rect {
+                x: overlay.config.x,
+                y: overlay.config.y,
+                width: overlay.config.width,
+                height: overlay.config.height,
+            },
+        };
+        let to = Rect {
+            x,
+            y,
+            width,
+            height,
+        };
+
+        // The config holds the target right away, so it is what gets persisted
+        overlay.config.x = x;
+        overlay.config.y = y;
+        overlay.config.width = width;
+        overlay.config.height = height;
+
+        // Replacing a running animation drops it, which stops its task
+        let started = Instant::now();
+        let stopped = Arc::new(Mutex::new(false));
+        overlay.animation = Some(GeometryAnimation {
+            animation_id,
+            from,
+            to,
+            started,
+            duration,
+            stopped: stopped.clone(),
+        });
+        self.next_animation_id += 1;
+
+        // Steps only take the animation's own lock, not the overlay state
+        let window = overlay.window.clone();
+        let app = self.app.clone();
+        let id = id.to_string();
+        tauri::async_runtime::spawn(async move {
+            let mut frames = tokio::time::interval(GEOMETRY_ANIMATION_FRAME);
+
+            loop {
+                frames.tick().await;
+                let progress = animation_progress(started, duration);
+                let frame = interpolate_frame(from, to, ease_in_out(progress));
+
+                {
+                    let Ok(stopped) = stopped.lock() else {
+                        return;
+                    };
+                    if *stopped {
+                        return;
+                    }
+                    if let Err(e) = set_window_frame(&window, frame) {
+                        tracing::warn!("Failed to animate overlay {}: {}", id, e);
+                        return;
+                    }
+                }
+
+                if progress >= 1.0 {
+                    break;
+                }
+            }
+
+            let state = app.state::<OverlayState>();
+            if let Ok(mut manager) = state.0.lock() {
+                manager.end_geometry_animation(&id, animation_id);
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Drop a finished geometry animation, unless a newer one has replaced it
+    fn end_geometry_animation(&mut self, id: &str, animation_id: u64) {
+        if let Some(overlay) = self.windows.get_mut(id)
+            && overlay
+                .animation
+                .as_ref()
+                .is_some_and(|animation| animation.animation_id == animation_id)
+        {
+            overlay.animation = None;
+        }
+    }
+
+    /// Move an overlay window, keeping its size
+    pub fn set_overlay_position(&mut self, id: &str, x: f64, y: f64) -> Result<(), Error> {
+        let config = &self
             .windows
             .get(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?
+            .config;
+        let (width, height) = (config.width, config.height);
+
+        self.update_overlay_geometry(id, x, y, width, height)
+    }
+
+    /// Resize an overlay window, keeping its position
+    pub fn set_overlay_size(&mut self, id: &str, width: f64, height: f64) -> Result<(), Error> {
+        let config = &self
+            .windows
+            .get(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?
+            .config;
+        let (x, y) = (config.x, config.y);
+
+        self.update_overlay_geometry(id, x, y, width, height)
+    }
+
+    /// Set always-on-top state for an overlay window
+    pub fn set_always_on_top(&mut self, id: &str, enabled: bool) -> Result<(), Error> {
+        let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        overlay.config.always_on_top = enabled;
+        apply_platform_config(overlay)
+    }
+
+    /// Set the content zoom factor of an overlay window (e.g. 1.25 for 125%)
+    pub fn set_zoom(&mut self, id: &str, zoom: f64) -> Result<(), Error> {
+        if !zoom.is_finite() || zoom <= 0.0 {
+            return Err(Error::WindowCreation(format!(
+                "Invalid zoom factor: {}",
+                zoom
+            )));
+        }
+
+        let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        overlay
+            .window
+            .set_zoom(zoom)
+            .map_err(|e| Error::WindowCreation(e.to_string()))?;
+        overlay.config.zoom = zoom;
+
+        Ok(())
+    }
+
+    /// Toggle window decorations (framed vs frameless) without reopening the overlay
+    pub fn set_decorations(&mut self, id: &str, enabled: bool) -> Result<(), Error> {
+        let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        overlay
+            .window
+            .set_decorations(enabled)
+            .map_err(|e| Error::WindowCreation(e.to_string()))?;
+        overlay.config.frameless = !enabled;
+
+        // Changing the style mask on macOS resets the window level and lets AppKit
+        // constrain the frame again, so reapply the overlay configuration and position
+        #[cfg(target_os = "macos")]
+        {
+            configure_overlay(&overlay.window, &platform_config(overlay))?;
+            overlay.animation = None;
+            let config = &overlay.config;
+
+            // set_unconstrained_position also orders the window front, so skip hidden overlays
+            if overlay.window.is_visible().unwrap_or(false) {
+                set_unconstrained_position(
+                    &overlay.window,
+                    config.x,
+                    config.y,
+                    config.width,
+                    config.height,
+                    true,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Set the window background color (RGBA)
+    pub fn set_background_color(&mut self, id: &str, color: [u8; 4]) -> Result<(), Error> {
+        let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        overlay
+            .window
+            .set_background_color(Some(background(color)))
+            .map_err(|e| Error::WindowCreation(e.to_string()))?;
+        overlay.config.background_color = color;
+
+        Ok(())
+    }
+
+    /// Set an overlay's corner radius and border, natively where supported and
+    /// as CSS variables in the webview
+    pub fn set_chrome(
+        &mut self,
+        id: &str,
+        corner_radius: f64,
+        border: Option<OverlayBorder>,
+    ) -> Result<(), Error> {
+        validate_length(corner_radius)?;
+        if let Some(border) = &border {
+            validate_length(border.width)?;
+        }
+
+        let overlay = self
+            .windows
+            .get_mut(id)
             .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
 
+        overlay.config.corner_radius = corner_radius;
+        overlay.config.border = border;
+
+        overlay.window.eval(overlay.config.chrome_script())?;
+        apply_platform_config(overlay)
+    }
+
+    /// Replace the user CSS injected into an overlay, None removes it
+    pub fn inject_css(&mut self, id: &str, css: Option<String>) -> Result<(), Error> {
+        let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        overlay.config.inject_css = css.filter(|css| !css.is_empty());
+        overlay.window.eval(overlay.config.css_script())?;
+
+        Ok(())
+    }
+
+    /// Show or hide an overlay over fullscreen apps (macOS only)
+    pub fn set_show_on_fullscreen(&mut self, id: &str, enabled: bool) -> Result<(), Error> {
+        let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        overlay.config.show_on_fullscreen = enabled;
+        apply_platform_config(overlay)
+    }
+
+    /// Pin an overlay to a desktop layer behind all windows, None floats it again
+    pub fn set_desktop_level(
+        &mut self,
+        id: &str,
+        level: Option<DesktopLevel>,
+    ) -> Result<(), Error> {
+        let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        overlay.config.desktop_level = level;
+        apply_platform_config(overlay)
+    }
+
+    /// Set the regions of an overlay that capture the mouse. Everything outside them
+    /// passes clicks through; an empty list restores the overlay's click-through setting.
+    pub fn set_interactive_regions(&mut self, id: &str, regions: Vec<Rect>) -> Result<(), Error> {
+        let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        // Start passing clicks through until the hit test loop sees the cursor in a region
+        let ignore = if regions.is_empty() {
+            overlay.config.click_through
+        } else {
+            true
+        };
+
         overlay
             .window
-            .set_always_on_top(enabled)
+            .set_ignore_cursor_events(ignore)
             .map_err(|e| Error::WindowCreation(e.to_string()))?;
 
+        overlay.interactive_regions = regions;
+        overlay.cursor_captured = false;
+
         Ok(())
     }
 
+    /// Snapshot the overlays that need cursor hit testing
+    pub(crate) fn hit_test_targets(&self) -> Vec<HitTestTarget> {
+        self.windows
+            .values()
+            .filter(|o| !o.interactive_regions.is_empty())
+            .map(|o| HitTestTarget {
+                id: o.config.id.clone(),
+                window: o.window.clone(),
+                regions: o.interactive_regions.clone(),
+                zoom: o.config.zoom,
+                captured: o.cursor_captured,
+            })
+            .collect()
+    }
+
+    /// Record whether the cursor is inside one of an overlay's interactive regions
+    pub(crate) fn set_cursor_captured(&mut self, id: &str, captured: bool) {
+        if let Some(overlay) = self.windows.get_mut(id) {
+            // Regions may have been cleared while the hit test was running
+            if !overlay.interactive_regions.is_empty() {
+                overlay.cursor_captured = captured;
+            }
+        }
+    }
+
+    /// Set the resting opacity and the opacity while hovered (both 0 - 100).
+    /// Passing None for `hover_opacity` disables the hover fade.
+    pub fn set_opacity(
+        &mut self,
+        id: &str,
+        opacity: f64,
+        hover_opacity: Option<f64>,
+    ) -> Result<(), Error> {
+        validate_opacity(opacity)?;
+        if let Some(hover_opacity) = hover_opacity {
+            validate_opacity(hover_opacity)?;
+        }
+
+        let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        overlay.config.opacity = opacity;
+        overlay.config.hover_opacity = hover_opacity;
+
+        // Without hover fading the SDK applies the opacity, so reset the window alpha.
+        // Otherwise the hover loop fades from the current alpha to the new target.
+        if overlay.config.hover_opacity.is_none() && overlay.hover_alpha != 1.0 {
+            set_window_alpha(&overlay.window, 1.0)?;
+            overlay.hover_alpha = 1.0;
+        }
+
+        Ok(())
+    }
+
+    /// Snapshot the overlays that have hover fading enabled
+    pub(crate) fn hover_targets(&self) -> Vec<HoverTarget> {
+        self.windows
+            .values()
+            .filter_map(|o| {
+                let hover = o.config.hover_opacity?;
+                Some(HoverTarget {
+                    id: o.config.id.clone(),
+                    window: o.window.clone(),
+                    resting: o.config.opacity / 100.0,
+                    hover: hover / 100.0,
+                    alpha: o.hover_alpha,
+                    click_through: o.config.click_through,
+                })
+            })
+            .collect()
+    }
+
+    /// Record the window alpha applied by the hover fade
+    pub(crate) fn set_hover_alpha(&mut self, id: &str, alpha: f64) {
+        if let Some(overlay) = self.windows.get_mut(id) {
+            overlay.hover_alpha = alpha;
+        }
+    }
+
+    /// Snapshot the overlays that hide while an app is fullscreen
+    pub(crate) fn fullscreen_targets(&self) -> Vec<FullscreenTarget> {
+        self.windows
+            .values()
+            .filter(|o| o.config.hide_on_fullscreen && o.flash.is_none())
+            .map(|o| FullscreenTarget {
+                id: o.config.id.clone(),
+                window: o.window.clone(),
+                hidden: o.hidden_for_fullscreen,
+                hidden_by_schedule: o.hidden_by_schedule,
+            })
+            .collect()
+    }
+
+    /// Record whether an overlay is hidden because of a fullscreen app
+    pub(crate) fn set_hidden_for_fullscreen(&mut self, id: &str, hidden: bool) {
+        if let Some(overlay) = self.windows.get_mut(id) {
+            overlay.hidden_for_fullscreen = hidden;
+        }
+    }
+
+    /// Store an overlay's current size, returning whether it changed
+    pub(crate) fn record_size(&mut self, id: &str, width: f64, height: f64) -> bool {
+        let Some(overlay) = self.windows.get_mut(id) else {
+            return false;
+        };
+
+        // Sub-pixel differences come from rounding through physical pixels
+        let changed = (overlay.config.width - width).abs() >= 0.5
+            || (overlay.config.height - height).abs() >= 0.5;
+        overlay.config.width = width;
+        overlay.config.height = height;
+        changed
+    }
+
+    /// Snapshot the overlays that have a visibility schedule
+    pub(crate) fn schedule_targets(&self) -> Vec<ScheduleTarget> {
+        self.windows
+            .values()
+            .filter(|o| o.flash.is_none())
+            .filter(|o| !o.config.schedule.is_empty() || o.hidden_by_schedule)
+            .map(|o| ScheduleTarget {
+                id: o.config.id.clone(),
+                window: o.window.clone(),
+                schedule: o.config.schedule.clone(),
+                hidden: o.hidden_by_schedule,
+                hidden_for_fullscreen: o.hidden_for_fullscreen,
+            })
+            .collect()
+    }
+
+    /// Record whether an overlay is hidden because it is outside its schedule
+    pub(crate) fn set_hidden_by_schedule(&mut self, id: &str, hidden: bool) {
+        if let Some(overlay) = self.windows.get_mut(id) {
+            overlay.hidden_by_schedule = hidden;
+        }
+    }
+
+    /// Replace an overlay's visibility schedule, an empty one keeps it always visible.
+    /// Takes effect on the next `sync_scheduled_visibility`.
+    pub fn set_schedule(&mut self, id: &str, schedule: Vec<ScheduleWindow>) -> Result<(), Error> {
+        let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        overlay.config.schedule = schedule;
+        Ok(())
+    }
+
+    /// Turn desktop-only mode on or off for an overlay
+    pub fn set_hide_on_fullscreen(&mut self, id: &str, enabled: bool) -> Result<(), Error> {
+        let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        overlay.config.hide_on_fullscreen = enabled;
+
+        // Don't leave it hidden with nothing left to bring it back
+        if !enabled && overlay.hidden_for_fullscreen {
+            overlay.window.show()?;
+            overlay.hidden_for_fullscreen = false;
+        }
+
+        Ok(())
+    }
+
+    /// Assign an overlay to a group, or remove it from its group with None
+    pub fn set_group(&mut self, id: &str, group: Option<String>) -> Result<(), Error> {
+        let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        overlay.config.group = group;
+        Ok(())
+    }
+
+    /// Lock an overlay in place so group moves leave it where it is
+    pub fn set_locked(&mut self, id: &str, locked: bool) -> Result<(), Error> {
+        let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        overlay.config.locked = locked;
+        Ok(())
+    }
+
+    /// Leave an overlay out of (or bring it back into) show all / hide all
+    pub fn set_exclude_from_bulk_toggle(&mut self, id: &str, excluded: bool) -> Result<(), Error> {
+        let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        overlay.config.exclude_from_bulk_toggle = excluded;
+        Ok(())
+    }
+
+    /// Show or hide every overlay not excluded from bulk toggles.
+    /// Returns the IDs of the overlays that were changed.
+    pub fn set_visible_all(&self, visible: bool) -> Result<Vec<String>, Error> {
+        let ids: Vec<String> = self
+            .windows
+            .values()
+            .filter(|o| !o.config.exclude_from_bulk_toggle)
+            .map(|o| o.config.id.clone())
+            .collect();
+
+        for id in &ids {
+            self.set_visible(id, visible)?;
+        }
+        Ok(ids)
+    }
+
+    /// Get the IDs of all overlays in a group
+    pub fn group_members(&self, group: &str) -> Vec<String> {
+        self.windows
+            .values()
+            .filter(|o| o.config.group.as_deref() == Some(group))
+            .map(|o| o.config.id.clone())
+            .collect()
+    }
+
+    /// Show or hide every overlay in a group
+    pub fn set_group_visible(&self, group: &str, visible: bool) -> Result<(), Error> {
+        for id in self.group_members(group) {
+            self.set_visible(&id, visible)?;
+        }
+        Ok(())
+    }
+
+    /// Translate every unlocked overlay in a group by the same delta.
+    /// Returns the IDs of the overlays that moved.
+    pub fn move_group(&mut self, group: &str, dx: f64, dy: f64) -> Result<Vec<String>, Error> {
+        let members: Vec<OverlayConfig> = self
+            .windows
+            .values()
+            .filter(|o| o.config.group.as_deref() == Some(group) && !o.config.locked)
+            .map(|o| o.config.clone())
+            .collect();
+
+        let mut moved = Vec::new();
+        for config in members {
+            self.update_overlay_geometry(
+                &config.id,
+                config.x + dx,
+                config.y + dy,
+                config.width,
+                config.height,
+            )?;
+            moved.push(config.id);
+        }
+
+        Ok(moved)
+    }
+
+    /// Move an overlay onto a monitor. Overlays placed from a position preset are
+    /// re-placed from that preset, others keep their offset within the monitor.
+    /// Returns the new (x, y) position.
+    pub fn move_to_monitor(
+        &mut self,
+        id: &str,
+        target: MonitorBounds,
+    ) -> Result<(f64, f64), Error> {
+        let config = self
+            .windows
+            .get(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?
+            .config
+            .clone();
+
+        let (x, y) = match &config.position_preset {
+            Some(preset) => {
+                target.position_for_preset(preset, config.width, config.height, config.padding)
+            }
+            None => {
+                let current = monitor_bounds(&self.app)
+                    .into_iter()
+                    .find(|m| m.contains(config.x, config.y))
+                    .unwrap_or(target);
+                target.translate_from(&current, config.x, config.y, config.width, config.height)
+            }
+        };
+
+        self.update_overlay_geometry(id, x, y, config.width, config.height)?;
+        Ok((x, y))
+    }
+
+    /// Move every overlay whose position is outside all of the given monitors onto
+    /// the primary monitor, using its position preset (centered if it has none)
+    pub fn relocate_offscreen_overlays(
+        &mut self,
+        monitors: &[MonitorBounds],
+        primary: MonitorBounds,
+    ) -> Vec<OverlayRelocatedEvent> {
+        let offscreen: Vec<OverlayConfig> = self
+            .windows
+            .values()
+            .filter(|o| !monitors.iter().any(|m| m.contains(o.config.x, o.config.y)))
+            .map(|o| o.config.clone())
+            .collect();
+
+        let mut relocated = Vec::new();
+        for config in offscreen {
+            let preset = config.position_preset.as_deref().unwrap_or("center");
+            let (x, y) =
+                primary.position_for_preset(preset, config.width, config.height, config.padding);
+
+            match self.update_overlay_geometry(&config.id, x, y, config.width, config.height) {
+                Ok(()) => relocated.push(OverlayRelocatedEvent {
+                    id: config.id,
+                    x,
+                    y,
+                }),
+                Err(e) => {
+                    tracing::error!("Failed to relocate overlay {}: {}", config.id, e);
+                }
+            }
+        }
+
+        relocated
+    }
+
+    /// Spawn a copy of an overlay under a new id (`<id>-copy-<n>`), offset from the original.
+    /// Returns the copy's config.
+    pub fn duplicate_overlay(&mut self, id: &str) -> Result<OverlayConfig, Error> {
+        let source = self
+            .windows
+            .get(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        let mut config = source.config.clone();
+        config.id = (2..)
+            .map(|n| format!("{}-copy-{}", id, n))
+            .find(|candidate| !self.windows.contains_key(candidate))
+            .expect("unbounded range always yields a free id");
+        config.x += DUPLICATE_OFFSET;
+        config.y += DUPLICATE_OFFSET;
+        // The copy sits at a custom position, not the source's preset
+        config.position_preset = None;
+
+        self.spawn_overlay(config.clone())?;
+        Ok(config)
+    }
+
     /// Check if an overlay with the given ID exists
     pub fn has_overlay(&self, id: &str) -> bool {
         self.windows.contains_key(id)
@@ -201,6 +1080,94 @@ impl OverlayManager {
         Ok(())
     }
 
+    /// Show an overlay that was waiting for its content to paint.
+    /// Returns false if it wasn't waiting (already shown, or shown by the timeout).
+    /// Overlays hidden by their schedule or a fullscreen app in the meantime stay hidden.
+    pub fn mark_content_ready(&mut self, id: &str) -> Result<bool, Error> {
+        let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        if !overlay.awaiting_content {
+            return Ok(false);
+        }
+        overlay.awaiting_content = false;
+
+        if !overlay.hidden_by_schedule && !overlay.hidden_for_fullscreen {
+            overlay.animation = None;
+            let config = &overlay.config;
+            set_unconstrained_position(
+                &overlay.window,
+                config.x,
+                config.y,
+                config.width,
+                config.height,
+                true,
+            )?;
+            // macOS is ordered front above without taking focus
+            #[cfg(not(target_os = "macos"))]
+            overlay.window.show()?;
+        }
+
+        Ok(true)
+    }
+
+    /// Show an overlay above everything, including fullscreen apps, until
+    /// `end_flash` is called with the returned flash ID. Flashing an overlay
+    /// that is already flashing replaces the earlier flash.
+    pub fn start_flash(&mut self, id: &str) -> Result<u64, Error> {
+        let flash_id = self.next_flash_id;
+        let overlay = self
+            .windows
+            .get_mut(id)
+            .ok_or_else(|| Error::WindowNotFound(id.to_string()))?;
+
+        let was_visible = match overlay.flash {
+            Some(flash) => flash.was_visible,
+            None => overlay.window.is_visible().unwrap_or(false),
+        };
+
+        raise_for_flash(&overlay.window, &overlay.config)?;
+        overlay.window.set_always_on_top(true)?;
+        overlay.window.set_ignore_cursor_events(false)?;
+        overlay
+            .window
+            .show()
+            .map_err(|e| Error::WindowCreation(e.to_string()))?;
+
+        overlay.flash = Some(FlashRestore {
+            flash_id,
+            was_visible,
+        });
+        self.next_flash_id += 1;
+        Ok(flash_id)
+    }
+
+    /// Put a flashing overlay back the way it was: its own window level,
+    /// click-through and fullscreen behavior, and hidden again if it was hidden.
+    /// Does nothing if the overlay has been flashed again since.
+    pub fn end_flash(&mut self, id: &str, flash_id: u64) -> Result<(), Error> {
+        let Some(overlay) = self.windows.get_mut(id) else {
+            return Ok(());
+        };
+        if overlay.flash.map(|flash| flash.flash_id) != Some(flash_id) {
+            return Ok(());
+        }
+
+        let flash = overlay.flash.take();
+        apply_platform_config(overlay)?;
+
+        if flash.is_some_and(|flash| !flash.was_visible) {
+            overlay
+                .window
+                .hide()
+                .map_err(|e| Error::WindowCreation(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
     pub fn registry(&self) -> &ServiceRegistry {
         &self.registry
     }
@@ -209,3 +1176,226 @@ impl OverlayManager {
         &mut self.registry
     }
 }
+
+/// Cubic ease-in-out of an animation's progress (0.0 - 1.0)
+fn ease_in_out(t: f64) -> f64 {
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+    }
+}
+
+/// How far (0.0 - 1.0) an animation started at `started` has got
+fn animation_progress(started: Instant, duration: Duration) -> f64 {
+    (started.elapsed().as_secs_f64() / duration.as_secs_f64()).min(1.0)
+}
+
+/// Frame a running animation is at right now
+fn animation_frame(animation: &GeometryAnimation) -> Rect {
+    let progress = animation_progress(animation.started, animation.duration);
+    interpolate_frame(animation.from, animation.to, ease_in_out(progress))
+}
+
+/// Frame a fraction `t` of the way from `from` to `to`
+fn interpolate_frame(from: Rect, to: Rect, t: f64) -> Rect {
+    let lerp = |a: f64, b: f64| a + (b - a) * t;
+    Rect {
+        x: lerp(from.x, to.x),
+        y: lerp(from.y, to.y),
+        width: lerp(from.width, to.width),
+        height: lerp(from.height, to.height),
+    }
+}
+
+/// Bring a live window in line with its config. Each setting is applied in both
+/// directions so toggling one off actually undoes it.
+fn apply_platform_config(overlay: &OverlayWindow) -> Result<(), Error> {
+    let window = &overlay.window;
+    let config = &overlay.config;
+
+    // Overlays with interactive regions have their cursor events driven by the hit-test loop
+    if overlay.interactive_regions.is_empty() {
+        window.set_ignore_cursor_events(config.click_through)?;
+    }
+    window.set_focusable(!config.click_through)?;
+    window.set_shadow(!config.click_through)?;
+    window.set_always_on_top(config.always_on_top && config.desktop_level.is_none())?;
+    window.set_always_on_bottom(config.desktop_level.is_some())?;
+
+    configure_overlay(window, &platform_config(overlay))
+}
+
+/// The config to hand `configure_overlay`. While interactive regions are set the
+/// hit-test loop owns cursor events, so keep what it last decided instead of
+/// resetting them to the click-through setting.
+fn platform_config(overlay: &OverlayWindow) -> OverlayConfig {
+    let mut config = overlay.config.clone();
+    if !overlay.interactive_regions.is_empty() {
+        config.click_through = !overlay.cursor_captured;
+    }
+    config
+}
+
+/// Check an opacity is a 0 - 100 percentage
+fn validate_opacity(opacity: f64) -> Result<f64, Error> {
+    if !opacity.is_finite() || !(0.0..=100.0).contains(&opacity) {
+        return Err(Error::WindowCreation(format!(
+            "Invalid opacity: {}",
+            opacity
+        )));
+    }
+    Ok(opacity)
+}
+
+/// Check a corner radius or border width is a non-negative size in pixels
+fn validate_length(length: f64) -> Result<f64, Error> {
+    if !length.is_finite() || length < 0.0 {
+        return Err(Error::WindowCreation(format!("Invalid length: {}", length)));
+    }
+    Ok(length)
+}
+
+fn background([r, g, b, a]: [u8; 4]) -> Color {
+    Color(r, g, b, a)
+}
+
+/// Bring a reloaded overlay page up to date with the overlay's current config, and
+/// show an overlay that was waiting for its first load
+fn handle_page_load(window: WebviewWindow, payload: PageLoadPayload<'_>) {
+    if payload.event() != PageLoadEvent::Finished {
+        return;
+    }
+
+    // Off the main thread - commands may hold the lock while waiting on it
+    let app = window.app_handle().clone();
+    let id = window.label().to_string();
+    tauri::async_runtime::spawn(async move {
+        if let Some(state) = app.try_state::<OverlayState>()
+            && let Ok(mut manager) = state.0.lock()
+        {
+            // Not registered yet on the first load, the initialization scripts are current
+            let _ = manager.reapply_page_scripts(&id);
+            // Plugins without the SDK never call overlay_ready
+            let _ = manager.mark_content_ready(&id);
+        }
+    });
+}
+
+/// Record and persist a resized overlay's logical size and tell its settings hook
+fn handle_overlay_resized(app: &AppHandle, id: &str, size: PhysicalSize<u32>) {
+    // Minimizing reports a zero size
+    if size.width == 0 || size.height == 0 {
+        return;
+    }
+
+    // The builder takes logical sizes, so store what the next spawn expects on HiDPI screens
+    let scale = app
+        .get_webview_window(id)
+        .and_then(|window| window.scale_factor().ok())
+        .unwrap_or(1.0);
+    let size = size.to_logical::<f64>(scale);
+    let event = OverlayResizedEvent {
+        id: id.to_string(),
+        width: size.width,
+        height: size.height,
+    };
+
+    // Update the config off the main thread - commands may hold the lock while waiting on it
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let Some(state) = app.try_state::<OverlayState>() else {
+            return;
+        };
+        let changed = match state.0.lock() {
+            Ok(mut manager) => manager.record_size(&event.id, event.width, event.height),
+            Err(_) => return,
+        };
+        if changed {
+            crate::commands::persist_overlay_size(&app, &event.id, event.width, event.height);
+            let _ = app.emit(OVERLAY_RESIZED_EVENT, &event);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn ease_in_out_starts_and_ends_in_place() {
+        assert_close(ease_in_out(0.0), 0.0);
+        assert_close(ease_in_out(0.5), 0.5);
+        assert_close(ease_in_out(1.0), 1.0);
+    }
+
+    #[test]
+    fn ease_in_out_is_symmetric() {
+        for t in [0.1, 0.25, 0.4] {
+            assert_close(ease_in_out(t), 1.0 - ease_in_out(1.0 - t));
+        }
+    }
+
+    #[test]
+    fn ease_in_out_is_slow_at_the_ends() {
+        assert!(ease_in_out(0.1) < 0.1);
+        assert!(ease_in_out(0.9) > 0.9);
+    }
+
+    #[test]
+    fn ease_in_out_never_goes_backwards() {
+        let mut last = 0.0;
+        for step in 1..=100 {
+            let eased = ease_in_out(step as f64 / 100.0);
+            assert!(eased >= last, "eased progress went back at step {step}");
+            last = eased;
+        }
+    }
+
+    #[test]
+    fn interpolate_frame_hits_both_ends() {
+        let from = rect(0.0, 10.0, 100.0, 50.0);
+        let to = rect(200.0, -30.0, 300.0, 150.0);
+
+        let start = interpolate_frame(from, to, 0.0);
+        assert_close(start.x, 0.0);
+        assert_close(start.y, 10.0);
+        assert_close(start.width, 100.0);
+        assert_close(start.height, 50.0);
+
+        let end = interpolate_frame(from, to, 1.0);
+        assert_close(end.x, 200.0);
+        assert_close(end.y, -30.0);
+        assert_close(end.width, 300.0);
+        assert_close(end.height, 150.0);
+    }
+
+    #[test]
+    fn interpolate_frame_moves_every_edge_linearly() {
+        let frame = interpolate_frame(
+            rect(0.0, 10.0, 100.0, 50.0),
+            rect(200.0, -30.0, 300.0, 150.0),
+            0.25,
+        );
+        assert_close(frame.x, 50.0);
+        assert_close(frame.y, 0.0);
+        assert_close(frame.width, 150.0);
+        assert_close(frame.height, 75.0);
+    }
+}
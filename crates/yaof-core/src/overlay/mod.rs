@@ -1,7 +1,27 @@
+pub mod capture;
+pub mod fullscreen;
+pub mod hit_test;
+pub mod hover;
 pub mod manager;
+pub mod monitor;
 pub mod platform;
+pub mod schedule;
 pub mod window;
 
+pub use fullscreen::sync_fullscreen_visibility;
+pub use hit_test::{Rect, start_hit_test_loop};
+pub use manager::{
+    OVERLAY_CLOSED_EVENT, OVERLAY_RESIZED_EVENT, OVERLAY_SPAWNED_EVENT, OverlayMessage,
+    OverlayResizedEvent, overlay_message_event,
+};
+pub use monitor::{
+    MonitorBounds, MonitorInfo, MonitorSelector, OVERLAY_RELOCATED_EVENT, OverlayRelocatedEvent,
+    start_monitor_watch,
+};
 pub use platform::configure_overlay;
+pub use platform::raise_for_flash;
 pub use platform::set_unconstrained_position;
+pub use platform::set_window_alpha;
+pub use platform::{prefers_reduced_motion, set_window_frame};
+pub use schedule::{ScheduleDay, ScheduleWindow, sync_scheduled_visibility};
 pub use window::*;
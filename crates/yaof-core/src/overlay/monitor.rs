@@ -0,0 +1,234 @@
+//! Monitor lookup and multi-monitor overlay positioning
+//!
+//! Overlay coordinates are logical pixels on the virtual desktop, so monitor
+//! bounds are converted from physical pixels using each monitor's scale factor.
+//!
+//! Tauri has no monitor hotplug event, so the monitor layout is polled and
+//! overlays left off-screen (e.g. after unplugging a display) are moved back
+//! onto the primary monitor.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Monitor};
+
+use crate::OverlayState;
+use crate::settings::{Padding, ScreenInfo, calculate_position_from_preset};
+
+/// Emitted for each overlay moved back on screen after a monitor change
+pub const OVERLAY_RELOCATED_EVENT: &str = "yaof:overlay:relocated";
+
+/// How often the monitor layout is checked for changes
+const MONITOR_POLL_INTERVAL_MS: u64 = 2000;
+
+/// Payload of the `yaof:overlay:relocated` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayRelocatedEvent {
+    pub id: String,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Identifies a monitor either by its position in the monitor list or by name
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum MonitorSelector {
+    Index(usize),
+    Name(String),
+}
+
+/// A connected monitor as reported to the frontend, in logical pixels.
+/// Its position in the list is the index accepted by `MonitorSelector::Index`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorInfo {
+    pub name: Option<String>,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub scale_factor: f64,
+    pub is_primary: bool,
+}
+
+/// Monitor bounds in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonitorBounds {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl MonitorBounds {
+    pub fn from_monitor(monitor: &Monitor) -> Self {
+        let scale = monitor.scale_factor();
+        let position = monitor.position();
+        let size = monitor.size();
+
+        Self {
+            x: position.x as f64 / scale,
+            y: position.y as f64 / scale,
+            width: size.width as f64 / scale,
+            height: size.height as f64 / scale,
+        }
+    }
+
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+
+    /// Position of a window of the given size on this monitor for a preset
+    pub fn position_for_preset(
+        &self,
+        preset: &str,
+        width: f64,
+        height: f64,
+        padding: Padding,
+    ) -> (f64, f64) {
+        let screen = ScreenInfo {
+            width: self.width,
+            height: self.height,
+        };
+        let (x, y) = calculate_position_from_preset(preset, &screen, width, height, padding);
+        (self.x + x, self.y + y)
+    }
+
+    /// Translate a position from another monitor onto this one, keeping its offset
+    /// from the monitor's origin and keeping the window fully on screen
+    pub fn translate_from(
+        &self,
+        from: &MonitorBounds,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    ) -> (f64, f64) {
+        let x = self.x + (x - from.x);
+        let y = self.y + (y - from.y);
+
+        (
+            x.min(self.x + self.width - width).max(self.x),
+            y.min(self.y + self.height - height).max(self.y),
+        )
+    }
+}
+
+/// Get the bounds of every connected monitor
+pub fn monitor_bounds(app: &AppHandle) -> Vec<MonitorBounds> {
+    app.available_monitors()
+        .map(|monitors| monitors.iter().map(MonitorBounds::from_monitor).collect())
+        .unwrap_or_default()
+}
+
+/// Describe every connected monitor, in the order used for index selection
+pub fn list_monitors(app: &AppHandle) -> Vec<MonitorInfo> {
+    let primary = app.primary_monitor().ok().flatten();
+    let is_primary = |monitor: &Monitor| {
+        primary
+            .as_ref()
+            .is_some_and(|p| p.name() == monitor.name() && p.position() == monitor.position())
+    };
+
+    app.available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .map(|monitor| {
+            let bounds = MonitorBounds::from_monitor(monitor);
+            MonitorInfo {
+                name: monitor.name().cloned(),
+                x: bounds.x,
+                y: bounds.y,
+                width: bounds.width,
+                height: bounds.height,
+                scale_factor: monitor.scale_factor(),
+                is_primary: is_primary(monitor),
+            }
+        })
+        .collect()
+}
+
+/// Get the bounds of the primary monitor
+pub fn primary_monitor_bounds(app: &AppHandle) -> Option<MonitorBounds> {
+    app.primary_monitor()
+        .ok()
+        .flatten()
+        .map(|m| MonitorBounds::from_monitor(&m))
+}
+
+/// Find a monitor by index or name, falling back to the primary monitor when it
+/// no longer exists (e.g. it was unplugged)
+pub fn find_monitor(app: &AppHandle, selector: &MonitorSelector) -> Option<MonitorBounds> {
+    let monitors = app.available_monitors().unwrap_or_default();
+
+    let found = match selector {
+        MonitorSelector::Index(index) => monitors.get(*index),
+        MonitorSelector::Name(name) => monitors
+            .iter()
+            .find(|m| m.name().is_some_and(|n| n == name)),
+    };
+
+    match found {
+        Some(monitor) => Some(MonitorBounds::from_monitor(monitor)),
+        None => {
+            tracing::warn!(
+                "Monitor {:?} not found, using the primary monitor",
+                selector
+            );
+            primary_monitor_bounds(app)
+        }
+    }
+}
+
+/// Find the monitor the cursor is currently on
+pub fn cursor_monitor(app: &AppHandle) -> Option<MonitorBounds> {
+    let cursor = app.cursor_position().ok()?;
+    app.monitor_from_point(cursor.x, cursor.y)
+        .ok()
+        .flatten()
+        .map(|m| MonitorBounds::from_monitor(&m))
+        .or_else(|| primary_monitor_bounds(app))
+}
+
+/// Watch for monitors being added or removed and relocate overlays that end up off-screen
+pub fn start_monitor_watch(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval =
+            tokio::time::interval(tokio::time::Duration::from_millis(MONITOR_POLL_INTERVAL_MS));
+        let mut known = monitor_bounds(&app);
+
+        loop {
+            interval.tick().await;
+
+            // Monitor queries are dispatched to the main thread, so query before locking
+            let monitors = monitor_bounds(&app);
+            if monitors.is_empty() || monitors == known {
+                continue;
+            }
+            known = monitors.clone();
+
+            tracing::info!("Monitor layout changed ({} monitor(s))", monitors.len());
+
+            let Some(primary) = primary_monitor_bounds(&app).or(monitors.first().copied()) else {
+                continue;
+            };
+
+            let relocated = {
+                let state = app.state::<OverlayState>();
+                let Ok(mut manager) = state.0.lock() else {
+                    continue;
+                };
+                manager.relocate_offscreen_overlays(&monitors, primary)
+            };
+
+            for event in relocated {
+                tracing::info!(
+                    "Relocated off-screen overlay {} to ({}, {})",
+                    event.id,
+                    event.x,
+                    event.y
+                );
+                let _ = app.emit(OVERLAY_RELOCATED_EVENT, &event);
+            }
+        }
+    });
+}
@@ -3,34 +3,52 @@
 //! This module handles configuring overlay windows to:
 //! - Stay below the menu bar but above normal windows on macOS
 //! - Persist across all virtual desktops/spaces
-//! - Stay always on top of other windows
+//! - Stay always on top of other windows, or pinned to the desktop behind them
 //! - Allow positioning in the notch/menu bar area (unconstrained)
 //! - Properly handle click-through without causing focus changes
+//! - Round the corners and draw a border (macOS, Windows 11)
+//! - Step geometry animations, snapping instead when reduced motion is on
 
 use tauri::WebviewWindow;
 
+#[cfg(target_os = "macos")]
+use super::DesktopLevel;
+use super::{OverlayConfig, Rect};
 use crate::Error;
 
 /// Configure an overlay window with platform-specific settings
 ///
 /// This sets up the window to behave as a proper overlay:
-/// - Floating window level (above normal windows, below menu bar)
+/// - Floating window level (above normal windows, below menu bar) when always on top
+/// - Desktop level (behind all windows) when `desktop_level` is set
 /// - Visible on all virtual desktops/spaces
 /// - Doesn't appear in window switchers
-/// - Proper click-through handling when enabled
-pub fn configure_overlay(window: &WebviewWindow, click_through: bool) -> Result<(), Error> {
+/// - Proper click-through handling, in both directions
+/// - Shown over fullscreen apps on macOS, unless `show_on_fullscreen` is false
+/// - Rounded corners and border, where the platform supports them
+///
+/// Every setting is applied explicitly, so this can be called again on a live
+/// window to bring it back in line with its config. For overlays with interactive
+/// regions, `click_through` should be the hit-test loop's current state.
+pub fn configure_overlay(window: &WebviewWindow, config: &OverlayConfig) -> Result<(), Error> {
     #[cfg(target_os = "macos")]
-    configure_overlay_macos(window, click_through)?;
+    {
+        configure_overlay_macos(window, config)?;
+        configure_chrome_macos(window, config)?;
+    }
 
     #[cfg(target_os = "windows")]
-    configure_overlay_windows(window, click_through)?;
+    {
+        configure_overlay_windows(window, config)?;
+        configure_chrome_windows(window, config);
+    }
 
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
     {
         // Linux and other platforms - no special configuration needed
-        // The always_on_top setting from Tauri should be sufficient
+        // The always_on_top/always_on_bottom settings from Tauri should be sufficient
         let _ = window;
-        let _ = click_through;
+        let _ = config;
     }
 
     Ok(())
@@ -38,15 +56,30 @@ pub fn configure_overlay(window: &WebviewWindow, click_through: bool) -> Result<
 
 /// macOS-specific overlay configuration
 #[cfg(target_os = "macos")]
-fn configure_overlay_macos(window: &WebviewWindow, click_through: bool) -> Result<(), Error> {
-    // Get the native NSWindow handle pointer (safe from any thread)
-    let ns_window_ptr = window
-        .ns_window()
-        .map_err(|e| Error::WindowCreation(format!("Failed to get NSWindow handle: {}", e)))?;
+fn configure_overlay_macos(window: &WebviewWindow, config: &OverlayConfig) -> Result<(), Error> {
+    // Get the native NSWindow handle pointer (safe from any thread).
+    // This intermittently fails on some older macOS versions; the window still
+    // works with Tauri's defaults, so degrade instead of failing the spawn.
+    let ns_window_ptr = match window.ns_window() {
+        Ok(ptr) => ptr,
+        Err(e) => {
+            tracing::warn!(
+                "Failed to get NSWindow handle for {}, skipping overlay configuration: {}",
+                window.label(),
+                e
+            );
+            return Ok(());
+        }
+    };
 
     // Convert pointer to usize for thread-safe transfer
     let ptr_addr = ns_window_ptr as usize;
 
+    let click_through = config.click_through;
+    let always_on_top = config.always_on_top;
+    let desktop_level = config.desktop_level;
+    let show_on_fullscreen = config.show_on_fullscreen && desktop_level.is_none();
+
     // Dispatch all NSWindow operations to the main thread
     // macOS 15 (Sequoia) strictly enforces that window operations happen on the main thread
     window
@@ -54,7 +87,7 @@ fn configure_overlay_macos(window: &WebviewWindow, click_through: bool) -> Resul
             use objc2::rc::Retained;
             use objc2_app_kit::{
                 NSAccessibility, NSAccessibilitySystemDialogSubrole, NSMainMenuWindowLevel,
-                NSWindow, NSWindowCollectionBehavior,
+                NSNormalWindowLevel, NSWindow, NSWindowCollectionBehavior,
             };
 
             // Convert usize back to pointer and retain
@@ -65,21 +98,32 @@ fn configure_overlay_macos(window: &WebviewWindow, click_through: bool) -> Resul
                 return;
             };
 
-            // Set window level to main menu level (24)
-            ns_window.setLevel(NSMainMenuWindowLevel);
-
-            if click_through {
-                ns_window.setIgnoresMouseEvents(true);
-                ns_window.setAcceptsMouseMovedEvents(false);
-                ns_window.setHasShadow(false);
+            // Desktop levels sit behind every app window, otherwise
+            // use the main menu level (24)
+            if let Some(level) = desktop_level {
+                let key = match level {
+                    DesktopLevel::Wallpaper => K_CG_DESKTOP_WINDOW_LEVEL_KEY,
+                    DesktopLevel::Icons => K_CG_DESKTOP_ICON_WINDOW_LEVEL_KEY,
+                };
+                ns_window.setLevel(unsafe { CGWindowLevelForKey(key) } as isize);
+            } else if always_on_top {
+                ns_window.setLevel(NSMainMenuWindowLevel);
+            } else {
+                ns_window.setLevel(NSNormalWindowLevel);
             }
 
+            ns_window.setIgnoresMouseEvents(click_through);
+            ns_window.setAcceptsMouseMovedEvents(!click_through);
+            ns_window.setHasShadow(!click_through);
+
             // Set collection behavior for overlay windows
-            let behavior = NSWindowCollectionBehavior::CanJoinAllSpaces
+            let mut behavior = NSWindowCollectionBehavior::CanJoinAllSpaces
                 | NSWindowCollectionBehavior::Stationary
                 | NSWindowCollectionBehavior::IgnoresCycle
-                | NSWindowCollectionBehavior::FullScreenAuxiliary
                 | NSWindowCollectionBehavior::Transient;
+            if show_on_fullscreen {
+                behavior |= NSWindowCollectionBehavior::FullScreenAuxiliary;
+            }
             ns_window.setCollectionBehavior(behavior);
 
             unsafe {
@@ -91,7 +135,76 @@ fn configure_overlay_macos(window: &WebviewWindow, click_through: bool) -> Resul
     Ok(())
 }
 
-/// Set window position without macOS frame constraining and show the window
+// CGWindowLevelKey values from CGWindowLevel.h
+#[cfg(target_os = "macos")]
+const K_CG_DESKTOP_WINDOW_LEVEL_KEY: i32 = 2;
+#[cfg(target_os = "macos")]
+const K_CG_DESKTOP_ICON_WINDOW_LEVEL_KEY: i32 = 18;
+
+#[cfg(target_os = "macos")]
+#[link(name = "CoreGraphics", kind = "framework")]
+unsafe extern "C" {
+    fn CGWindowLevelForKey(key: i32) -> i32;
+}
+
+/// Round the corners and draw the border of the window's content layer
+#[cfg(target_os = "macos")]
+fn configure_chrome_macos(window: &WebviewWindow, config: &OverlayConfig) -> Result<(), Error> {
+    let Ok(ns_window_ptr) = window.ns_window() else {
+        // Already reported by configure_overlay_macos
+        return Ok(());
+    };
+    let ptr_addr = ns_window_ptr as usize;
+
+    let corner_radius = config.corner_radius;
+    let border = config.border;
+
+    window
+        .run_on_main_thread(move || {
+            use std::ffi::c_void;
+
+            use objc2::msg_send;
+            use objc2::rc::Retained;
+            use objc2::runtime::{AnyObject, Bool};
+            use objc2_app_kit::{NSColor, NSWindow};
+
+            let ns_window: Option<Retained<NSWindow>> =
+                unsafe { Retained::retain(ptr_addr as *mut NSWindow) };
+            let Some(content_view) = ns_window.and_then(|w| w.contentView()) else {
+                return;
+            };
+
+            content_view.setWantsLayer(true);
+
+            unsafe {
+                let layer: *mut AnyObject = msg_send![&*content_view, layer];
+                let Some(layer) = layer.as_ref() else {
+                    return;
+                };
+
+                let _: () = msg_send![layer, setCornerRadius: corner_radius];
+                let _: () = msg_send![layer, setMasksToBounds: Bool::new(corner_radius > 0.0)];
+
+                let (width, [r, g, b, a]) = border
+                    .map(|border| (border.width, border.color))
+                    .unwrap_or((0.0, [0, 0, 0, 0]));
+                let color = NSColor::colorWithSRGBRed_green_blue_alpha(
+                    f64::from(r) / 255.0,
+                    f64::from(g) / 255.0,
+                    f64::from(b) / 255.0,
+                    f64::from(a) / 255.0,
+                );
+                let cg_color: *const c_void = msg_send![&*color, CGColor];
+                let _: () = msg_send![layer, setBorderWidth: width];
+                let _: () = msg_send![layer, setBorderColor: cg_color];
+            }
+        })
+        .map_err(|e| Error::WindowCreation(format!("Failed to run on main thread: {}", e)))?;
+
+    Ok(())
+}
+
+/// Set window position without macOS frame constraining, then show the window if `show`
 ///
 /// This function positions a window using absolute screen coordinates,
 /// bypassing macOS's automatic frame constraining that normally prevents
@@ -107,6 +220,7 @@ fn configure_overlay_macos(window: &WebviewWindow, click_through: bool) -> Resul
 /// This function dispatches the work to the main thread to ensure
 /// thread safety with macOS window operations. The window is made
 /// visible AFTER positioning to prevent visual glitches.
+/// Hidden overlays pass `show: false` so repositioning doesn't reveal them.
 #[cfg(target_os = "macos")]
 pub fn set_unconstrained_position(
     window: &WebviewWindow,
@@ -114,11 +228,26 @@ pub fn set_unconstrained_position(
     y: f64,
     width: f64,
     height: f64,
+    show: bool,
 ) -> Result<(), Error> {
-    // Get the native NSWindow handle pointer (this is safe to get from any thread)
-    let ns_window_ptr = window
-        .ns_window()
-        .map_err(|e| Error::WindowCreation(format!("Failed to get NSWindow handle: {}", e)))?;
+    // Get the native NSWindow handle pointer (this is safe to get from any thread).
+    // Without it, fall back to Tauri's (constrained) positioning so the overlay still shows.
+    let ns_window_ptr = match window.ns_window() {
+        Ok(ptr) => ptr,
+        Err(e) => {
+            tracing::warn!(
+                "Failed to get NSWindow handle for {}, using constrained positioning: {}",
+                window.label(),
+                e
+            );
+            window.set_size(tauri::LogicalSize::new(width, height))?;
+            window.set_position(tauri::LogicalPosition::new(x, y))?;
+            if show {
+                window.show()?;
+            }
+            return Ok(());
+        }
+    };
 
     // Convert pointer to usize for thread-safe transfer
     // SAFETY: We're just storing the address as a number, and will convert it back
@@ -169,7 +298,9 @@ pub fn set_unconstrained_position(
 
             // Now make the window visible after positioning
             // This prevents the window from appearing in the wrong position first
-            ns_window.orderFront(None);
+            if show {
+                ns_window.orderFront(None);
+            }
         })
         .map_err(|e| Error::WindowCreation(format!("Failed to run on main thread: {}", e)))?;
 
@@ -184,19 +315,202 @@ pub fn set_unconstrained_position(
     _y: f64,
     _width: f64,
     _height: f64,
+    _show: bool,
 ) -> Result<(), Error> {
     // On non-macOS platforms, the standard positioning should work fine
     // The window position is already set by Tauri's WebviewWindowBuilder
     Ok(())
 }
 
+/// Move and resize a window to a frame in logical pixels (top-left origin) without
+/// showing it or changing its z-order. Used for the steps of geometry animations.
+#[cfg(target_os = "macos")]
+pub fn set_window_frame(window: &WebviewWindow, frame: Rect) -> Result<(), Error> {
+    set_unconstrained_position(window, frame.x, frame.y, frame.width, frame.height, false)
+}
+
+/// Move and resize a window to a frame in logical pixels (top-left origin) without
+/// showing it or changing its z-order. Used for the steps of geometry animations.
+#[cfg(target_os = "windows")]
+pub fn set_window_frame(window: &WebviewWindow, frame: Rect) -> Result<(), Error> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{SWP_NOACTIVATE, SWP_NOZORDER, SetWindowPos};
+
+    let hwnd = window
+        .hwnd()
+        .map_err(|e| Error::WindowCreation(format!("Failed to get HWND handle: {}", e)))?;
+    let hwnd = HWND(hwnd.0);
+    let scale = window.scale_factor()?;
+    let (dx, dy) = desktop_parent_offset(hwnd);
+
+    unsafe {
+        SetWindowPos(
+            hwnd,
+            None,
+            (frame.x * scale).round() as i32 - dx,
+            (frame.y * scale).round() as i32 - dy,
+            (frame.width * scale).round() as i32,
+            (frame.height * scale).round() as i32,
+            SWP_NOZORDER | SWP_NOACTIVATE,
+        )
+        .map_err(|e| Error::WindowCreation(format!("Failed to set window position: {}", e)))?;
+    }
+
+    Ok(())
+}
+
+/// Move and resize a window to a frame in logical pixels (top-left origin) without
+/// showing it. Used for the steps of geometry animations.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn set_window_frame(window: &WebviewWindow, frame: Rect) -> Result<(), Error> {
+    window.set_size(tauri::LogicalSize::new(frame.width, frame.height))?;
+    window.set_position(tauri::LogicalPosition::new(frame.x, frame.y))?;
+    Ok(())
+}
+
+/// Check whether the user asked the system to reduce motion, in which case
+/// overlays snap to new geometry instead of animating
+pub fn prefers_reduced_motion() -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        objc2_app_kit::NSWorkspace::sharedWorkspace().accessibilityDisplayShouldReduceMotion()
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use windows::Win32::Foundation::BOOL;
+        use windows::Win32::UI::WindowsAndMessaging::{
+            SPI_GETCLIENTAREAANIMATION, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS, SystemParametersInfoW,
+        };
+
+        // "Animate controls and elements inside windows", off when animations are disabled
+        let mut enabled = BOOL(1);
+        let queried = unsafe {
+            SystemParametersInfoW(
+                SPI_GETCLIENTAREAANIMATION,
+                0,
+                Some(&mut enabled as *mut BOOL as *mut std::ffi::c_void),
+                SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+            )
+        };
+        queried.is_ok() && !enabled.as_bool()
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    false
+}
+
+/// Temporarily raise an overlay above everything, including fullscreen apps.
+/// The window is made clickable and always on top; on macOS it also joins
+/// fullscreen spaces and moves to the screen saver level. Calling
+/// `configure_overlay` with the overlay's own config undoes all of it.
+pub fn raise_for_flash(window: &WebviewWindow, config: &OverlayConfig) -> Result<(), Error> {
+    let flash_config = OverlayConfig {
+        click_through: false,
+        always_on_top: true,
+        desktop_level: None,
+        show_on_fullscreen: true,
+        ..config.clone()
+    };
+    configure_overlay(window, &flash_config)?;
+
+    #[cfg(target_os = "macos")]
+    {
+        let Ok(ns_window_ptr) = window.ns_window() else {
+            // Already reported by configure_overlay_macos
+            return Ok(());
+        };
+        let ptr_addr = ns_window_ptr as usize;
+
+        // Queued after configure_overlay_macos, so this level wins
+        window
+            .run_on_main_thread(move || {
+                use objc2::rc::Retained;
+                use objc2_app_kit::{NSScreenSaverWindowLevel, NSWindow};
+
+                let ns_window: Option<Retained<NSWindow>> =
+                    unsafe { Retained::retain(ptr_addr as *mut NSWindow) };
+
+                if let Some(ns_window) = ns_window {
+                    ns_window.setLevel(NSScreenSaverWindowLevel);
+                    ns_window.orderFrontRegardless();
+                }
+            })
+            .map_err(|e| Error::WindowCreation(format!("Failed to run on main thread: {}", e)))?;
+    }
+
+    Ok(())
+}
+
+/// Set the alpha (0.0 - 1.0) of the whole native window
+#[cfg(target_os = "macos")]
+pub fn set_window_alpha(window: &WebviewWindow, alpha: f64) -> Result<(), Error> {
+    let ns_window_ptr = window
+        .ns_window()
+        .map_err(|e| Error::WindowCreation(format!("Failed to get NSWindow handle: {}", e)))?;
+    let ptr_addr = ns_window_ptr as usize;
+
+    window
+        .run_on_main_thread(move || {
+            use objc2::rc::Retained;
+            use objc2_app_kit::NSWindow;
+
+            let ns_window: Option<Retained<NSWindow>> =
+                unsafe { Retained::retain(ptr_addr as *mut NSWindow) };
+
+            if let Some(ns_window) = ns_window {
+                ns_window.setAlphaValue(alpha.clamp(0.0, 1.0));
+            }
+        })
+        .map_err(|e| Error::WindowCreation(format!("Failed to run on main thread: {}", e)))?;
+
+    Ok(())
+}
+
+/// Set the alpha (0.0 - 1.0) of the whole native window
+#[cfg(target_os = "windows")]
+pub fn set_window_alpha(window: &WebviewWindow, alpha: f64) -> Result<(), Error> {
+    use windows::Win32::Foundation::{COLORREF, HWND};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GWL_EXSTYLE, GetWindowLongPtrW, LWA_ALPHA, SetLayeredWindowAttributes, SetWindowLongPtrW,
+        WS_EX_LAYERED,
+    };
+
+    let hwnd = window
+        .hwnd()
+        .map_err(|e| Error::WindowCreation(format!("Failed to get HWND handle: {}", e)))?;
+
+    unsafe {
+        let hwnd = HWND(hwnd.0);
+
+        // Per-window alpha is only available on layered windows
+        let ex_style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+        if ex_style & WS_EX_LAYERED.0 as isize == 0 {
+            SetWindowLongPtrW(hwnd, GWL_EXSTYLE, ex_style | WS_EX_LAYERED.0 as isize);
+        }
+
+        let alpha = (alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+        SetLayeredWindowAttributes(hwnd, COLORREF(0), alpha, LWA_ALPHA)
+            .map_err(|e| Error::WindowCreation(format!("Failed to set window alpha: {}", e)))?;
+    }
+
+    Ok(())
+}
+
+/// No-op on other platforms - there is no portable per-window alpha API
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn set_window_alpha(_window: &WebviewWindow, _alpha: f64) -> Result<(), Error> {
+    Ok(())
+}
+
 /// Windows-specific overlay configuration
 #[cfg(target_os = "windows")]
-fn configure_overlay_windows(window: &WebviewWindow, click_through: bool) -> Result<(), Error> {
+fn configure_overlay_windows(window: &WebviewWindow, config: &OverlayConfig) -> Result<(), Error> {
     use windows::Win32::Foundation::HWND;
     use windows::Win32::UI::WindowsAndMessaging::{
-        GWL_EXSTYLE, GetWindowLongPtrW, HWND_TOPMOST, SWP_NOMOVE, SWP_NOSIZE, SetWindowLongPtrW,
-        SetWindowPos, WS_EX_LAYERED, WS_EX_TOOLWINDOW, WS_EX_TRANSPARENT,
+        GA_PARENT, GWL_EXSTYLE, GetAncestor, GetDesktopWindow, GetWindowLongPtrW, HWND_NOTOPMOST,
+        HWND_TOPMOST, SWP_NOMOVE, SWP_NOSIZE, SetParent, SetWindowLongPtrW, SetWindowPos,
+        WS_EX_LAYERED, WS_EX_TOOLWINDOW, WS_EX_TRANSPARENT,
     };
 
     // Get the native HWND handle
@@ -213,18 +527,52 @@ fn configure_overlay_windows(window: &WebviewWindow, click_through: bool) -> Res
         ex_style |= WS_EX_TOOLWINDOW.0 as isize;
 
         // If click-through is enabled, add WS_EX_LAYERED and WS_EX_TRANSPARENT
-        // to make the window truly click-through without focus changes
-        if click_through {
+        // to make the window truly click-through without focus changes.
+        // WS_EX_LAYERED stays when disabled, the hover fade may rely on it.
+        if config.click_through {
             ex_style |= WS_EX_LAYERED.0 as isize;
             ex_style |= WS_EX_TRANSPARENT.0 as isize;
+        } else {
+            ex_style &= !(WS_EX_TRANSPARENT.0 as isize);
         }
 
         SetWindowLongPtrW(hwnd, GWL_EXSTYLE, ex_style);
 
+        // Desktop overlays become children of the wallpaper host, so they are
+        // drawn over the wallpaper and under the icons. Both levels map to it.
+        // Children are positioned relative to the host, so the overlay is moved
+        // again whenever it changes parent.
+        let scale = window.scale_factor()?;
+        if config.desktop_level.is_some() {
+            let Some(host) = find_wallpaper_host() else {
+                tracing::warn!(
+                    "No wallpaper host window found, {} stays a normal window",
+                    window.label()
+                );
+                return Ok(());
+            };
+            if GetAncestor(hwnd, GA_PARENT) != host {
+                SetParent(hwnd, Some(host))
+                    .map_err(|e| Error::WindowCreation(format!("Failed to set parent: {}", e)))?;
+                move_window(hwnd, config.x * scale, config.y * scale)?;
+            }
+            return Ok(());
+        }
+        if GetAncestor(hwnd, GA_PARENT) != GetDesktopWindow() {
+            SetParent(hwnd, None)
+                .map_err(|e| Error::WindowCreation(format!("Failed to clear parent: {}", e)))?;
+            move_window(hwnd, config.x * scale, config.y * scale)?;
+        }
+
         // Ensure the window is topmost
+        let insert_after = if config.always_on_top {
+            HWND_TOPMOST
+        } else {
+            HWND_NOTOPMOST
+        };
         SetWindowPos(
             hwnd,
-            Some(HWND_TOPMOST),
+            Some(insert_after),
             0,
             0,
             0,
@@ -241,3 +589,144 @@ fn configure_overlay_windows(window: &WebviewWindow, click_through: bool) -> Res
 
     Ok(())
 }
+
+/// Move a window to a position in physical screen pixels. Windows parented to the
+/// wallpaper host are positioned relative to it, which starts at the virtual screen
+/// origin rather than the primary monitor.
+#[cfg(target_os = "windows")]
+fn move_window(hwnd: windows::Win32::Foundation::HWND, x: f64, y: f64) -> Result<(), Error> {
+    use windows::Win32::UI::WindowsAndMessaging::{
+        SWP_NOACTIVATE, SWP_NOSIZE, SWP_NOZORDER, SetWindowPos,
+    };
+
+    let (dx, dy) = desktop_parent_offset(hwnd);
+    unsafe {
+        SetWindowPos(
+            hwnd,
+            None,
+            x.round() as i32 - dx,
+            y.round() as i32 - dy,
+            0,
+            0,
+            SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE,
+        )
+        .map_err(|e| Error::WindowCreation(format!("Failed to set window position: {}", e)))
+    }
+}
+
+/// Offset of a window's coordinate space from the screen's, in physical pixels:
+/// the virtual screen origin when it is parented to the wallpaper host, else (0, 0)
+#[cfg(target_os = "windows")]
+fn desktop_parent_offset(hwnd: windows::Win32::Foundation::HWND) -> (i32, i32) {
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GA_PARENT, GetAncestor, GetDesktopWindow, GetSystemMetrics, SM_XVIRTUALSCREEN,
+        SM_YVIRTUALSCREEN,
+    };
+
+    unsafe {
+        if GetAncestor(hwnd, GA_PARENT) == GetDesktopWindow() {
+            return (0, 0);
+        }
+        (
+            GetSystemMetrics(SM_XVIRTUALSCREEN),
+            GetSystemMetrics(SM_YVIRTUALSCREEN),
+        )
+    }
+}
+
+/// Find the WorkerW window that hosts the wallpaper, spawning it if needed.
+/// Its coordinates start at the virtual screen origin, not the primary monitor.
+#[cfg(target_os = "windows")]
+fn find_wallpaper_host() -> Option<windows::Win32::Foundation::HWND> {
+    use windows::Win32::Foundation::{BOOL, HWND, LPARAM, WPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, FindWindowExW, FindWindowW, SMTO_NORMAL, SendMessageTimeoutW,
+    };
+    use windows::core::w;
+
+    unsafe extern "system" fn find_icons_sibling(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        unsafe {
+            if FindWindowExW(Some(hwnd), None, w!("SHELLDLL_DefView"), None).is_ok() {
+                // The wallpaper WorkerW is the next top-level WorkerW after the icons
+                if let Ok(worker) = FindWindowExW(None, Some(hwnd), w!("WorkerW"), None) {
+                    *(lparam.0 as *mut HWND) = worker;
+                    return BOOL(0);
+                }
+            }
+        }
+        BOOL(1)
+    }
+
+    unsafe {
+        let progman = FindWindowW(w!("Progman"), None).ok()?;
+
+        // Undocumented: asks Progman to split the wallpaper into its own WorkerW
+        SendMessageTimeoutW(
+            progman,
+            0x052C,
+            WPARAM(0),
+            LPARAM(0),
+            SMTO_NORMAL,
+            1000,
+            None,
+        );
+
+        let mut worker = HWND::default();
+        let _ = EnumWindows(
+            Some(find_icons_sibling),
+            LPARAM(&mut worker as *mut HWND as isize),
+        );
+        if !worker.is_invalid() {
+            return Some(worker);
+        }
+
+        // Windows 11 24H2 keeps the WorkerW as a child of Progman
+        FindWindowExW(Some(progman), None, w!("WorkerW"), None).ok()
+    }
+}
+
+/// Round the corners and color the border via DWM.
+/// Windows 11 only, older versions ignore the attributes, so errors are not reported.
+/// DWM draws a 1px border, the configured width only affects the CSS hint.
+#[cfg(target_os = "windows")]
+fn configure_chrome_windows(window: &WebviewWindow, config: &OverlayConfig) {
+    use windows::Win32::Foundation::{COLORREF, HWND};
+    use windows::Win32::Graphics::Dwm::{
+        DWMWA_BORDER_COLOR, DWMWA_COLOR_NONE, DWMWA_WINDOW_CORNER_PREFERENCE, DWMWCP_DONOTROUND,
+        DWMWCP_ROUND, DwmSetWindowAttribute,
+    };
+
+    let Ok(hwnd) = window.hwnd() else {
+        return;
+    };
+
+    let corner_preference = if config.corner_radius > 0.0 {
+        DWMWCP_ROUND
+    } else {
+        DWMWCP_DONOTROUND
+    };
+    // COLORREF is 0x00BBGGRR
+    let border_color = match config.border {
+        Some(border) if border.width > 0.0 => {
+            let [r, g, b, _] = border.color;
+            COLORREF(u32::from(r) | (u32::from(g) << 8) | (u32::from(b) << 16))
+        }
+        _ => COLORREF(DWMWA_COLOR_NONE),
+    };
+
+    unsafe {
+        let hwnd = HWND(hwnd.0);
+        let _ = DwmSetWindowAttribute(
+            hwnd,
+            DWMWA_WINDOW_CORNER_PREFERENCE,
+            &corner_preference as *const _ as *const std::ffi::c_void,
+            std::mem::size_of_val(&corner_preference) as u32,
+        );
+        let _ = DwmSetWindowAttribute(
+            hwnd,
+            DWMWA_BORDER_COLOR,
+            &border_color as *const _ as *const std::ffi::c_void,
+            std::mem::size_of_val(&border_color) as u32,
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Scheduled overlay visibility
+//!
+//! Overlays with a `schedule` are only shown inside one of its time windows
+//! (local time). Schedules are applied when an overlay spawns or is reconfigured
+//! and re-evaluated on a coarse interval after that.
+
+use chrono::{DateTime, Datelike, Local, NaiveTime, Timelike, Weekday};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, WebviewWindow};
+
+use crate::OverlayState;
+
+/// Day of the week a schedule window starts on
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduleDay {
+    Mon,
+    Tue,
+    Wed,
+    Thu,
+    Fri,
+    Sat,
+    Sun,
+}
+
+impl From<Weekday> for ScheduleDay {
+    fn from(day: Weekday) -> Self {
+        match day {
+            Weekday::Mon => Self::Mon,
+            Weekday::Tue => Self::Tue,
+            Weekday::Wed => Self::Wed,
+            Weekday::Thu => Self::Thu,
+            Weekday::Fri => Self::Fri,
+            Weekday::Sat => Self::Sat,
+            Weekday::Sun => Self::Sun,
+        }
+    }
+}
+
+/// A local time window an overlay is visible in, e.g.
+/// `{ "days": ["mon", "tue", "wed", "thu", "fri"], "start": "09:00", "end": "17:00" }`.
+/// A window ending before it starts runs overnight (`22:00` - `06:00`) and
+/// belongs to the day it starts on.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleWindow {
+    /// Days the window starts on, every day when empty
+    #[serde(default)]
+    pub days: Vec<ScheduleDay>,
+    /// Start time as `HH:MM`
+    pub start: String,
+    /// End time as `HH:MM` (exclusive), equal to `start` for the whole day
+    pub end: String,
+}
+
+impl ScheduleWindow {
+    /// Check whether `now` falls inside this window.
+    /// Windows with unparsable times never match.
+    pub fn contains(&self, now: &DateTime<Local>) -> bool {
+        let (Some(start), Some(end)) = (parse_time(&self.start), parse_time(&self.end)) else {
+            return false;
+        };
+        let minute = now.hour() * 60 + now.minute();
+        let today = ScheduleDay::from(now.weekday());
+        let yesterday = ScheduleDay::from(now.weekday().pred());
+
+        if start < end {
+            self.on(today) && (start..end).contains(&minute)
+        } else if start > end {
+            // Overnight: the evening part today, or the morning part of yesterday's window
+            (self.on(today) && minute >= start) || (self.on(yesterday) && minute < end)
+        } else {
+            self.on(today)
+        }
+    }
+
+    fn on(&self, day: ScheduleDay) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+}
+
+/// Parse `HH:MM` into minutes since midnight
+fn parse_time(time: &str) -> Option<u32> {
+    let time = NaiveTime::parse_from_str(time.trim(), "%H:%M").ok()?;
+    Some(time.hour() * 60 + time.minute())
+}
+
+/// Check whether a schedule allows an overlay to be visible at `now`
+pub fn is_scheduled_visible(schedule: &[ScheduleWindow], now: &DateTime<Local>) -> bool {
+    schedule.is_empty() || schedule.iter().any(|window| window.contains(now))
+}
+
+/// Snapshot of an overlay with a visibility schedule
+pub(crate) struct ScheduleTarget {
+    pub id: String,
+    pub window: WebviewWindow,
+    pub schedule: Vec<ScheduleWindow>,
+    /// Whether the overlay is currently hidden by its schedule
+    pub hidden: bool,
+    /// Fullscreen hiding takes precedence over the schedule showing it again
+    pub hidden_for_fullscreen: bool,
+}
+
+/// Show or hide scheduled overlays for the current local time
+pub fn sync_scheduled_visibility(app: &AppHandle) {
+    let Some(state) = app.try_state::<OverlayState>() else {
+        return;
+    };
+
+    // Snapshot and release the lock - visibility queries wait on the main thread
+    let targets = match state.0.lock() {
+        Ok(manager) => manager.schedule_targets(),
+        Err(_) => return,
+    };
+
+    let now = Local::now();
+    let mut changed = Vec::new();
+    for target in targets {
+        let visible = is_scheduled_visible(&target.schedule, &now);
+        if !visible && !target.hidden {
+            if target.hidden_for_fullscreen {
+                // Already hidden, just keep the fullscreen sync from showing it again
+                changed.push((target.id, true));
+            } else if target.window.is_visible().unwrap_or(false) && target.window.hide().is_ok() {
+                // Overlays the user already hid stay hidden and aren't brought back later
+                changed.push((target.id, true));
+            }
+        } else if visible && target.hidden {
+            if !target.hidden_for_fullscreen {
+                let _ = target.window.show();
+            }
+            changed.push((target.id, false));
+        }
+    }
+
+    if changed.is_empty() {
+        return;
+    }
+
+    if let Ok(mut manager) = state.0.lock() {
+        for (id, hidden) in changed {
+            tracing::debug!(
+                "Overlay {} {} by its schedule",
+                id,
+                if hidden { "hidden" } else { "shown" }
+            );
+            manager.set_hidden_by_schedule(&id, hidden);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// 2026-10-12 is a Monday
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2026, 10, day, hour, minute, 0)
+            .single()
+            .expect("unambiguous local time")
+    }
+
+    fn window(days: &[ScheduleDay], start: &str, end: &str) -> ScheduleWindow {
+        ScheduleWindow {
+            days: days.to_vec(),
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
+
+    #[test]
+    fn daytime_window_is_start_inclusive_end_exclusive() {
+        let w = window(&[], "09:00", "17:00");
+        assert!(!w.contains(&at(12, 8, 59)));
+        assert!(w.contains(&at(12, 9, 0)));
+        assert!(w.contains(&at(12, 16, 59)));
+        assert!(!w.contains(&at(12, 17, 0)));
+    }
+
+    #[test]
+    fn overnight_window_wraps_past_midnight() {
+        let w = window(&[], "22:00", "06:00");
+        assert!(w.contains(&at(12, 22, 0)));
+        assert!(w.contains(&at(12, 23, 59)));
+        assert!(w.contains(&at(13, 0, 0)));
+        assert!(w.contains(&at(13, 5, 59)));
+        assert!(!w.contains(&at(13, 6, 0)));
+        assert!(!w.contains(&at(13, 21, 59)));
+    }
+
+    #[test]
+    fn overnight_window_belongs_to_the_day_it_starts() {
+        // Friday night into Saturday morning only
+        let w = window(&[ScheduleDay::Fri], "22:00", "06:00");
+        assert!(w.contains(&at(16, 23, 0)), "Friday evening");
+        assert!(w.contains(&at(17, 2, 0)), "Saturday morning");
+        assert!(!w.contains(&at(17, 23, 0)), "Saturday evening");
+        assert!(
+            !w.contains(&at(16, 2, 0)),
+            "Friday morning, Thursday's window"
+        );
+    }
+
+    #[test]
+    fn overnight_window_wraps_from_sunday_to_monday() {
+        let w = window(&[ScheduleDay::Sun], "23:00", "01:00");
+        assert!(w.contains(&at(18, 23, 30)));
+        assert!(w.contains(&at(19, 0, 30)));
+        assert!(
+            !w.contains(&at(18, 0, 30)),
+            "Sunday morning, Saturday's window"
+        );
+    }
+
+    #[test]
+    fn equal_start_and_end_covers_the_whole_day() {
+        let w = window(&[ScheduleDay::Mon], "00:00", "00:00");
+        assert!(w.contains(&at(12, 0, 0)));
+        assert!(w.contains(&at(12, 23, 59)));
+        assert!(!w.contains(&at(13, 12, 0)));
+    }
+
+    #[test]
+    fn unparsable_times_never_match() {
+        assert!(!window(&[], "9am", "17:00").contains(&at(12, 12, 0)));
+        assert!(!window(&[], "09:00", "25:00").contains(&at(12, 12, 0)));
+    }
+
+    #[test]
+    fn empty_schedule_is_always_visible() {
+        assert!(is_scheduled_visible(&[], &at(12, 3, 0)));
+        let schedule = [window(&[], "09:00", "17:00"), window(&[], "20:00", "22:00")];
+        assert!(is_scheduled_visible(&schedule, &at(12, 21, 0)));
+        assert!(!is_scheduled_visible(&schedule, &at(12, 18, 0)));
+    }
+}
@@ -1,6 +1,12 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
 use serde::{Deserialize, Serialize};
 use tauri::WebviewWindow;
 
+use super::{Rect, ScheduleWindow};
+use crate::settings::Padding;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OverlayConfig {
@@ -13,10 +19,249 @@ pub struct OverlayConfig {
     pub y: f64,
     pub click_through: bool,
     pub frameless: bool,
+    #[serde(default = "default_true")]
+    pub always_on_top: bool,
+    /// Pin the overlay to the desktop behind all application windows,
+    /// taking precedence over `always_on_top`
+    #[serde(default)]
+    pub desktop_level: Option<DesktopLevel>,
+    /// Content zoom factor applied to the webview (1.0 = 100%)
+    #[serde(default = "default_zoom")]
+    pub zoom: f64,
+    /// Resting opacity (0 - 100)
+    #[serde(default = "default_opacity")]
+    pub opacity: f64,
+    /// Opacity while hovered (0 - 100), None disables the hover fade
+    #[serde(default)]
+    pub hover_opacity: Option<f64>,
+    /// Overlays sharing a group id are shown, hidden and moved together
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Locked overlays keep their position when their group is moved
+    #[serde(default)]
+    pub locked: bool,
+    /// Excluded overlays are left alone by "Show All" / "Hide All"
+    #[serde(default)]
+    pub exclude_from_bulk_toggle: bool,
+    /// Preset the position was derived from (e.g. "top-right"), if any
+    #[serde(default)]
+    pub position_preset: Option<String>,
+    /// Whether the overlay appears over fullscreen apps (macOS only)
+    #[serde(default = "default_true")]
+    pub show_on_fullscreen: bool,
+    /// Desktop-only mode: hide the overlay while any app is fullscreen
+    #[serde(default)]
+    pub hide_on_fullscreen: bool,
+    /// Window background as RGBA, fully transparent by default
+    #[serde(default)]
+    pub background_color: [u8; 4],
+    /// Corner radius in logical pixels, 0 for square corners
+    #[serde(default)]
+    pub corner_radius: f64,
+    /// Stroke drawn around the overlay, if any
+    #[serde(default)]
+    pub border: Option<OverlayBorder>,
+    /// Edge padding used when (re)placing the overlay from its position preset
+    #[serde(default)]
+    pub padding: Padding,
+    /// User CSS injected into this overlay's webview, either a stylesheet or a path to one
+    #[serde(default)]
+    pub inject_css: Option<String>,
+    /// Local time windows the overlay is visible in, always visible when empty
+    #[serde(default)]
+    pub schedule: Vec<ScheduleWindow>,
+    /// How long the window stays hidden waiting for its page to load (or `overlay_ready`)
+    /// before it is shown anyway. 0 shows it right after positioning.
+    #[serde(default)]
+    pub ready_timeout_ms: u64,
+}
+
+/// Layer of the desktop a pinned overlay sits on
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DesktopLevel {
+    /// On the wallpaper, below the desktop icons
+    Wallpaper,
+    /// At the level of the desktop icons (macOS only, the wallpaper elsewhere)
+    Icons,
+}
+
+/// Stroke drawn around an overlay window
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayBorder {
+    /// Width in logical pixels (Windows always draws a 1px border)
+    pub width: f64,
+    /// Color as RGBA
+    pub color: [u8; 4],
+}
+
+impl OverlayConfig {
+    /// Client-side route the overlay loads, taken from the entry point's hash fragment
+    pub fn route(&self) -> &str {
+        self.entry_point
+            .split_once('#')
+            .map(|(_, route)| route)
+            .filter(|route| !route.is_empty())
+            .unwrap_or("/")
+    }
+
+    /// Script defining `window.__YAOF__ = { overlayId, pluginId, route, isDev }`,
+    /// so overlays know who they are before any command round-trip
+    pub fn runtime_script(&self) -> String {
+        let runtime = serde_json::json!({
+            "overlayId": self.id,
+            "pluginId": self.plugin_id,
+            "route": self.route(),
+            "isDev": crate::is_dev_mode(),
+        });
+
+        format!("window.__YAOF__ = Object.freeze({});", runtime)
+    }
+
+    /// Script applying `inject_css` as a `<style id="yaof-injected-css">` element,
+    /// replacing (or removing) whatever was injected before
+    pub fn css_script(&self) -> String {
+        let css = self
+            .inject_css
+            .as_deref()
+            .map(resolve_css)
+            .unwrap_or_default();
+        let css = serde_json::Value::from(css);
+
+        format!(
+            r#"(function () {{
+  var css = {css};
+  var apply = function () {{
+    var style = document.getElementById("yaof-injected-css");
+    if (!css) {{
+      if (style) style.remove();
+      return;
+    }}
+    if (!style) {{
+      style = document.createElement("style");
+      style.id = "yaof-injected-css";
+      (document.head || document.documentElement).appendChild(style);
+    }}
+    style.textContent = css;
+  }};
+  if (document.readyState === "loading") {{
+    document.addEventListener("DOMContentLoaded", apply);
+  }} else {{
+    apply();
+  }}
+}})();"#
+        )
+    }
+
+    /// Script exposing the overlay's corner radius and border to the webview, as
+    /// `window.__YAOF_OVERLAY_CHROME__` and the `--yaof-corner-radius`,
+    /// `--yaof-border-width` and `--yaof-border-color` CSS variables.
+    /// Plugins use these to draw chrome consistent with the native window (Linux has none).
+    pub fn chrome_script(&self) -> String {
+        let (border_width, [r, g, b, a]) = self
+            .border
+            .map(|border| (border.width, border.color))
+            .unwrap_or((0.0, [0, 0, 0, 0]));
+        let chrome = serde_json::json!({
+            "cornerRadius": self.corner_radius,
+            "border": self.border,
+        });
+
+        format!(
+            r#"(function () {{
+  window.__YAOF_OVERLAY_CHROME__ = {chrome};
+  var apply = function () {{
+    var style = document.documentElement.style;
+    style.setProperty("--yaof-corner-radius", "{radius}px");
+    style.setProperty("--yaof-border-width", "{border_width}px");
+    style.setProperty("--yaof-border-color", "rgba({r}, {g}, {b}, {alpha})");
+  }};
+  if (document.documentElement) {{
+    apply();
+  }} else {{
+    document.addEventListener("DOMContentLoaded", apply);
+  }}
+}})();"#,
+            radius = self.corner_radius,
+            alpha = f64::from(a) / 255.0,
+        )
+    }
+}
+
+/// Read `inject_css` from disk when it names an existing file, otherwise use it as CSS
+fn resolve_css(css: &str) -> String {
+    let path = std::path::Path::new(css.trim());
+    if path.extension().is_some_and(|ext| ext == "css") && path.is_file() {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => return contents,
+            Err(e) => tracing::warn!("Failed to read injected CSS {:?}: {}", path, e),
+        }
+    }
+    css.to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_zoom() -> f64 {
+    1.0
+}
+
+fn default_opacity() -> f64 {
+    100.0
 }
 
 pub struct OverlayWindow {
     pub window: WebviewWindow,
     pub plugin_id: String,
     pub config: OverlayConfig,
+    /// Regions that capture the mouse while the rest of the overlay is click-through
+    pub interactive_regions: Vec<Rect>,
+    /// Whether the cursor is currently inside an interactive region
+    pub cursor_captured: bool,
+    /// Window alpha currently applied by the hover fade (0.0 - 1.0)
+    pub hover_alpha: f64,
+    /// Whether the overlay is hidden because a fullscreen app is focused
+    pub hidden_for_fullscreen: bool,
+    /// Whether the overlay is hidden because it is outside its schedule
+    pub hidden_by_schedule: bool,
+    /// Set while `overlay_flash` has the overlay raised above everything
+    pub flash: Option<FlashRestore>,
+    /// Whether the window is still hidden waiting for its content to report ready
+    pub awaiting_content: bool,
+    /// Set while the overlay is animating to new geometry
+    pub animation: Option<GeometryAnimation>,
+}
+
+/// A running geometry animation of an overlay. Dropping it, when the overlay's
+/// geometry is set again or the overlay closes, stops the animation.
+#[derive(Debug)]
+pub struct GeometryAnimation {
+    /// Identifies the animation, so a finished one only clears itself
+    pub animation_id: u64,
+    /// Window frames (logical pixels, top-left origin) it animates between
+    pub from: Rect,
+    pub to: Rect,
+    pub started: Instant,
+    pub duration: Duration,
+    /// Set once the animation is stopped. The animation task holds the lock while
+    /// applying a step, so no step lands after the geometry that replaced it.
+    pub stopped: Arc<Mutex<bool>>,
+}
+
+impl Drop for GeometryAnimation {
+    fn drop(&mut self) {
+        *self.stopped.lock().unwrap_or_else(PoisonError::into_inner) = true;
+    }
+}
+
+/// What a flashing overlay goes back to once its flash ends
+#[derive(Debug, Clone, Copy)]
+pub struct FlashRestore {
+    /// Identifies the flash, so an earlier flash's timer doesn't end a newer one
+    pub flash_id: u64,
+    /// Whether the overlay was visible before it started flashing
+    pub was_visible: bool,
 }
@@ -0,0 +1,122 @@
+//! Integrity records for installed plugins
+//!
+//! Copy installs get a `.integrity.json` listing the SHA-256 of every file, so a
+//! partially copied or modified install can be detected later. Symlinked (dev)
+//! installs point at a working tree that changes constantly and get no record.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::Error;
+
+/// File in the plugin directory holding the integrity record
+pub const INTEGRITY_FILE: &str = ".integrity.json";
+
+/// SHA-256 (hex) of every file in a plugin, keyed by `/`-separated relative path
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IntegrityRecord {
+    pub files: BTreeMap<String, String>,
+}
+
+/// Result of checking a plugin against its integrity record
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityReport {
+    pub plugin_id: String,
+    pub ok: bool,
+    /// Files whose contents changed since install
+    pub mismatched: Vec<String>,
+    /// Files recorded at install but no longer present
+    pub missing: Vec<String>,
+    /// Files present now but not recorded at install
+    pub unexpected: Vec<String>,
+}
+
+impl IntegrityRecord {
+    /// Hash every file in a plugin directory
+    pub fn compute(dir: &Path) -> Result<Self, Error> {
+        let mut files = BTreeMap::new();
+        hash_dir(dir, dir, &mut files)?;
+        Ok(Self { files })
+    }
+
+    /// Compute and write the record for a plugin directory
+    pub fn write(dir: &Path) -> Result<(), Error> {
+        let record = Self::compute(dir)?;
+        let json = serde_json::to_string_pretty(&record)
+            .map_err(|e| Error::ManifestParse(e.to_string()))?;
+        fs::write(dir.join(INTEGRITY_FILE), json)?;
+        Ok(())
+    }
+
+    /// Read the record written at install time
+    pub fn read(dir: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(dir.join(INTEGRITY_FILE))?;
+        serde_json::from_str(&content).map_err(|e| Error::ManifestParse(e.to_string()))
+    }
+
+    /// Compare the plugin directory's current contents against this record
+    pub fn verify(&self, plugin_id: &str, dir: &Path) -> Result<IntegrityReport, Error> {
+        let current = Self::compute(dir)?;
+
+        let mut mismatched = Vec::new();
+        let mut missing = Vec::new();
+        for (path, hash) in &self.files {
+            match current.files.get(path) {
+                Some(current_hash) if current_hash != hash => mismatched.push(path.clone()),
+                Some(_) => {}
+                None => missing.push(path.clone()),
+            }
+        }
+
+        let unexpected: Vec<String> = current
+            .files
+            .keys()
+            .filter(|path| !self.files.contains_key(*path))
+            .cloned()
+            .collect();
+
+        Ok(IntegrityReport {
+            plugin_id: plugin_id.to_string(),
+            ok: mismatched.is_empty() && missing.is_empty() && unexpected.is_empty(),
+            mismatched,
+            missing,
+            unexpected,
+        })
+    }
+}
+
+fn hash_dir(root: &Path, dir: &Path, files: &mut BTreeMap<String, String>) -> Result<(), Error> {
+    for entry in fs::read_dir(dir)? {
+        let path: PathBuf = entry?.path();
+
+        if path.is_dir() {
+            hash_dir(root, &path, files)?;
+            continue;
+        }
+
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        if relative == INTEGRITY_FILE {
+            continue;
+        }
+
+        let hash = Sha256::digest(fs::read(&path)?);
+        let hex = hash.iter().map(|b| format!("{:02x}", b)).collect();
+        files.insert(relative, hex);
+    }
+
+    Ok(())
+}
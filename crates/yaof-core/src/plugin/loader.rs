@@ -2,15 +2,22 @@ use std::{
     collections::HashMap,
     fs,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 
-use crate::{Error, manifest::PluginManifest};
+use crate::{
+    Error,
+    integrity::{IntegrityRecord, IntegrityReport},
+    manifest::{MANIFEST_FILE, MANIFEST_FILE_JSON5, NativeConfig, PluginManifest},
+};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "type", content = "details", rename_all = "camelCase")]
 pub enum PluginSource {
     Local(PathBuf),
     Git {
         url: String,
+        #[serde(rename = "ref")]
         ref_: Option<String>,
     },
     Npm {
@@ -19,6 +26,15 @@ pub enum PluginSource {
     },
 }
 
+/// A native library of an installed plugin, resolved for the current platform
+#[derive(Debug, Clone)]
+pub struct NativeComponentPath {
+    /// Native plugin ID (`<plugin-id>` or `<plugin-id>:<component>`)
+    pub id: String,
+    pub path: PathBuf,
+    pub tick_interval_ms: u64,
+}
+
 #[derive(Debug, Clone)]
 pub struct InstalledPlugin {
     pub manifest: PluginManifest,
@@ -31,28 +47,92 @@ impl InstalledPlugin {
     pub fn native_library_path(&self) -> Option<PathBuf> {
         let native_config = self.manifest.native.as_ref()?;
 
-        // Get the relative library path for the current platform
-        let relative_path = native_config.library_for_current_platform()?;
-
-        // Resolve to absolute path relative to plugin directory
-        let absolute_path = self.path.join(relative_path);
+        // Get the library path for the current platform
+        let library = native_config.library_for_current_platform()?;
+        let absolute_path = self.resolve_library_path(library)?;
 
         // Check if the library exists
         if absolute_path.exists() {
             Some(absolute_path)
         } else {
-            eprintln!(
-                "[YAOF] Warning: Native library not found at {:?} for plugin {}",
-                absolute_path, self.manifest.id
+            tracing::warn!(
+                "Native library not found at {:?} for plugin {}",
+                absolute_path,
+                self.manifest.id
             );
             None
         }
     }
 
+    /// Get every native library of this plugin that exists for the current platform:
+    /// the main library (if any) followed by its named components
+    pub fn native_components(&self) -> Vec<NativeComponentPath> {
+        let Some(native_config) = self.manifest.native.as_ref() else {
+            return Vec::new();
+        };
+        let default_tick = native_config.tick_interval_ms.unwrap_or(1000);
+
+        let mut components = Vec::new();
+
+        if let Some(path) = self.native_library_path() {
+            components.push(NativeComponentPath {
+                id: self.manifest.id.clone(),
+                path,
+                tick_interval_ms: default_tick,
+            });
+        }
+
+        for (name, component) in &native_config.components {
+            let Some(absolute_path) = component
+                .library_for_current_platform()
+                .and_then(|library| self.resolve_library_path(library))
+            else {
+                continue;
+            };
+
+            if !absolute_path.exists() {
+                tracing::warn!(
+                    "Native library not found at {:?} for component {} of plugin {}",
+                    absolute_path,
+                    name,
+                    self.manifest.id
+                );
+                continue;
+            }
+
+            components.push(NativeComponentPath {
+                id: NativeConfig::component_id(&self.manifest.id, Some(name)),
+                path: absolute_path,
+                tick_interval_ms: component.tick_interval_ms.unwrap_or(default_tick),
+            });
+        }
+
+        components
+    }
+
+    /// Resolve a manifest library path: environment variables (`$VAR`, `${VAR}`) are
+    /// expanded, absolute paths are used as-is and anything else is relative to the
+    /// plugin directory
+    fn resolve_library_path(&self, library: &str) -> Option<PathBuf> {
+        match expand_env_vars(library) {
+            // Joining an absolute path replaces the plugin directory
+            Ok(expanded) => Some(self.path.join(expanded)),
+            Err(var) => {
+                tracing::warn!(
+                    "Native library path {:?} of plugin {} uses unset variable {}",
+                    library,
+                    self.manifest.id,
+                    var
+                );
+                None
+            }
+        }
+    }
+
     /// Check if this plugin has a native component that can be loaded on this platform
     pub fn can_load_native(&self) -> bool {
         self.manifest.native.as_ref().map_or(false, |native| {
-            native.supports_current_platform() && self.native_library_path().is_some()
+            native.supports_current_platform() && !self.native_components().is_empty()
         })
     }
 
@@ -67,9 +147,73 @@ impl InstalledPlugin {
     }
 }
 
+/// An installed plugin with its install details, as reported to the plugin manager UI
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledPluginInfo {
+    pub manifest: PluginManifest,
+    pub path: PathBuf,
+    pub source: PluginSource,
+    /// Whether a native component will actually load on this platform
+    pub can_load_native: bool,
+    pub has_ui: bool,
+    pub is_native_only: bool,
+}
+
+impl From<&InstalledPlugin> for InstalledPluginInfo {
+    fn from(plugin: &InstalledPlugin) -> Self {
+        Self {
+            manifest: plugin.manifest.clone(),
+            path: plugin.path.clone(),
+            source: plugin.source.clone(),
+            can_load_native: plugin.can_load_native(),
+            has_ui: plugin.has_ui(),
+            is_native_only: plugin.is_native_only(),
+        }
+    }
+}
+
+/// A plugin directory that failed to load during the last scan
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginLoadError {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+/// Modification times a cached manifest was parsed at. The directory mtime
+/// catches files being added, removed or renamed into place, the manifest
+/// mtime catches in-place edits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ManifestStamp {
+    dir: SystemTime,
+    manifest: SystemTime,
+}
+
+impl ManifestStamp {
+    /// Read the stamp of a plugin directory, None if either time is unavailable
+    fn read(plugin_dir: &Path) -> Option<Self> {
+        let manifest_path = plugin_dir.join(MANIFEST_FILE);
+        let manifest_path = if manifest_path.exists() {
+            manifest_path
+        } else {
+            plugin_dir.join(MANIFEST_FILE_JSON5)
+        };
+
+        Some(Self {
+            dir: fs::metadata(plugin_dir).ok()?.modified().ok()?,
+            manifest: fs::metadata(manifest_path).ok()?.modified().ok()?,
+        })
+    }
+}
+
 pub struct PluginLoader {
     plugins_dir: PathBuf,
     installed: HashMap<String, InstalledPlugin>,
+    /// Plugins skipped by the last scan, e.g. because they need a newer yaof
+    load_errors: Vec<PluginLoadError>,
+    /// Manifests parsed by earlier scans, keyed by plugin directory
+    manifest_cache: HashMap<PathBuf, (ManifestStamp, PluginManifest)>,
 }
 
 impl PluginLoader {
@@ -77,17 +221,18 @@ impl PluginLoader {
         Self {
             plugins_dir,
             installed: HashMap::new(),
+            load_errors: Vec::new(),
+            manifest_cache: HashMap::new(),
         }
     }
 
     pub fn with_default_dir() -> Result<Self, Error> {
-        let home = dirs::home_dir().ok_or_else(|| {
+        let plugins_dir = crate::yaof_plugins_dir().ok_or_else(|| {
             Error::IoError(std::io::Error::new(
                 std::io::ErrorKind::NotFound,
                 "Could not find home directory",
             ))
         })?;
-        let plugins_dir = home.join(".yaof").join("plugins");
 
         // Create the directory if it doesn't exist
         fs::create_dir_all(&plugins_dir)?;
@@ -95,21 +240,42 @@ impl PluginLoader {
         Ok(Self::new(plugins_dir))
     }
 
+    /// Scan the plugins directory and return every valid manifest.
+    /// Manifests whose directory and file are unchanged since the last scan
+    /// are reused instead of parsed again.
     pub fn scan_plugins(&mut self) -> Result<Vec<PluginManifest>, Error> {
         let mut manifests = Vec::new();
         self.installed.clear();
+        self.load_errors.clear();
 
         if !self.plugins_dir.exists() {
+            self.manifest_cache.clear();
             return Ok(manifests);
         }
 
+        let mut cache = HashMap::new();
+
         for entry in fs::read_dir(&self.plugins_dir)? {
             let entry = entry?;
             let path = entry.path();
 
             if path.is_dir() {
-                match self.load_manifest(&path) {
+                let stamp = ManifestStamp::read(&path);
+                let cached = self
+                    .manifest_cache
+                    .remove(&path)
+                    .filter(|(cached_stamp, _)| Some(*cached_stamp) == stamp)
+                    .map(|(_, manifest)| manifest);
+                let result = match cached {
+                    Some(manifest) => Ok(manifest),
+                    None => self.load_manifest(&path),
+                };
+
+                match result {
                     Ok(manifest) => {
+                        if let Some(stamp) = stamp {
+                            cache.insert(path.clone(), (stamp, manifest.clone()));
+                        }
                         let id = manifest.id.clone();
                         self.installed.insert(
                             id,
@@ -122,19 +288,31 @@ impl PluginLoader {
                         manifests.push(manifest);
                     }
                     Err(e) => {
-                        eprintln!("Warning: Failed to load plugin at {:?}: {}", path, e);
+                        tracing::warn!("Failed to load plugin at {:?}: {}", path, e);
+                        self.load_errors.push(PluginLoadError {
+                            path,
+                            error: e.to_string(),
+                        });
                     }
                 }
             }
         }
 
+        // Directories that disappeared since the last scan drop out here
+        self.manifest_cache = cache;
+
         Ok(manifests)
     }
 
+    /// Load and validate `overlay.json`, or `overlay.json5` when there is no `overlay.json`
     pub fn load_manifest(&self, plugin_dir: &Path) -> Result<PluginManifest, Error> {
-        let manifest_path = plugin_dir.join("overlay.json");
-        let content = fs::read_to_string(&manifest_path)?;
-        let manifest = PluginManifest::from_json(&content)?;
+        let manifest_path = plugin_dir.join(MANIFEST_FILE);
+        let manifest = if manifest_path.exists() {
+            PluginManifest::from_json(&fs::read_to_string(&manifest_path)?)?
+        } else {
+            let content = fs::read_to_string(plugin_dir.join(MANIFEST_FILE_JSON5))?;
+            PluginManifest::from_json5(&content)?
+        };
         manifest.validate()?;
         Ok(manifest)
     }
@@ -143,10 +321,37 @@ impl PluginLoader {
         self.installed.get(id)
     }
 
+    /// Resolve the on-disk directory of an installed plugin.
+    /// Symlinked (dev) installs resolve to the real target directory.
+    pub fn resolve_plugin_dir(&self, id: &str) -> Result<PathBuf, Error> {
+        let plugin = self
+            .installed
+            .get(id)
+            .ok_or_else(|| Error::PluginNotFound(id.to_string()))?;
+
+        Ok(plugin.path.canonicalize()?)
+    }
+
     pub fn list_plugins(&self) -> Vec<&InstalledPlugin> {
         self.installed.values().collect()
     }
 
+    /// Get the plugins that failed to load during the last scan
+    pub fn load_errors(&self) -> &[PluginLoadError] {
+        &self.load_errors
+    }
+
+    /// Load and validate a plugin's manifest from a source directory without installing it
+    pub fn validate_source(&self, source_path: &Path) -> Result<PluginManifest, Error> {
+        if !source_path.is_dir() {
+            return Err(Error::IoError(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Plugin source {:?} is not a directory", source_path),
+            )));
+        }
+        self.load_manifest(source_path)
+    }
+
     pub fn install_local(
         &mut self,
         source_path: &Path,
@@ -155,6 +360,7 @@ impl PluginLoader {
         // Load and validate manifest from source
         let manifest = self.load_manifest(source_path)?;
         let dest_path = self.plugins_dir.join(&manifest.id);
+        self.manifest_cache.remove(&dest_path);
 
         // Remove existing installation if present
         if dest_path.exists() {
@@ -170,6 +376,7 @@ impl PluginLoader {
         } else {
             // Copy files for production install
             copy_dir_recursive(source_path, &dest_path)?;
+            IntegrityRecord::write(&dest_path)?;
         }
 
         // Add to installed map
@@ -190,6 +397,7 @@ impl PluginLoader {
             .installed
             .remove(plugin_id)
             .ok_or_else(|| Error::PluginNotFound(plugin_id.to_string()))?;
+        self.manifest_cache.remove(&plugin.path);
 
         if plugin.path.exists() {
             fs::remove_dir_all(&plugin.path)?;
@@ -198,12 +406,137 @@ impl PluginLoader {
         Ok(())
     }
 
+    /// Check an installed plugin's files against the integrity record written at install
+    pub fn verify_integrity(&self, plugin_id: &str) -> Result<IntegrityReport, Error> {
+        let dir = self.resolve_plugin_dir(plugin_id)?;
+        let record = IntegrityRecord::read(&dir).map_err(|e| match e {
+            Error::IoError(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Error::IoError(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("Plugin {} has no integrity record", plugin_id),
+                ))
+            }
+            e => e,
+        })?;
+        record.verify(plugin_id, &dir)
+    }
+
+    /// Read a file from an installed plugin's directory.
+    /// The path is relative to the plugin root and may not leave it.
+    pub fn read_plugin_file(&self, plugin_id: &str, relative_path: &str) -> Result<Vec<u8>, Error> {
+        let dir = self.resolve_plugin_dir(plugin_id)?;
+
+        // Reject traversal up front, then check the resolved path so symlinks can't escape either
+        let safe = relative_path
+            .split(['/', '\\'])
+            .all(|c| c.is_empty() || (c != "." && c != ".."));
+        if !safe || Path::new(relative_path).is_absolute() {
+            return Err(access_denied(relative_path));
+        }
+
+        let file = dir
+            .join(relative_path.trim_start_matches(['/', '\\']))
+            .canonicalize()?;
+        if !file.starts_with(&dir) {
+            return Err(access_denied(relative_path));
+        }
+        if !file.is_file() {
+            return Err(Error::IoError(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("{} is not a file", relative_path),
+            )));
+        }
+
+        Ok(fs::read(&file)?)
+    }
+
+    /// List every file of an installed plugin as sorted, `/`-separated paths
+    /// relative to the plugin root
+    pub fn list_plugin_files(&self, plugin_id: &str) -> Result<Vec<String>, Error> {
+        let dir = self.resolve_plugin_dir(plugin_id)?;
+        let mut files = Vec::new();
+        collect_files(&dir, &dir, &mut files)?;
+        files.sort();
+        Ok(files)
+    }
+
     /// Get the plugins directory path
     pub fn plugins_dir(&self) -> &Path {
         &self.plugins_dir
     }
 }
 
+/// Expand `$VAR` and `${VAR}` references, failing with the name of the first unset variable
+fn expand_env_vars(path: &str) -> Result<String, String> {
+    let mut expanded = String::with_capacity(path.len());
+    let mut rest = path;
+
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        let (name, remainder) = if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], &braced[end + 1..]),
+                None => return Err(format!("${{{}", braced)),
+            }
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], &after[end..])
+        };
+
+        if name.is_empty() {
+            // A lone `$` isn't a variable reference
+            expanded.push('$');
+        } else {
+            let value = std::env::var(name).map_err(|_| name.to_string())?;
+            expanded.push_str(&value);
+        }
+        rest = remainder;
+    }
+
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+fn access_denied(relative_path: &str) -> Error {
+    Error::IoError(std::io::Error::new(
+        std::io::ErrorKind::PermissionDenied,
+        format!("{} is outside the plugin directory", relative_path),
+    ))
+}
+
+/// List the files under `dir`. Symlinks are skipped, they may loop or point
+/// outside the plugin (e.g. pnpm's `node_modules`)
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<String>) -> Result<(), Error> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+
+        if file_type.is_symlink() {
+            continue;
+        }
+        if file_type.is_dir() {
+            collect_files(root, &path, files)?;
+            continue;
+        }
+
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        files.push(relative);
+    }
+
+    Ok(())
+}
+
 fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), Error> {
     fs::create_dir_all(dst)?;
 
@@ -221,3 +554,131 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), Error> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::time::Duration;
+
+    use super::*;
+
+    /// A plugins directory holding one plugin, `clock`
+    fn plugins_dir() -> (tempfile::TempDir, PathBuf) {
+        let root = tempfile::tempdir().unwrap();
+        let plugin_dir = root.path().join("clock");
+        fs::create_dir(&plugin_dir).unwrap();
+        (root, plugin_dir)
+    }
+
+    fn write_manifest(plugin_dir: &Path, version: &str) {
+        let manifest = format!(
+            r#"{{"id": "clock", "name": "Clock", "version": "{}", "entry": "index.html"}}"#,
+            version
+        );
+        fs::write(plugin_dir.join(MANIFEST_FILE), manifest).unwrap();
+    }
+
+    /// Pin a file's mtime, so tests don't depend on the filesystem's timestamp resolution
+    fn set_mtime(path: &Path, secs: u64) {
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+            .unwrap();
+    }
+
+    fn scanned_version(loader: &mut PluginLoader) -> String {
+        let manifests = loader.scan_plugins().unwrap();
+        assert_eq!(manifests.len(), 1);
+        manifests[0].version.clone()
+    }
+
+    #[test]
+    fn stamp_needs_a_manifest() {
+        let (_root, plugin_dir) = plugins_dir();
+        assert_eq!(ManifestStamp::read(&plugin_dir), None);
+    }
+
+    #[test]
+    fn stamp_falls_back_to_json5() {
+        let (_root, plugin_dir) = plugins_dir();
+        let manifest_path = plugin_dir.join(MANIFEST_FILE_JSON5);
+        fs::write(&manifest_path, "{}").unwrap();
+        set_mtime(&manifest_path, 1_000);
+
+        let stamp = ManifestStamp::read(&plugin_dir).unwrap();
+        assert_eq!(
+            stamp.manifest,
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1_000)
+        );
+    }
+
+    #[test]
+    fn unchanged_manifest_is_reused() {
+        let (root, plugin_dir) = plugins_dir();
+        let manifest_path = plugin_dir.join(MANIFEST_FILE);
+        write_manifest(&plugin_dir, "1.0.0");
+        set_mtime(&manifest_path, 1_000);
+
+        let mut loader = PluginLoader::new(root.path().to_path_buf());
+        assert_eq!(scanned_version(&mut loader), "1.0.0");
+
+        // Same mtimes, so the edit goes unnoticed and the cached manifest is used
+        write_manifest(&plugin_dir, "2.0.0");
+        set_mtime(&manifest_path, 1_000);
+        assert_eq!(scanned_version(&mut loader), "1.0.0");
+    }
+
+    #[test]
+    fn edited_manifest_is_parsed_again() {
+        let (root, plugin_dir) = plugins_dir();
+        let manifest_path = plugin_dir.join(MANIFEST_FILE);
+        write_manifest(&plugin_dir, "1.0.0");
+        set_mtime(&manifest_path, 1_000);
+
+        let mut loader = PluginLoader::new(root.path().to_path_buf());
+        assert_eq!(scanned_version(&mut loader), "1.0.0");
+
+        write_manifest(&plugin_dir, "2.0.0");
+        set_mtime(&manifest_path, 2_000);
+        assert_eq!(scanned_version(&mut loader), "2.0.0");
+    }
+
+    // Directories can only be opened as files on Unix
+    #[cfg(unix)]
+    #[test]
+    fn changed_directory_is_parsed_again() {
+        let (root, plugin_dir) = plugins_dir();
+        let manifest_path = plugin_dir.join(MANIFEST_FILE);
+        write_manifest(&plugin_dir, "1.0.0");
+        set_mtime(&manifest_path, 1_000);
+        File::open(&plugin_dir)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(1_000))
+            .unwrap();
+
+        let mut loader = PluginLoader::new(root.path().to_path_buf());
+        assert_eq!(scanned_version(&mut loader), "1.0.0");
+
+        // Only the new file bumps the directory mtime, the manifest's stays put
+        write_manifest(&plugin_dir, "2.0.0");
+        set_mtime(&manifest_path, 1_000);
+        fs::write(plugin_dir.join("index.html"), "").unwrap();
+        assert_eq!(scanned_version(&mut loader), "2.0.0");
+    }
+
+    #[test]
+    fn removed_plugins_leave_the_cache() {
+        let (root, plugin_dir) = plugins_dir();
+        write_manifest(&plugin_dir, "1.0.0");
+
+        let mut loader = PluginLoader::new(root.path().to_path_buf());
+        assert_eq!(scanned_version(&mut loader), "1.0.0");
+        assert_eq!(loader.manifest_cache.len(), 1);
+
+        fs::remove_dir_all(&plugin_dir).unwrap();
+        assert!(loader.scan_plugins().unwrap().is_empty());
+        assert!(loader.manifest_cache.is_empty());
+    }
+}
@@ -1,19 +1,114 @@
 //! Native plugin manager for discovering, loading, and running native plugins
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, mpsc};
+use std::time::{Duration, Instant};
 
-use tauri::AppHandle;
-use tokio::sync::RwLock;
-use tokio::time::interval;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::{RwLock, RwLockWriteGuard, watch};
+use tokio::time::{MissedTickBehavior, interval};
 
-use super::loader::PluginLoader;
-use super::native::NativePluginHost;
+use super::loader::{NativeComponentPath, PluginLoader};
+use super::native::{NativePluginHost, NextTimer, VerifiedLibrary};
+use super::signing::SigningPolicy;
 use crate::error::Error;
 
+/// How often plugin timers are checked, independent of the tick interval
+const TIMER_RESOLUTION_MS: u64 = 25;
+
+/// Maximum time a single plugin's `shutdown` may take before it is abandoned
+pub const PLUGIN_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Emitted when a plugin's tick keeps failing and ticking is disabled for it
+pub const PLUGIN_FAULTED_EVENT: &str = "yaof:plugin:faulted";
+
+/// Message type passed to a native plugin's `handle_message` after its settings were
+/// written (must match yaof-plugin). The payload is a JSON [`PluginSettingsChanged`].
+pub const SETTINGS_CHANGED_MESSAGE_TYPE: &str = "__settings_changed";
+
+/// Prefix of the message type sent to a plugin's native components when one of its
+/// tray items is clicked (`tray:<item-id>`, empty payload, must match yaof-plugin)
+pub const TRAY_MESSAGE_PREFIX: &str = "tray:";
+
+/// Whether a message type is reserved for messages sent by the host, like timers,
+/// service requests, resume and tray clicks (must match yaof-plugin)
+pub fn is_reserved_message_type(msg_type: &str) -> bool {
+    msg_type.starts_with("__") || msg_type.starts_with(TRAY_MESSAGE_PREFIX)
+}
+
+/// Emitted after plugins were installed, uninstalled or rescanned
+pub const PLUGINS_CHANGED_EVENT: &str = "yaof:plugins:changed";
+
+/// Environment variable listing native libraries to load as dev plugins straight from
+/// their build output, separated like `PATH` (e.g. `YAOF_NATIVE_DEV=target/debug/libfoo.dylib`)
+pub const NATIVE_DEV_ENV_VAR: &str = "YAOF_NATIVE_DEV";
+
+/// Emitted after a dev native plugin was (re)loaded, with the library's source path
+/// as payload, so the dev mode watcher can reload it on rebuild
+pub const NATIVE_DEV_LOADED_EVENT: &str = "yaof:native-dev:loaded";
+
+/// Libraries named by `YAOF_NATIVE_DEV`
+pub fn native_dev_paths() -> Vec<PathBuf> {
+    std::env::var_os(NATIVE_DEV_ENV_VAR)
+        .map(|paths| {
+            std::env::split_paths(&paths)
+                .filter(|p| !p.as_os_str().is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Name of the event emitted when one of a plugin's tray items is clicked:
+/// `yaof:plugin:{id}:tray` with the item ID as payload
+pub fn plugin_tray_event(plugin_id: &str) -> String {
+    format!("yaof:plugin:{}:tray", plugin_id)
+}
+
+/// Name of the event emitted after a plugin's settings were written:
+/// `yaof:plugin:{id}:settings-changed` with a [`PluginSettingsChanged`] payload
+pub fn plugin_settings_changed_event(plugin_id: &str) -> String {
+    format!("yaof:plugin:{}:settings-changed", plugin_id)
+}
+
+/// Payload of the settings changed event and message
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginSettingsChanged {
+    pub plugin_id: String,
+    /// Keys that were set or deleted, None when all settings were cleared
+    pub keys: Option<Vec<String>>,
+}
+
+/// Consecutive tick errors before ticks are backed off
+const TICK_BACKOFF_THRESHOLD: u32 = 5;
+
+/// Consecutive tick errors before ticking is disabled (the plugin stays loaded)
+const TICK_DISABLE_THRESHOLD: u32 = 10;
+
+/// Upper bound for the delay between ticks of a failing plugin
+const MAX_TICK_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Tick error tracking for a single plugin
+#[derive(Debug, Default)]
+struct TickHealth {
+    consecutive_errors: u32,
+    /// Ticks are skipped until then while backing off
+    retry_at: Option<Instant>,
+    disabled: bool,
+}
+
+/// Payload of the `yaof:plugin:faulted` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginFaultedEvent {
+    pub plugin_id: String,
+    pub consecutive_errors: u32,
+    pub last_error: i32,
+}
+
 /// Information about a loaded native plugin
 #[derive(Debug, Clone)]
 pub struct NativePluginInfo {
@@ -22,24 +117,38 @@ pub struct NativePluginInfo {
     pub tick_interval_ms: u64,
 }
 
+/// A native plugin loaded from its build output instead of the plugins directory
+#[derive(Debug, Clone)]
+struct DevPlugin {
+    /// The library as built, watched for rebuilds
+    source: PathBuf,
+}
+
 /// Manages native plugins - discovery, loading, tick loop
 pub struct NativePluginManager {
     plugins_dir: PathBuf,
     plugins: HashMap<String, NativePluginHost>,
     plugin_info: HashMap<String, NativePluginInfo>,
+    /// Plugin IDs in the order they were loaded, used to unload in reverse
+    load_order: Vec<String>,
+    tick_health: HashMap<String, TickHealth>,
+    /// Dev plugins by ID, see `load_dev_plugin`
+    dev_plugins: HashMap<String, DevPlugin>,
+    /// Earliest timer across all plugins, see `fire_timers`
+    next_timer: NextTimer,
     app_handle: AppHandle,
 }
 
 impl NativePluginManager {
     /// Create a new native plugin manager
     pub fn new(app_handle: AppHandle) -> Result<Self, Error> {
-        let home = dirs::home_dir().ok_or_else(|| {
+        let home = crate::yaof_home().ok_or_else(|| {
             Error::IoError(std::io::Error::new(
                 std::io::ErrorKind::NotFound,
                 "Could not find home directory",
             ))
         })?;
-        let plugins_dir = home.join(".yaof").join("native-plugins");
+        let plugins_dir = home.join("native-plugins");
 
         // Create the directory if it doesn't exist
         fs::create_dir_all(&plugins_dir)?;
@@ -48,6 +157,10 @@ impl NativePluginManager {
             plugins_dir,
             plugins: HashMap::new(),
             plugin_info: HashMap::new(),
+            load_order: Vec::new(),
+            tick_health: HashMap::new(),
+            dev_plugins: HashMap::new(),
+            next_timer: NextTimer::default(),
             app_handle,
         })
     }
@@ -61,6 +174,11 @@ impl NativePluginManager {
     pub fn discover_and_load(&mut self) -> Result<Vec<String>, Error> {
         let mut loaded = Vec::new();
 
+        if crate::is_safe_mode() {
+            tracing::warn!("Safe mode: not loading native plugins");
+            return Ok(loaded);
+        }
+
         if !self.plugins_dir.exists() {
             return Ok(loaded);
         }
@@ -74,21 +192,25 @@ impl NativePluginManager {
             "so"
         };
 
-        for entry in fs::read_dir(&self.plugins_dir)? {
-            let entry = entry?;
-            let path = entry.path();
+        // Standalone libraries have no manifest to declare dependencies,
+        // so load them in a stable order by file name
+        let mut paths = fs::read_dir(&self.plugins_dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        paths.sort();
 
+        for path in paths {
             // Check if it's a library file
             if path.is_file() {
                 if let Some(ext) = path.extension() {
                     if ext == lib_extension {
                         match self.load_plugin(&path) {
                             Ok(id) => {
-                                println!("[YAOF] Loaded native plugin: {}", id);
+                                tracing::info!("Loaded native plugin: {}", id);
                                 loaded.push(id);
                             }
                             Err(e) => {
-                                eprintln!("[YAOF] Failed to load native plugin {:?}: {}", path, e);
+                                tracing::error!("Failed to load native plugin {:?}: {}", path, e);
                             }
                         }
                     }
@@ -107,14 +229,16 @@ impl NativePluginManager {
 
         // Check if already loaded
         if self.plugins.contains_key(&plugin_id) {
-            return Err(Error::PluginNotFound(format!(
-                "Plugin {} is already loaded",
-                plugin_id
-            )));
+            return Err(Error::AlreadyLoaded(plugin_id));
         }
 
         // Load the plugin
-        let host = NativePluginHost::load(path, plugin_id.clone(), self.app_handle.clone())?;
+        let host = NativePluginHost::load(
+            path,
+            plugin_id.clone(),
+            self.app_handle.clone(),
+            self.next_timer.clone(),
+        )?;
 
         // Store plugin info
         let info = NativePluginInfo {
@@ -124,28 +248,240 @@ impl NativePluginManager {
         };
 
         self.plugins.insert(plugin_id.clone(), host);
+        self.load_order.push(plugin_id.clone());
+        self.plugin_info.insert(plugin_id.clone(), info);
+
+        Ok(plugin_id)
+    }
+
+    /// Load a library straight from a build output (e.g. `target/debug/libfoo.dylib`)
+    /// as a dev plugin, replacing the build loaded from it before.
+    ///
+    /// The library is copied before loading, so the next build can overwrite it and
+    /// the OS loader doesn't hand back the already loaded image of the old build.
+    /// Dev builds get no exemption from the signing policy: while signed plugins
+    /// are required, they need a trusted `.sig` next to the build output.
+    pub fn load_dev_plugin(&mut self, source: &Path) -> Result<String, Error> {
+        let source = source.canonicalize()?;
+        let plugin_id = derive_plugin_id(&source)?;
+
+        if self.plugins.contains_key(&plugin_id) && !self.dev_plugins.contains_key(&plugin_id) {
+            return Err(Error::AlreadyLoaded(plugin_id));
+        }
+
+        // Copy first, a build still being written (or unsigned) leaves the old one running
+        let policy = SigningPolicy::load(&self.app_handle)?;
+        let library = VerifiedLibrary::copy(&source, &policy)?;
+
+        if let Some(host) = self.take_plugin(&plugin_id) {
+            shutdown_with_timeout(&plugin_id, host, PLUGIN_SHUTDOWN_TIMEOUT);
+            self.unregister_services(&plugin_id);
+        }
+
+        // Keep tracking the source even if this build fails, so the next one is retried
+        self.dev_plugins.insert(
+            plugin_id.clone(),
+            DevPlugin {
+                source: source.clone(),
+            },
+        );
+
+        let host = NativePluginHost::load_verified(
+            library,
+            plugin_id.clone(),
+            self.app_handle.clone(),
+            self.next_timer.clone(),
+        )?;
+
+        let info = NativePluginInfo {
+            id: plugin_id.clone(),
+            path: source.clone(),
+            tick_interval_ms: 1000,
+        };
+
+        self.plugins.insert(plugin_id.clone(), host);
+        self.load_order.push(plugin_id.clone());
         self.plugin_info.insert(plugin_id.clone(), info);
 
+        if let Err(e) = self.app_handle.emit(NATIVE_DEV_LOADED_EVENT, &source) {
+            tracing::warn!("Failed to emit dev plugin load for {}: {}", plugin_id, e);
+        }
+
         Ok(plugin_id)
     }
 
+    /// Load the dev plugins named by `YAOF_NATIVE_DEV`, returning the loaded IDs
+    pub fn load_dev_plugins_from_env(&mut self) -> Vec<String> {
+        let mut loaded = Vec::new();
+
+        for path in native_dev_paths() {
+            match self.load_dev_plugin(&path) {
+                Ok(id) => {
+                    tracing::info!("Loaded dev native plugin {} from {:?}", id, path);
+                    loaded.push(id);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to load dev native plugin {:?}: {}", path, e);
+                }
+            }
+        }
+
+        loaded
+    }
+
+    /// Reload the dev plugin built to `source` after a rebuild,
+    /// None if no dev plugin is loaded from that path
+    pub fn reload_dev_plugin(&mut self, source: &Path) -> Option<Result<String, Error>> {
+        self.dev_plugins
+            .values()
+            .any(|dev| dev.source == source)
+            .then(|| self.load_dev_plugin(source))
+    }
+
     /// Unload a plugin by ID
     pub fn unload_plugin(&mut self, plugin_id: &str) -> Result<(), Error> {
-        self.plugins
-            .remove(plugin_id)
+        let host = self
+            .take_plugin(plugin_id)
             .ok_or_else(|| Error::PluginNotFound(plugin_id.to_string()))?;
-        self.plugin_info.remove(plugin_id);
+        shutdown_with_timeout(plugin_id, host, PLUGIN_SHUTDOWN_TIMEOUT);
+        self.unregister_services(plugin_id);
+
+        // An unloaded dev plugin is no longer reloaded on rebuild
+        self.dev_plugins.remove(plugin_id);
         Ok(())
     }
 
-    /// Call tick on all loaded plugins
+    /// Tell subscribers of the services an unloaded plugin broadcast that they are gone
+    fn unregister_services(&self, plugin_id: &str) {
+        if let Some(state) = self.app_handle.try_state::<crate::OverlayState>()
+            && let Ok(mut manager) = state.0.lock()
+        {
+            manager
+                .registry_mut()
+                .unregister_native_plugin(plugin_id, &self.app_handle);
+        }
+    }
+
+    /// Remove a plugin from every table without shutting it down
+    fn take_plugin(&mut self, plugin_id: &str) -> Option<NativePluginHost> {
+        let host = self.plugins.remove(plugin_id)?;
+        self.plugin_info.remove(plugin_id);
+        self.load_order.retain(|id| id != plugin_id);
+        self.tick_health.remove(plugin_id);
+        Some(host)
+    }
+
+    /// Unload every loaded native component of a plugin
+    /// (`<plugin-id>` and `<plugin-id>:<component>`), returning the unloaded IDs
+    pub fn unload_plugin_components(&mut self, plugin_id: &str) -> Vec<String> {
+        let prefix = format!("{}:", plugin_id);
+        let ids: Vec<String> = self
+            .load_order
+            .iter()
+            .filter(|id| *id == plugin_id || id.starts_with(&prefix))
+            .cloned()
+            .collect();
+
+        for id in &ids {
+            if let Err(e) = self.unload_plugin(id) {
+                tracing::warn!("Failed to unload native plugin {}: {}", id, e);
+            }
+        }
+
+        ids
+    }
+
+    /// Call tick on all loaded plugins.
+    ///
+    /// A plugin whose tick keeps failing is ticked less and less often, and after
+    /// `TICK_DISABLE_THRESHOLD` consecutive errors no longer ticked at all.
     pub fn tick_all(&mut self) {
+        let now = Instant::now();
+
         for (id, plugin) in self.plugins.iter_mut() {
+            let health = self.tick_health.entry(id.clone()).or_default();
+            if health.disabled || health.retry_at.is_some_and(|at| now < at) {
+                continue;
+            }
+
             let result = plugin.tick();
-            if result != 0 {
-                eprintln!("[YAOF] Plugin {} tick returned error: {}", id, result);
+            if result == 0 {
+                if health.consecutive_errors >= TICK_BACKOFF_THRESHOLD {
+                    tracing::info!("Plugin {} tick recovered", id);
+                }
+                *health = TickHealth::default();
+                continue;
+            }
+
+            health.consecutive_errors += 1;
+
+            if health.consecutive_errors >= TICK_DISABLE_THRESHOLD {
+                health.disabled = true;
+                tracing::error!(
+                    "Plugin {} tick failed {} times in a row ({}), disabling ticks",
+                    id,
+                    health.consecutive_errors,
+                    result
+                );
+                let _ = self.app_handle.emit(
+                    PLUGIN_FAULTED_EVENT,
+                    PluginFaultedEvent {
+                        plugin_id: id.clone(),
+                        consecutive_errors: health.consecutive_errors,
+                        last_error: result,
+                    },
+                );
+            } else if health.consecutive_errors >= TICK_BACKOFF_THRESHOLD {
+                // 1s, 2s, 4s, ... capped at MAX_TICK_BACKOFF
+                let exponent = health.consecutive_errors - TICK_BACKOFF_THRESHOLD;
+                let delay = Duration::from_secs(1 << exponent.min(6)).min(MAX_TICK_BACKOFF);
+                health.retry_at = Some(now + delay);
+                tracing::warn!(
+                    "Plugin {} tick returned error: {} ({} in a row, retrying in {:?})",
+                    id,
+                    result,
+                    health.consecutive_errors,
+                    delay
+                );
+            } else {
+                tracing::warn!("Plugin {} tick returned error: {}", id, result);
+            }
+        }
+    }
+
+    /// Whether ticking was disabled for a plugin after repeated tick errors
+    pub fn is_tick_disabled(&self, plugin_id: &str) -> bool {
+        self.tick_health
+            .get(plugin_id)
+            .is_some_and(|health| health.disabled)
+    }
+
+    /// Deliver due timers on all loaded plugins and record when the next one is due
+    pub fn fire_timers(&mut self) {
+        for (id, plugin) in self.plugins.iter_mut() {
+            for (timer_id, result) in plugin.fire_timers() {
+                if result != 0 {
+                    tracing::warn!(
+                        "Plugin {} timer {} returned error: {}",
+                        id,
+                        timer_id,
+                        result
+                    );
+                }
             }
         }
+
+        // Plugins take this lock only after releasing their timer table, so a timer
+        // scheduled meanwhile is either seen here or lowers the result afterwards
+        let mut next_timer = self
+            .next_timer
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        *next_timer = self
+            .plugins
+            .values()
+            .filter_map(NativePluginHost::next_timer_due)
+            .min();
     }
 
     /// Send a message to a specific plugin
@@ -162,6 +498,43 @@ impl NativePluginManager {
         Ok(plugin.send_message(msg_type, payload))
     }
 
+    /// Send a message to every loaded plugin, in load order.
+    /// Returns each plugin's `handle_message` result code.
+    pub fn broadcast_message(&mut self, msg_type: &str, payload: &[u8]) -> HashMap<String, i32> {
+        let mut results = HashMap::new();
+
+        for id in &self.load_order {
+            if let Some(plugin) = self.plugins.get_mut(id) {
+                results.insert(id.clone(), plugin.send_message(msg_type, payload));
+            }
+        }
+
+        results
+    }
+
+    /// Send a message to every loaded native component of a plugin
+    /// (`<plugin-id>` and `<plugin-id>:<component>`)
+    pub fn send_message_to_components(
+        &mut self,
+        plugin_id: &str,
+        msg_type: &str,
+        payload: &[u8],
+    ) -> HashMap<String, i32> {
+        let prefix = format!("{}:", plugin_id);
+        let mut results = HashMap::new();
+
+        for id in &self.load_order {
+            if id != plugin_id && !id.starts_with(&prefix) {
+                continue;
+            }
+            if let Some(plugin) = self.plugins.get_mut(id) {
+                results.insert(id.clone(), plugin.send_message(msg_type, payload));
+            }
+        }
+
+        results
+    }
+
     /// List all loaded plugins
     pub fn list_plugins(&self) -> Vec<&NativePluginInfo> {
         self.plugin_info.values().collect()
@@ -174,24 +547,49 @@ impl NativePluginManager {
 
     /// Shutdown all plugins (called on drop, but can be called explicitly)
     pub fn shutdown_all(&mut self) {
-        // Plugins are dropped when removed from the HashMap
-        // The Drop impl on NativePluginHost calls shutdown
-        self.plugins.clear();
+        self.shutdown_all_with_timeout(PLUGIN_SHUTDOWN_TIMEOUT);
+    }
+
+    /// Shutdown all plugins in reverse load order, giving each plugin at most
+    /// `timeout` to return from its `shutdown` before moving on to the next one
+    pub fn shutdown_all_with_timeout(&mut self, timeout: Duration) {
+        let order: Vec<String> = self.load_order.drain(..).rev().collect();
+        for plugin_id in order {
+            if let Some(host) = self.plugins.remove(&plugin_id) {
+                shutdown_with_timeout(&plugin_id, host, timeout);
+            }
+        }
+
+        // Anything not tracked in the load order is shut down last
+        for (plugin_id, host) in self.plugins.drain() {
+            shutdown_with_timeout(&plugin_id, host, timeout);
+        }
         self.plugin_info.clear();
+        self.tick_health.clear();
+
+        self.dev_plugins.clear();
     }
 
-    /// Load native plugins from installed plugins (in ~/.yaof/plugins/)
-    /// This scans all installed plugins and loads their native components if available
+    /// Load native plugins from installed plugins (in the plugins directory)
+    /// This scans all installed plugins and loads each of their native components
     pub fn load_from_installed_plugins(
         &mut self,
         plugin_loader: &mut PluginLoader,
     ) -> Result<Vec<String>, Error> {
         let mut loaded = Vec::new();
 
+        if crate::is_safe_mode() {
+            tracing::warn!("Safe mode: not loading native plugins from installed plugins");
+            return Ok(loaded);
+        }
+
         // Scan for installed plugins
         let manifests = plugin_loader.scan_plugins()?;
+        let installed_ids: HashSet<&str> = manifests.iter().map(|m| m.id.as_str()).collect();
 
-        for manifest in manifests {
+        // Collect the plugins with a loadable native component, with their dependencies
+        let mut native_plugins = Vec::new();
+        for manifest in &manifests {
             // Get the installed plugin
             let installed = match plugin_loader.get_plugin(&manifest.id) {
                 Some(p) => p,
@@ -200,57 +598,80 @@ impl NativePluginManager {
 
             // Check if this plugin has a native component that can be loaded
             if !installed.can_load_native() {
-                continue;
-            }
-
-            // Get the native library path
-            let lib_path = match installed.native_library_path() {
-                Some(p) => p,
-                None => {
-                    eprintln!(
-                        "[YAOF] Plugin {} has native config but no library found",
+                if manifest.native.is_some() {
+                    tracing::warn!(
+                        "Plugin {} has native config but no library found",
                         manifest.id
                     );
-                    continue;
                 }
-            };
+                continue;
+            }
 
-            // Check if already loaded
-            if self.plugins.contains_key(&manifest.id) {
-                println!(
-                    "[YAOF] Native plugin {} already loaded, skipping",
-                    manifest.id
+            let dependencies = manifest
+                .native
+                .as_ref()
+                .map(|n| n.dependencies.clone())
+                .unwrap_or_default();
+            native_plugins.push((manifest.id.clone(), dependencies));
+        }
+
+        // Dependencies initialize before their dependents
+        let (ordered, cyclic) = sort_by_dependencies(native_plugins);
+        for id in &cyclic {
+            tracing::error!(
+                "Not loading native plugin {}: dependency cycle among {}",
+                id,
+                cyclic.join(", ")
+            );
+        }
+
+        // Plugins that were not loaded, so their dependents aren't either
+        let mut failed: HashSet<String> = cyclic.into_iter().collect();
+
+        for (plugin_id, dependencies) in ordered {
+            if let Some(dep) = dependencies
+                .iter()
+                .find(|d| !installed_ids.contains(d.as_str()))
+            {
+                tracing::error!(
+                    "Not loading native plugin {}: depends on {}, which is not installed",
+                    plugin_id,
+                    dep
+                );
+                failed.insert(plugin_id);
+                continue;
+            }
+            if let Some(dep) = dependencies.iter().find(|d| failed.contains(*d)) {
+                tracing::error!(
+                    "Not loading native plugin {}: its dependency {} failed to load",
+                    plugin_id,
+                    dep
                 );
+                failed.insert(plugin_id);
                 continue;
             }
 
-            // Load the plugin
-            match NativePluginHost::load(&lib_path, manifest.id.clone(), self.app_handle.clone()) {
-                Ok(host) => {
-                    // Get tick interval from manifest if specified
-                    let tick_interval_ms = manifest
-                        .native
-                        .as_ref()
-                        .and_then(|n| n.tick_interval_ms)
-                        .unwrap_or(1000);
-
-                    let info = NativePluginInfo {
-                        id: manifest.id.clone(),
-                        path: lib_path,
-                        tick_interval_ms,
-                    };
-
-                    self.plugins.insert(manifest.id.clone(), host);
-                    self.plugin_info.insert(manifest.id.clone(), info);
-
-                    println!(
-                        "[YAOF] Loaded native plugin from installed: {}",
-                        manifest.id
-                    );
-                    loaded.push(manifest.id);
+            let Some(installed) = plugin_loader.get_plugin(&plugin_id) else {
+                continue;
+            };
+
+            for component in installed.native_components() {
+                // Check if already loaded
+                if self.plugins.contains_key(&component.id) {
+                    tracing::debug!("Native plugin {} already loaded, skipping", component.id);
+                    continue;
                 }
-                Err(e) => {
-                    eprintln!("[YAOF] Failed to load native plugin {}: {}", manifest.id, e);
+
+                let id = component.id.clone();
+                match self.load_component(component) {
+                    Ok(()) => {
+                        tracing::info!("Loaded native plugin from installed: {}", id);
+                        loaded.push(id);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to load native plugin {}: {}", id, e);
+                        failed.insert(plugin_id.clone());
+                    }
                 }
             }
         }
@@ -258,7 +679,7 @@ impl NativePluginManager {
         Ok(loaded)
     }
 
-    /// Load a native plugin by its plugin ID (from installed plugins)
+    /// Load all native components of a plugin by its plugin ID (from installed plugins)
     pub fn load_plugin_by_id(
         &mut self,
         plugin_id: &str,
@@ -271,45 +692,66 @@ impl NativePluginManager {
 
         // Check if this plugin has a native component
         if !installed.can_load_native() {
-            return Err(Error::PluginNotFound(format!(
-                "Plugin {} does not have a native component for this platform",
+            return Err(Error::Incompatible(format!(
+                "{} does not have a native component for this platform",
                 plugin_id
             )));
         }
 
-        // Get the native library path
-        let lib_path = installed.native_library_path().ok_or_else(|| {
-            Error::PluginNotFound(format!("Plugin {} native library not found", plugin_id))
-        })?;
+        // Dependencies are normally loaded first on startup; loading out of order
+        // still works, but the plugin may init before a service it needs exists
+        if let Some(native) = &installed.manifest.native {
+            for dep in &native.dependencies {
+                let prefix = format!("{}:", dep);
+                let dep_loaded = self
+                    .load_order
+                    .iter()
+                    .any(|id| id == dep || id.starts_with(&prefix));
+                let dep_native = plugin_loader
+                    .get_plugin(dep)
+                    .is_some_and(|p| p.can_load_native());
+                if dep_native && !dep_loaded {
+                    tracing::warn!(
+                        "Loading native plugin {} before its dependency {}",
+                        plugin_id,
+                        dep
+                    );
+                }
+            }
+        }
+
+        let components = installed.native_components();
 
         // Check if already loaded
-        if self.plugins.contains_key(plugin_id) {
-            return Err(Error::PluginNotFound(format!(
-                "Plugin {} is already loaded",
-                plugin_id
-            )));
+        if let Some(component) = components.iter().find(|c| self.plugins.contains_key(&c.id)) {
+            return Err(Error::AlreadyLoaded(component.id.clone()));
         }
 
-        // Load the plugin
-        let host =
-            NativePluginHost::load(&lib_path, plugin_id.to_string(), self.app_handle.clone())?;
+        for component in components {
+            self.load_component(component)?;
+        }
 
-        // Get tick interval from manifest if specified
-        let tick_interval_ms = installed
-            .manifest
-            .native
-            .as_ref()
-            .and_then(|n| n.tick_interval_ms)
-            .unwrap_or(1000);
+        Ok(())
+    }
+
+    /// Load a single resolved native component and track it
+    fn load_component(&mut self, component: NativeComponentPath) -> Result<(), Error> {
+        let host = NativePluginHost::load(
+            &component.path,
+            component.id.clone(),
+            self.app_handle.clone(),
+            self.next_timer.clone(),
+        )?;
 
         let info = NativePluginInfo {
-            id: plugin_id.to_string(),
-            path: lib_path,
-            tick_interval_ms,
+            id: component.id.clone(),
+            path: component.path,
+            tick_interval_ms: component.tick_interval_ms,
         };
 
-        self.plugins.insert(plugin_id.to_string(), host);
-        self.plugin_info.insert(plugin_id.to_string(), info);
+        self.plugins.insert(component.id.clone(), host);
+        self.load_order.push(component.id.clone());
+        self.plugin_info.insert(component.id, info);
 
         Ok(())
     }
@@ -321,6 +763,66 @@ impl Drop for NativePluginManager {
     }
 }
 
+/// Shut down a plugin on a separate thread and wait at most `timeout` for it.
+///
+/// Dropping the host calls the plugin's `shutdown` and then unloads the library.
+/// A plugin that exceeds the timeout is abandoned: its thread keeps running
+/// and the library is released once `shutdown` eventually returns, so a
+/// misbehaving plugin can no longer hang app exit. Unloading the library
+/// while its code is still executing would crash the process.
+fn shutdown_with_timeout(plugin_id: &str, host: NativePluginHost, timeout: Duration) {
+    let (tx, rx) = mpsc::channel();
+
+    let spawned = std::thread::Builder::new()
+        .name(format!("yaof-shutdown-{}", plugin_id))
+        .spawn(move || {
+            drop(host);
+            let _ = tx.send(());
+        });
+
+    if let Err(e) = spawned {
+        // The closure (and the host with it) was dropped inline, so shutdown already ran
+        tracing::warn!(
+            "Failed to spawn shutdown thread for native plugin {}: {}",
+            plugin_id,
+            e
+        );
+        return;
+    }
+
+    if rx.recv_timeout(timeout).is_err() {
+        tracing::warn!(
+            "Native plugin {} did not shut down within {:?}, continuing without it",
+            plugin_id,
+            timeout
+        );
+    }
+}
+
+/// Order plugins so each comes after the plugins it depends on, otherwise keeping
+/// their order. Dependencies outside `plugins` don't constrain the order.
+/// Returns the ordered plugins and the IDs that can't be ordered because of a cycle.
+fn sort_by_dependencies(
+    plugins: Vec<(String, Vec<String>)>,
+) -> (Vec<(String, Vec<String>)>, Vec<String>) {
+    let ids: HashSet<String> = plugins.iter().map(|(id, _)| id.clone()).collect();
+    let mut placed: HashSet<String> = HashSet::new();
+    let mut remaining = plugins;
+    let mut ordered = Vec::with_capacity(remaining.len());
+
+    while let Some(pos) = remaining
+        .iter()
+        .position(|(_, deps)| deps.iter().all(|d| !ids.contains(d) || placed.contains(d)))
+    {
+        let plugin = remaining.remove(pos);
+        placed.insert(plugin.0.clone());
+        ordered.push(plugin);
+    }
+
+    let cyclic = remaining.into_iter().map(|(id, _)| id).collect();
+    (ordered, cyclic)
+}
+
 /// Derive a plugin ID from a library path
 /// e.g., "libtopbar_service.dylib" -> "topbar-service"
 fn derive_plugin_id(path: &Path) -> Result<String, Error> {
@@ -341,24 +843,71 @@ fn derive_plugin_id(path: &Path) -> Result<String, Error> {
 /// Thread-safe wrapper for NativePluginManager
 pub struct NativePluginManagerHandle {
     inner: Arc<RwLock<NativePluginManager>>,
+    /// Shared with the manager, checked by the tick loop without taking `inner`
+    next_timer: NextTimer,
+    /// Set once the standalone native plugins have been discovered and loaded
+    ready: watch::Sender<bool>,
 }
 
 impl NativePluginManagerHandle {
     pub fn new(manager: NativePluginManager) -> Self {
         Self {
+            next_timer: manager.next_timer.clone(),
             inner: Arc::new(RwLock::new(manager)),
+            ready: watch::Sender::new(false),
         }
     }
 
-    /// Start the tick loop in a background task
+    /// Record that the initial plugin discovery finished, successful or not
+    pub fn mark_ready(&self) {
+        self.ready.send_replace(true);
+    }
+
+    /// Wait until the initial plugin discovery has finished
+    pub async fn wait_ready(&self) {
+        let mut ready = self.ready.subscribe();
+        let _ = ready.wait_for(|ready| *ready).await;
+    }
+
+    /// Check whether the initial plugin discovery has finished
+    pub fn is_ready(&self) -> bool {
+        *self.ready.borrow()
+    }
+
+    /// Start the tick loop in a background task.
+    /// The loop wakes at the timer resolution to fire plugin timers and ticks
+    /// plugins every `tick_interval_ms`.
     pub fn start_tick_loop(self: Arc<Self>, tick_interval_ms: u64) {
         let handle = self.clone();
         tokio::spawn(async move {
-            let mut interval = interval(Duration::from_millis(tick_interval_ms));
+            let tick_interval = Duration::from_millis(tick_interval_ms);
+            let mut interval = interval(Duration::from_millis(
+                TIMER_RESOLUTION_MS.min(tick_interval_ms).max(1),
+            ));
+            // A tick that ran long is followed by a full period, not a burst of catch-up ticks
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            let mut last_tick: Option<Instant> = None;
             loop {
                 interval.tick().await;
+
+                // Only take the manager lock when there is something to do
+                let timer_due = handle
+                    .next_timer
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .is_some_and(|due| due <= Instant::now());
+                let tick_due = last_tick.is_none_or(|t| t.elapsed() >= tick_interval);
+                if !timer_due && !tick_due {
+                    continue;
+                }
+
                 let mut manager = handle.inner.write().await;
-                manager.tick_all();
+                manager.fire_timers();
+
+                if tick_due {
+                    manager.tick_all();
+                    last_tick = Some(Instant::now());
+                }
             }
         });
     }
@@ -372,12 +921,93 @@ impl NativePluginManagerHandle {
     pub async fn write(&self) -> tokio::sync::RwLockWriteGuard<'_, NativePluginManager> {
         self.inner.write().await
     }
+
+    /// Get write access to the manager together with the plugin loader, in lock order
+    /// (see `NativePluginState`). The loader guard must be dropped before the next await.
+    pub async fn write_with_loader<'a>(
+        &'a self,
+        loader: &'a Mutex<PluginLoader>,
+    ) -> (
+        RwLockWriteGuard<'a, NativePluginManager>,
+        MutexGuard<'a, PluginLoader>,
+    ) {
+        let manager = self.inner.write().await;
+        // A poisoned loader only means a panic mid-scan, the next scan rebuilds it
+        let loader = loader.lock().unwrap_or_else(PoisonError::into_inner);
+        (manager, loader)
+    }
 }
 
 impl Clone for NativePluginManagerHandle {
     fn clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
+            next_timer: self.next_timer.clone(),
+            ready: self.ready.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin(id: &str, deps: &[&str]) -> (String, Vec<String>) {
+        (id.to_string(), deps.iter().map(|d| d.to_string()).collect())
+    }
+
+    fn ids(plugins: &[(String, Vec<String>)]) -> Vec<&str> {
+        plugins.iter().map(|(id, _)| id.as_str()).collect()
+    }
+
+    #[test]
+    fn places_dependencies_first() {
+        let (ordered, cyclic) = sort_by_dependencies(vec![
+            plugin("app", &["ui", "core"]),
+            plugin("ui", &["core"]),
+            plugin("core", &[]),
+        ]);
+        assert_eq!(ids(&ordered), ["core", "ui", "app"]);
+        assert!(cyclic.is_empty());
+    }
+
+    #[test]
+    fn keeps_the_order_of_independent_plugins() {
+        let (ordered, _) = sort_by_dependencies(vec![
+            plugin("c", &[]),
+            plugin("a", &[]),
+            plugin("b", &["c"]),
+            plugin("d", &[]),
+        ]);
+        assert_eq!(ids(&ordered), ["c", "a", "b", "d"]);
+    }
+
+    #[test]
+    fn ignores_dependencies_that_are_not_loaded() {
+        let (ordered, cyclic) = sort_by_dependencies(vec![
+            plugin("a", &["missing"]),
+            plugin("b", &["a", "also-missing"]),
+        ]);
+        assert_eq!(ids(&ordered), ["a", "b"]);
+        assert!(cyclic.is_empty());
+    }
+
+    #[test]
+    fn reports_cycles_and_what_depends_on_them() {
+        let (ordered, cyclic) = sort_by_dependencies(vec![
+            plugin("a", &["b"]),
+            plugin("b", &["a"]),
+            plugin("c", &["a"]),
+            plugin("d", &[]),
+        ]);
+        assert_eq!(ids(&ordered), ["d"]);
+        assert_eq!(cyclic, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn reports_a_plugin_depending_on_itself() {
+        let (ordered, cyclic) = sort_by_dependencies(vec![plugin("a", &["a"]), plugin("b", &[])]);
+        assert_eq!(ids(&ordered), ["b"]);
+        assert_eq!(cyclic, ["a"]);
+    }
+}
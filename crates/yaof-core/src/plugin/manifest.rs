@@ -1,6 +1,8 @@
 use indexmap::IndexMap;
 
 use crate::Error;
+use crate::overlay::DesktopLevel;
+use crate::settings::Padding;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -40,6 +42,16 @@ pub struct OverlayDefinition {
     /// plugin to render different components based on the route.
     #[serde(default)]
     pub route: Option<String>,
+    /// Edge padding used when placing the overlay from a position preset
+    #[serde(default)]
+    pub padding: Padding,
+    /// Opt in to keeping the window hidden until its page has loaded, for at most
+    /// this long. Shown right away when unset or 0
+    #[serde(default)]
+    pub ready_timeout_ms: Option<u64>,
+    /// Pin the overlay to the desktop behind all windows instead of floating on top
+    #[serde(default)]
+    pub desktop_level: Option<DesktopLevel>,
 }
 
 fn default_true() -> bool {
@@ -57,6 +69,13 @@ pub struct DevConfig {
     pub port: u16,
 }
 
+impl DevConfig {
+    /// Entry URL on the plugin's local dev server for a HashRouter route
+    pub fn entry_url(&self, route: &str) -> String {
+        format!("http://localhost:{}/#{}", self.port, route)
+    }
+}
+
 /// Supported platform identifiers
 pub type Platform = String; // e.g., "darwin-arm64", "darwin-x64", "linux-x64", "win32-x64"
 
@@ -66,7 +85,8 @@ pub struct NativeConfig {
     /// List of platforms this native plugin supports
     #[serde(default)]
     pub platforms: Vec<Platform>,
-    /// Platform-specific library paths
+    /// Platform-specific library paths, relative to the plugin directory unless absolute.
+    /// `$VAR` and `${VAR}` are expanded from the environment (e.g. `${XDG_DATA_HOME}/...`).
     #[serde(default)]
     pub libraries: std::collections::HashMap<Platform, String>,
     /// Legacy single library path (for backwards compatibility)
@@ -76,6 +96,39 @@ pub struct NativeConfig {
     /// Default is 1000ms (1 second)
     #[serde(default)]
     pub tick_interval_ms: Option<u64>,
+    /// Additional named native components (e.g. a background "worker"), each loaded
+    /// as its own native plugin with the ID `<plugin-id>:<component>`
+    #[serde(default)]
+    pub components: IndexMap<String, NativeComponent>,
+    /// IDs of plugins whose native components must be loaded before this plugin's,
+    /// e.g. the provider of a service it consumes during init
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+}
+
+/// A named native library within a plugin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeComponent {
+    /// Platform-specific library paths
+    #[serde(default)]
+    pub libraries: std::collections::HashMap<Platform, String>,
+    /// Single library path used when no platform-specific entry matches
+    #[serde(default)]
+    pub library: Option<String>,
+    /// Tick interval override for this component, defaults to the plugin's interval
+    #[serde(default)]
+    pub tick_interval_ms: Option<u64>,
+}
+
+impl NativeComponent {
+    /// Get the library path for the current platform
+    pub fn library_for_current_platform(&self) -> Option<&str> {
+        self.libraries
+            .get(&NativeConfig::current_platform())
+            .map(|lib| lib.as_str())
+            .or(self.library.as_deref())
+    }
 }
 
 impl NativeConfig {
@@ -98,12 +151,23 @@ impl NativeConfig {
 
         // If platforms list is empty, assume all platforms are supported (legacy behavior)
         if self.platforms.is_empty() {
-            return self.library.is_some() || !self.libraries.is_empty();
+            return self.library.is_some()
+                || !self.libraries.is_empty()
+                || !self.components.is_empty();
         }
 
         self.platforms.contains(&current_platform)
     }
 
+    /// Get the native plugin ID for a component of the given plugin.
+    /// The main library uses the plugin ID itself.
+    pub fn component_id(plugin_id: &str, component: Option<&str>) -> String {
+        match component {
+            Some(name) => format!("{}:{}", plugin_id, name),
+            None => plugin_id.to_string(),
+        }
+    }
+
     /// Get the current platform identifier
     pub fn current_platform() -> Platform {
         #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
@@ -241,6 +305,25 @@ pub enum SettingField {
     },
 }
 
+impl SettingField {
+    /// The field's declared default value, None for categories and fields without one
+    pub fn default_value(&self) -> Option<serde_json::Value> {
+        match self {
+            SettingField::String { default, .. }
+            | SettingField::Select { default, .. }
+            | SettingField::Color { default, .. }
+            | SettingField::Keybind { default, .. } => default.clone().map(Into::into),
+            SettingField::Number { default, .. } | SettingField::Slider { default, .. } => {
+                default.map(Into::into)
+            }
+            SettingField::Boolean { default, .. } => default.map(Into::into),
+            SettingField::OrderedList { default, .. } => default.clone().map(Into::into),
+            SettingField::MultiChoice { default, .. } => default.clone().map(Into::into),
+            SettingField::Category { .. } => None,
+        }
+    }
+}
+
 /// Option for select fields
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SelectOption {
@@ -260,6 +343,30 @@ pub struct PluginSettingsConfig {
     pub component: Option<String>,
 }
 
+impl PluginSettingsConfig {
+    /// Default value of every field that declares one, keyed by setting key.
+    /// Category fields only group settings, so their fields are flattened in.
+    pub fn defaults(&self) -> serde_json::Map<String, serde_json::Value> {
+        // dyn, a generic iterator would grow a new type on every level of recursion
+        fn collect<'a>(
+            fields: &mut dyn Iterator<Item = (&'a String, &'a SettingField)>,
+            defaults: &mut serde_json::Map<String, serde_json::Value>,
+        ) {
+            for (key, field) in fields {
+                if let SettingField::Category { fields, .. } = field {
+                    collect(&mut fields.iter().map(|(k, f)| (k, f.as_ref())), defaults);
+                } else if let Some(default) = field.default_value() {
+                    defaults.insert(key.clone(), default);
+                }
+            }
+        }
+
+        let mut defaults = serde_json::Map::new();
+        collect(&mut self.schema.iter(), &mut defaults);
+        defaults
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PluginManifest {
@@ -286,8 +393,26 @@ pub struct PluginManifest {
     /// Plugin settings configuration
     #[serde(default)]
     pub settings: Option<PluginSettingsConfig>,
+    /// Oldest yaof version (semver) the plugin works with
+    #[serde(default)]
+    pub min_yaof_version: Option<String>,
+    /// Items added to the tray's Plugins submenu
+    #[serde(default)]
+    pub tray: Vec<TrayMenuItem>,
 }
 
+/// A tray menu item declared by a plugin. Clicking it sends the plugin's native
+/// components a `tray:<id>` message and emits `yaof:plugin:{plugin-id}:tray`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrayMenuItem {
+    pub id: String,
+    pub label: String,
+}
+
+/// Version of the running yaof, compared against `minYaofVersion`
+pub const YAOF_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 /// List of plugin IDs that are allowed to be loaded as core plugins.
 /// This prevents third-party plugins from claiming to be core plugins.
 pub const ALLOWED_CORE_PLUGINS: &[&str] = &[
@@ -303,9 +428,27 @@ impl PluginManifest {
     }
 }
 
+/// Plugin manifest file name
+pub const MANIFEST_FILE: &str = "overlay.json";
+
+/// Alternative manifest file name, parsed as JSON5 (comments, trailing commas, ...)
+pub const MANIFEST_FILE_JSON5: &str = "overlay.json5";
+
 impl PluginManifest {
+    /// Parse a manifest as strict JSON. Manifests that only fail because of
+    /// comments or trailing commas are accepted through the JSON5 parser instead.
     pub fn from_json(json: &str) -> Result<Self, Error> {
-        serde_json::from_str(json).map_err(|e| Error::ManifestParse(e.to_string()))
+        serde_json::from_str(json).or_else(|strict_err| {
+            // Report the strict error for anything JSON5 can't make sense of either
+            json5::from_str(json)
+                .inspect(|_| tracing::debug!("Parsed manifest leniently: {}", strict_err))
+                .map_err(|_| Error::ManifestParse(strict_err.to_string()))
+        })
+    }
+
+    /// Parse a manifest as JSON5
+    pub fn from_json5(json5: &str) -> Result<Self, Error> {
+        json5::from_str(json5).map_err(|e| Error::ManifestParse(e.to_string()))
     }
 
     /// Validate the manifest
@@ -325,12 +468,32 @@ impl PluginManifest {
                 "entry is required for non-native plugins".to_string(),
             ));
         }
+        self.check_yaof_version()?;
+        let mut tray_ids = std::collections::HashSet::new();
+        for item in &self.tray {
+            if item.id.is_empty() || item.label.is_empty() {
+                return Err(Error::ManifestParse(
+                    "tray items need an id and a label".to_string(),
+                ));
+            }
+            if !tray_ids.insert(item.id.as_str()) {
+                return Err(Error::ManifestParse(format!(
+                    "duplicate tray item id {:?}",
+                    item.id
+                )));
+            }
+        }
         // Validate native config if present
         if let Some(ref native) = self.native {
+            if native.dependencies.contains(&self.id) {
+                return Err(Error::ManifestParse(
+                    "native.dependencies must not include the plugin itself".to_string(),
+                ));
+            }
             if !native.supports_current_platform() {
                 // This is a warning, not an error - the plugin just won't load on this platform
-                eprintln!(
-                    "[YAOF] Warning: Plugin {} does not support current platform ({})",
+                tracing::warn!(
+                    "Plugin {} does not support current platform ({})",
                     self.id,
                     NativeConfig::current_platform()
                 );
@@ -339,6 +502,45 @@ impl PluginManifest {
         Ok(())
     }
 
+    /// Refuse plugins that need a newer yaof, and warn about ones written
+    /// for an older major version
+    fn check_yaof_version(&self) -> Result<(), Error> {
+        let Some(min) = &self.min_yaof_version else {
+            return Ok(());
+        };
+
+        let required = semver::Version::parse(min).map_err(|e| {
+            Error::ManifestParse(format!("invalid minYaofVersion {:?}: {}", min, e))
+        })?;
+        let running = semver::Version::parse(YAOF_VERSION).expect("crate version is valid semver");
+
+        if required > running {
+            return Err(Error::Incompatible(format!(
+                "{} requires yaof {} or newer, running {}",
+                self.id, required, running
+            )));
+        }
+
+        if required.major < running.major {
+            tracing::warn!(
+                "Plugin {} targets yaof {}, it may not work with {}",
+                self.id,
+                required,
+                running
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Dev server entry URL for a route, when dev mode is on and `dev.port` is set
+    pub fn dev_entry_point(&self, route: &str) -> Option<String> {
+        if !crate::is_dev_mode() {
+            return None;
+        }
+        self.dev.as_ref().map(|dev| dev.entry_url(route))
+    }
+
     /// Check if this plugin has a native component
     pub fn has_native(&self) -> bool {
         self.native.is_some()
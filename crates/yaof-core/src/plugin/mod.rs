@@ -1,9 +1,17 @@
+pub mod integrity;
 pub mod loader;
 pub mod manager;
 pub mod manifest;
 pub mod native;
+pub mod registry;
+pub mod secrets;
+pub mod signing;
 
+pub use integrity::*;
 pub use loader::*;
 pub use manager::*;
 pub use manifest::*;
 pub use native::*;
+pub use registry::*;
+pub use secrets::*;
+pub use signing::*;
@@ -1,23 +1,42 @@
 //! Native plugin host for loading and running Rust plugins
 
+use std::collections::HashMap;
 use std::ffi::c_void;
-use std::path::Path;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
 
 use libloading::{Library, Symbol};
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
+use tempfile::TempPath;
 
+use super::signing::SigningPolicy;
 use crate::error::Error;
+use crate::logging::NATIVE_PLUGIN_TARGET;
+use crate::services::{SERVICE_BATCH_EVENT, SERVICE_BINARY_EVENT_PREFIX, SERVICE_RESPONSE_EVENT};
 
 /// ABI version - must match yaof-plugin
-const ABI_VERSION: u32 = 1;
+const ABI_VERSION: u32 = 5;
+
+/// Oldest ABI version still accepted. Newer versions only append to
+/// `PluginContext`, so older plugins simply never read the extra fields.
+const MIN_ABI_VERSION: u32 = 1;
+
+/// Message type used to deliver timers to `handle_message` (must match yaof-plugin)
+const TIMER_MESSAGE_TYPE: &str = "__timer";
 
 /// C-compatible plugin vtable (must match yaof-plugin::abi)
+///
+/// `init` and `shutdown` are required by the ABI but are read as `Option` so a
+/// malformed library exporting null pointers can be rejected instead of called.
 #[repr(C)]
 struct PluginVTable {
     abi_version: u32,
-    init: unsafe extern "C" fn(*mut PluginContext) -> i32,
+    init: Option<unsafe extern "C" fn(*mut PluginContext) -> i32>,
     tick: Option<unsafe extern "C" fn(*mut PluginContext) -> i32>,
-    shutdown: unsafe extern "C" fn(*mut PluginContext) -> i32,
+    shutdown: Option<unsafe extern "C" fn(*mut PluginContext) -> i32>,
     handle_message:
         Option<unsafe extern "C" fn(*mut PluginContext, *const u8, usize, *const u8, usize) -> i32>,
 }
@@ -28,17 +47,100 @@ struct PluginContext {
     host_data: *mut c_void,
     emit_event: unsafe extern "C" fn(*mut c_void, *const u8, usize, *const u8, usize) -> i32,
     log: unsafe extern "C" fn(*mut c_void, u32, *const u8, usize),
+    set_timer: unsafe extern "C" fn(*mut c_void, *const u8, usize, u64, u32) -> i32,
+    clear_timer: unsafe extern "C" fn(*mut c_void, *const u8, usize) -> i32,
+    store_get: unsafe extern "C" fn(
+        *mut c_void,
+        *const u8,
+        usize,
+        *mut c_void,
+        unsafe extern "C" fn(*mut c_void, *const u8, usize),
+    ) -> i32,
+    store_set: unsafe extern "C" fn(*mut c_void, *const u8, usize, *const u8, usize) -> i32,
+    emit_event_binary: unsafe extern "C" fn(*mut c_void, *const u8, usize, *const u8, usize) -> i32,
+    secret_get: unsafe extern "C" fn(
+        *mut c_void,
+        *const u8,
+        usize,
+        *mut c_void,
+        unsafe extern "C" fn(*mut c_void, *const u8, usize),
+    ) -> i32,
+    secret_set: unsafe extern "C" fn(*mut c_void, *const u8, usize, *const u8, usize) -> i32,
+}
+
+/// Due time of the earliest timer across all native plugins, shared with the tick loop
+/// so it can check for due timers without taking the manager lock
+pub type NextTimer = Arc<Mutex<Option<Instant>>>;
+
+/// A timer scheduled by a plugin through its context
+struct Timer {
+    due: Instant,
+    /// Repeat interval, None for one-shot timers
+    interval: Option<Duration>,
 }
 
 /// Host data stored in the context
 struct HostData {
     app: AppHandle,
     plugin_id: String,
+    /// Timers by id, plugins may schedule them from their own threads
+    timers: Mutex<HashMap<String, Timer>>,
+    next_timer: NextTimer,
+}
+
+/// A library checked against the signing policy and written to a private copy
+/// in `<yaof home>/cache/native`. Loading the copy loads exactly the checked bytes,
+/// the original can't be swapped in between. The copy is deleted when dropped.
+pub struct VerifiedLibrary {
+    path: TempPath,
+}
+
+impl VerifiedLibrary {
+    /// Read `source` once, check it against `policy` and copy it
+    pub fn copy(source: &Path, policy: &SigningPolicy) -> Result<Self, Error> {
+        let contents = fs::read(source)?;
+        policy.verify(source, &contents)?;
+
+        let dir = crate::yaof_home()
+            .ok_or_else(|| {
+                Error::IoError(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "Could not find home directory",
+                ))
+            })?
+            .join("cache")
+            .join("native");
+        fs::create_dir_all(&dir)?;
+
+        let stem = source
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("plugin");
+        let suffix = source
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| format!(".{}", e))
+            .unwrap_or_default();
+
+        // Randomly named and only accessible by the current user
+        let mut file = tempfile::Builder::new()
+            .prefix(&format!("{}-", stem))
+            .suffix(&suffix)
+            .tempfile_in(&dir)?;
+        file.write_all(&contents)?;
+        file.flush()?;
+
+        Ok(Self {
+            path: file.into_temp_path(),
+        })
+    }
 }
 
 /// Hosts a native plugin
 pub struct NativePluginHost {
     _library: Library,
+    /// Private copy the library was loaded from, dropped after the library
+    _copy: Option<VerifiedLibrary>,
     vtable: &'static PluginVTable,
     context: Box<PluginContext>,
     host_data: Box<HostData>,
@@ -53,40 +155,106 @@ unsafe impl Send for NativePluginHost {}
 unsafe impl Sync for NativePluginHost {}
 
 impl NativePluginHost {
-    /// Load a native plugin from a dynamic library
-    pub fn load(path: &Path, plugin_id: String, app: AppHandle) -> Result<Self, Error> {
+    /// Load a native plugin from a dynamic library.
+    ///
+    /// While signed plugins are required, unsigned libraries are refused before any
+    /// of their code runs and the checked bytes are loaded from a private copy.
+    pub fn load(
+        path: &Path,
+        plugin_id: String,
+        app: AppHandle,
+        next_timer: NextTimer,
+    ) -> Result<Self, Error> {
+        let policy = SigningPolicy::load(&app)?;
+        if policy.require_signed {
+            let library = VerifiedLibrary::copy(path, &policy)?;
+            Self::load_verified(library, plugin_id, app, next_timer)
+        } else {
+            Self::load_library(path, None, plugin_id, app, next_timer)
+        }
+    }
+
+    /// Load a native plugin from a library that was already checked and copied
+    pub fn load_verified(
+        library: VerifiedLibrary,
+        plugin_id: String,
+        app: AppHandle,
+        next_timer: NextTimer,
+    ) -> Result<Self, Error> {
+        let path = library.path.to_path_buf();
+        Self::load_library(&path, Some(library), plugin_id, app, next_timer)
+    }
+
+    fn load_library(
+        path: &Path,
+        copy: Option<VerifiedLibrary>,
+        plugin_id: String,
+        app: AppHandle,
+        next_timer: NextTimer,
+    ) -> Result<Self, Error> {
         // Load the library
-        let library = unsafe { Library::new(path) }
-            .map_err(|e| Error::PluginNotFound(format!("Failed to load library: {}", e)))?;
+        let library =
+            unsafe { Library::new(path) }.map_err(|e| Error::NativeLoad(e.to_string()))?;
 
         // Get the vtable symbol
         let vtable: Symbol<*const PluginVTable> = unsafe { library.get(b"YAOF_PLUGIN") }
-            .map_err(|e| Error::PluginNotFound(format!("Symbol not found: {}", e)))?;
+            .map_err(|e| Error::NativeLoad(format!("Symbol not found: {}", e)))?;
+
+        if vtable.is_null() {
+            return Err(Error::NativeLoad(
+                "invalid vtable: YAOF_PLUGIN is null".to_string(),
+            ));
+        }
 
         let vtable: &'static PluginVTable = unsafe { &**vtable };
 
         // Check ABI version
-        if vtable.abi_version != ABI_VERSION {
-            return Err(Error::PluginNotFound(format!(
-                "ABI version mismatch: expected {}, got {}",
-                ABI_VERSION, vtable.abi_version
+        if !(MIN_ABI_VERSION..=ABI_VERSION).contains(&vtable.abi_version) {
+            return Err(Error::NativeLoad(format!(
+                "ABI version mismatch: expected {}-{}, got {}",
+                MIN_ABI_VERSION, ABI_VERSION, vtable.abi_version
             )));
         }
 
+        // Validate required function pointers before the first call
+        // Optional tick/handle_message being None is fine
+        let Some(init) = vtable.init else {
+            return Err(Error::NativeLoad(
+                "invalid vtable: init is null".to_string(),
+            ));
+        };
+        if vtable.shutdown.is_none() {
+            return Err(Error::NativeLoad(
+                "invalid vtable: shutdown is null".to_string(),
+            ));
+        }
+
         // Create host data
-        let mut host_data = Box::new(HostData { app, plugin_id });
+        let mut host_data = Box::new(HostData {
+            app,
+            plugin_id,
+            timers: Mutex::new(HashMap::new()),
+            next_timer,
+        });
 
         // Create context
         let mut context = Box::new(PluginContext {
             host_data: host_data.as_mut() as *mut HostData as *mut c_void,
             emit_event: emit_event_callback,
             log: log_callback,
+            set_timer: set_timer_callback,
+            clear_timer: clear_timer_callback,
+            store_get: store_get_callback,
+            store_set: store_set_callback,
+            emit_event_binary: emit_event_binary_callback,
+            secret_get: secret_get_callback,
+            secret_set: secret_set_callback,
         });
 
         // Initialize the plugin
-        let result = unsafe { (vtable.init)(context.as_mut()) };
+        let result = unsafe { init(context.as_mut()) };
         if result != 0 {
-            return Err(Error::PluginNotFound(format!(
+            return Err(Error::NativeLoad(format!(
                 "Plugin init returned error code: {}",
                 result
             )));
@@ -94,6 +262,7 @@ impl NativePluginHost {
 
         Ok(Self {
             _library: library,
+            _copy: copy,
             vtable,
             context,
             host_data,
@@ -127,10 +296,64 @@ impl NativePluginHost {
     }
 }
 
+impl NativePluginHost {
+    /// Deliver every due timer to the plugin as a `TIMER_MESSAGE_TYPE` message.
+    /// Returns the ids and results of the timers that fired.
+    pub fn fire_timers(&mut self) -> Vec<(String, i32)> {
+        let now = Instant::now();
+
+        let due: Vec<String> = {
+            let Ok(mut timers) = self.host_data.timers.lock() else {
+                return Vec::new();
+            };
+
+            let due: Vec<String> = timers
+                .iter()
+                .filter(|(_, timer)| timer.due <= now)
+                .map(|(id, _)| id.clone())
+                .collect();
+
+            // Reschedule intervals, drop one-shot timers
+            for id in &due {
+                match timers.get(id).and_then(|t| t.interval) {
+                    Some(interval) => {
+                        if let Some(timer) = timers.get_mut(id) {
+                            timer.due = now + interval;
+                        }
+                    }
+                    None => {
+                        timers.remove(id);
+                    }
+                }
+            }
+
+            due
+        };
+
+        // The lock is released so the plugin can schedule timers while handling these
+        due.into_iter()
+            .map(|id| {
+                let payload = serde_json::json!({ "id": id }).to_string();
+                let result = self.send_message(TIMER_MESSAGE_TYPE, payload.as_bytes());
+                (id, result)
+            })
+            .collect()
+    }
+
+    /// Due time of the plugin's earliest timer
+    pub fn next_timer_due(&self) -> Option<Instant> {
+        let timers = self.host_data.timers.lock().ok()?;
+        timers.values().map(|timer| timer.due).min()
+    }
+}
+
 impl Drop for NativePluginHost {
     fn drop(&mut self) {
-        unsafe {
-            (self.vtable.shutdown)(self.context.as_mut());
+        // Validated as non-null in load()
+        if let Some(shutdown) = self.vtable.shutdown {
+            unsafe {
+                shutdown(self.context.as_mut());
+            }
         }
     }
 }
@@ -155,16 +378,148 @@ unsafe extern "C" fn emit_event_callback(
             Err(_) => return -1,
         };
 
-        // Emit to the service event channel that useService listens on
-        // Format: yaof:service:{event_name} - this matches what the SDK's useService hook expects
-        let full_event = format!("yaof:service:{}", event_name);
-        match host.app.emit(&full_event, payload_json) {
+        if event_name == SERVICE_RESPONSE_EVENT {
+            return resolve_service_request(host, payload_json);
+        }
+
+        if event_name == SERVICE_BATCH_EVENT {
+            let Ok(updates) =
+                serde_json::from_value::<Vec<(String, serde_json::Value)>>(payload_json)
+            else {
+                return -1;
+            };
+            // Validated as a whole, like the service_broadcast_batch command
+            return broadcast_native(host, |registry, app| {
+                for (service_id, _) in &updates {
+                    claim_native_service(registry, app, service_id, &host.plugin_id)?;
+                }
+                registry.broadcast_batch(updates, app)
+            });
+        }
+
+        broadcast_native(host, |registry, app| {
+            claim_native_service(registry, app, event_name, &host.plugin_id)?;
+            registry.broadcast(event_name, payload_json, app)
+        })
+    }
+}
+
+/// Claim a service for a native plugin before it broadcasts on it. A service another
+/// plugin provides is not broadcast; the conflict is reported like `service_register` does.
+fn claim_native_service(
+    registry: &mut crate::ServiceRegistry,
+    app: &AppHandle,
+    service_id: &str,
+    plugin_id: &str,
+) -> Result<(), String> {
+    registry
+        .claim_native_service(service_id, plugin_id)
+        .map_err(|conflict| {
+            let _ = app.emit(crate::SERVICE_CONFLICT_EVENT, &conflict);
+            format!(
+                "Service {} is already provided by plugin {}",
+                service_id, conflict.existing_plugin_id
+            )
+        })
+}
+
+/// Run a native plugin's broadcast through the service registry, under the same rate
+/// limit as `service_broadcast`. Emits on the service event channel useService listens on.
+/// The plugin is recorded as the services' provider, so unloading it unregisters them.
+fn broadcast_native(
+    host: &HostData,
+    broadcast: impl FnOnce(&mut crate::ServiceRegistry, &AppHandle) -> Result<(), String>,
+) -> i32 {
+    let Some(state) = host.app.try_state::<crate::OverlayState>() else {
+        return -1;
+    };
+    let Ok(mut manager) = state.0.lock() else {
+        return -1;
+    };
+
+    match broadcast(manager.registry_mut(), &host.app) {
+        Ok(()) => 0,
+        Err(e) => {
+            tracing::warn!("Native plugin {} broadcast failed: {}", host.plugin_id, e);
+            -1
+        }
+    }
+}
+
+// Callback for plugins to emit raw bytes without a JSON round trip
+unsafe extern "C" fn emit_event_binary_callback(
+    host_data: *mut c_void,
+    event_name: *const u8,
+    event_name_len: usize,
+    data: *const u8,
+    data_len: usize,
+) -> i32 {
+    unsafe {
+        let host = &*(host_data as *const HostData);
+        let Ok(event_name) =
+            std::str::from_utf8(std::slice::from_raw_parts(event_name, event_name_len))
+        else {
+            return -1;
+        };
+        let data = std::slice::from_raw_parts(data, data_len).to_vec();
+
+        let Some(state) = host.app.try_state::<crate::OverlayState>() else {
+            return -1;
+        };
+        match state.0.lock() {
+            Ok(mut manager) => manager.registry_mut().record_binary(event_name, data),
+            Err(_) => return -1,
+        }
+
+        // Only announce the payload, overlays fetch it with service_get_binary
+        let full_event = format!("{}:{}", SERVICE_BINARY_EVENT_PREFIX, event_name);
+        match host
+            .app
+            .emit(&full_event, serde_json::json!({ "byteLength": data_len }))
+        {
             Ok(_) => 0,
             Err(_) => -1,
         }
     }
 }
 
+/// Deliver a native provider's reply to the request it answers
+fn resolve_service_request(host: &HostData, payload: serde_json::Value) -> i32 {
+    let Some(request_id) = payload.get("requestId").and_then(|v| v.as_str()) else {
+        return -1;
+    };
+
+    let response = match payload.get("error").and_then(|v| v.as_str()) {
+        Some(error) => Err(error.to_string()),
+        None => Ok(payload
+            .get("result")
+            .cloned()
+            .unwrap_or(serde_json::Value::Null)),
+    };
+
+    let Some(state) = host.app.try_state::<crate::OverlayState>() else {
+        return -1;
+    };
+    let Ok(mut manager) = state.0.lock() else {
+        return -1;
+    };
+
+    match manager
+        .registry_mut()
+        .resolve_request(request_id, &host.plugin_id, response)
+    {
+        Ok(()) => 0,
+        Err(e) => {
+            tracing::warn!(
+                "Plugin {} failed to answer a request: {}",
+                host.plugin_id,
+                e
+            );
+            -1
+        }
+    }
+}
+
 // Callback for plugins to log messages
 unsafe extern "C" fn log_callback(
     host_data: *mut c_void,
@@ -177,15 +532,212 @@ unsafe extern "C" fn log_callback(
         let message =
             std::str::from_utf8_unchecked(std::slice::from_raw_parts(message, message_len));
 
-        let level_str = match level {
-            0 => "TRACE",
-            1 => "DEBUG",
-            2 => "INFO",
-            3 => "WARN",
-            4 => "ERROR",
-            _ => "UNKNOWN",
+        // Route through tracing so plugin logs share the host's filter
+        let plugin = host.plugin_id.as_str();
+        match level {
+            0 => tracing::trace!(target: NATIVE_PLUGIN_TARGET, plugin, "{}", message),
+            1 => tracing::debug!(target: NATIVE_PLUGIN_TARGET, plugin, "{}", message),
+            2 => tracing::info!(target: NATIVE_PLUGIN_TARGET, plugin, "{}", message),
+            3 => tracing::warn!(target: NATIVE_PLUGIN_TARGET, plugin, "{}", message),
+            _ => tracing::error!(target: NATIVE_PLUGIN_TARGET, plugin, "{}", message),
+        }
+    }
+}
+
+// Callback for plugins to schedule a timer
+unsafe extern "C" fn set_timer_callback(
+    host_data: *mut c_void,
+    timer_id: *const u8,
+    timer_id_len: usize,
+    delay_ms: u64,
+    repeat: u32,
+) -> i32 {
+    unsafe {
+        let host = &*(host_data as *const HostData);
+        let Ok(timer_id) = std::str::from_utf8(std::slice::from_raw_parts(timer_id, timer_id_len))
+        else {
+            return -1;
         };
 
-        println!("[{}][{}] {}", host.plugin_id, level_str, message);
+        let delay = Duration::from_millis(delay_ms);
+        let timer = Timer {
+            due: Instant::now() + delay,
+            interval: (repeat != 0).then_some(delay.max(Duration::from_millis(1))),
+        };
+
+        let due = timer.due;
+        match host.timers.lock() {
+            Ok(mut timers) => {
+                timers.insert(timer_id.to_string(), timer);
+            }
+            Err(_) => return -1,
+        }
+
+        // Only taken after the timer table is released, see NativePluginManager::fire_timers
+        let mut next_timer = host
+            .next_timer
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if next_timer.is_none_or(|next| due < next) {
+            *next_timer = Some(due);
+        }
+        0
+    }
+}
+
+// Callback for plugins to cancel a timer
+unsafe extern "C" fn clear_timer_callback(
+    host_data: *mut c_void,
+    timer_id: *const u8,
+    timer_id_len: usize,
+) -> i32 {
+    unsafe {
+        let host = &*(host_data as *const HostData);
+        let Ok(timer_id) = std::str::from_utf8(std::slice::from_raw_parts(timer_id, timer_id_len))
+        else {
+            return -1;
+        };
+
+        match host.timers.lock() {
+            Ok(mut timers) if timers.contains_key(timer_id) => {
+                timers.remove(timer_id);
+                0
+            }
+            _ => -1,
+        }
+    }
+}
+
+/// Persistent store file for a native plugin. Components (`<plugin-id>:<component>`)
+/// share their plugin's store.
+fn native_store_path(app: &AppHandle, plugin_id: &str) -> PathBuf {
+    let plugin_id = plugin_id.split(':').next().unwrap_or(plugin_id);
+    crate::plugin_store_path(app, &format!("{}-native-store.json", plugin_id))
+}
+
+// Callback for plugins to read from their persistent store
+unsafe extern "C" fn store_get_callback(
+    host_data: *mut c_void,
+    key: *const u8,
+    key_len: usize,
+    out: *mut c_void,
+    write: unsafe extern "C" fn(*mut c_void, *const u8, usize),
+) -> i32 {
+    use base64::Engine;
+    use tauri_plugin_store::StoreExt;
+
+    unsafe {
+        let host = &*(host_data as *const HostData);
+        let Ok(key) = std::str::from_utf8(std::slice::from_raw_parts(key, key_len)) else {
+            return -1;
+        };
+
+        let store_path = native_store_path(&host.app, &host.plugin_id);
+        let store = match host.app.store(store_path) {
+            Ok(store) => store,
+            Err(_) => return -1,
+        };
+
+        // Values are stored base64 encoded so arbitrary bytes survive the JSON file
+        let Some(value) = store.get(key) else {
+            return 1;
+        };
+        let Some(bytes) = value
+            .as_str()
+            .and_then(|s| base64::engine::general_purpose::STANDARD.decode(s).ok())
+        else {
+            return -1;
+        };
+
+        write(out, bytes.as_ptr(), bytes.len());
+        0
+    }
+}
+
+// Callback for plugins to write to their persistent store
+unsafe extern "C" fn store_set_callback(
+    host_data: *mut c_void,
+    key: *const u8,
+    key_len: usize,
+    value: *const u8,
+    value_len: usize,
+) -> i32 {
+    use base64::Engine;
+    use tauri_plugin_store::StoreExt;
+
+    unsafe {
+        let host = &*(host_data as *const HostData);
+        let Ok(key) = std::str::from_utf8(std::slice::from_raw_parts(key, key_len)) else {
+            return -1;
+        };
+        let value = std::slice::from_raw_parts(value, value_len);
+
+        let store_path = native_store_path(&host.app, &host.plugin_id);
+        let store = match host.app.store(store_path) {
+            Ok(store) => store,
+            Err(_) => return -1,
+        };
+
+        store.set(key, base64::engine::general_purpose::STANDARD.encode(value));
+        match store.save() {
+            Ok(()) => 0,
+            Err(_) => -1,
+        }
+    }
+}
+
+// Callback for plugins to read a secret from the OS keychain. Blocks on the keychain, so
+// plugins must not call it from tick/handle_message while the tick loop holds the lock
+unsafe extern "C" fn secret_get_callback(
+    host_data: *mut c_void,
+    key: *const u8,
+    key_len: usize,
+    out: *mut c_void,
+    write: unsafe extern "C" fn(*mut c_void, *const u8, usize),
+) -> i32 {
+    unsafe {
+        let host = &*(host_data as *const HostData);
+        let Ok(key) = std::str::from_utf8(std::slice::from_raw_parts(key, key_len)) else {
+            return -1;
+        };
+
+        match crate::get_secret(&host.plugin_id, key) {
+            Ok(Some(value)) => {
+                write(out, value.as_ptr(), value.len());
+                0
+            }
+            Ok(None) => 1,
+            Err(e) => {
+                tracing::warn!("Failed to read secret for {}: {}", host.plugin_id, e);
+                -1
+            }
+        }
+    }
+}
+
+// Callback for plugins to store a secret in the OS keychain
+unsafe extern "C" fn secret_set_callback(
+    host_data: *mut c_void,
+    key: *const u8,
+    key_len: usize,
+    value: *const u8,
+    value_len: usize,
+) -> i32 {
+    unsafe {
+        let host = &*(host_data as *const HostData);
+        let Ok(key) = std::str::from_utf8(std::slice::from_raw_parts(key, key_len)) else {
+            return -1;
+        };
+        let Ok(value) = std::str::from_utf8(std::slice::from_raw_parts(value, value_len)) else {
+            return -1;
+        };
+
+        match crate::set_secret(&host.plugin_id, key, value) {
+            Ok(()) => 0,
+            Err(e) => {
+                tracing::warn!("Failed to store secret for {}: {}", host.plugin_id, e);
+                -1
+            }
+        }
     }
 }
@@ -0,0 +1,168 @@
+//! Plugin registry index
+//!
+//! A registry is a JSON index of available plugins served over HTTP(S), either a
+//! plain array of entries or `{ "plugins": [...] }`. Fetched indexes are cached
+//! under `<yaof home>/cache` so the browse list still works offline; a stale cache
+//! is served when the registry can't be reached.
+
+use std::{
+    fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+/// How long a cached index is used without refetching
+pub const REGISTRY_CACHE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Timeout for fetching an index
+const REGISTRY_FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// A plugin listed in a registry index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryEntry {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Where to install the plugin from (git URL or npm package)
+    pub source: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RegistryIndex {
+    List(Vec<RegistryEntry>),
+    Wrapped { plugins: Vec<RegistryEntry> },
+}
+
+impl From<RegistryIndex> for Vec<RegistryEntry> {
+    fn from(index: RegistryIndex) -> Self {
+        match index {
+            RegistryIndex::List(entries) | RegistryIndex::Wrapped { plugins: entries } => entries,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedIndex {
+    url: String,
+    /// Seconds since the Unix epoch
+    fetched_at: u64,
+    entries: Vec<RegistryEntry>,
+}
+
+impl CachedIndex {
+    fn is_fresh(&self) -> bool {
+        now_secs().saturating_sub(self.fetched_at) < REGISTRY_CACHE_TTL.as_secs()
+    }
+}
+
+/// Get a registry's index, from the cache while it is fresh (unless `refresh` is set).
+/// Falls back to a stale cache if the registry can't be fetched.
+pub async fn fetch_index(url: &str, refresh: bool) -> Result<Vec<RegistryEntry>, Error> {
+    let parsed: tauri::Url = url
+        .parse()
+        .map_err(|e| Error::Registry(format!("Invalid registry URL {}: {}", url, e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(Error::Registry(format!(
+            "Registry URL must be http(s): {}",
+            url
+        )));
+    }
+
+    let cached = read_cache(url);
+    if !refresh && let Some(cached) = cached.as_ref().filter(|c| c.is_fresh()) {
+        return Ok(cached.entries.clone());
+    }
+
+    match download_index(url).await {
+        Ok(entries) => {
+            if let Err(e) = write_cache(url, &entries) {
+                tracing::warn!("Failed to cache registry index {}: {}", url, e);
+            }
+            Ok(entries)
+        }
+        Err(e) => match cached {
+            Some(cached) => {
+                tracing::warn!("Using cached registry index for {}: {}", url, e);
+                Ok(cached.entries)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+async fn download_index(url: &str) -> Result<Vec<RegistryEntry>, Error> {
+    let client = reqwest::Client::builder()
+        .timeout(REGISTRY_FETCH_TIMEOUT)
+        .build()
+        .map_err(|e| Error::Registry(e.to_string()))?;
+
+    let index: RegistryIndex = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| Error::Registry(format!("Failed to fetch {}: {}", url, e)))?
+        .json()
+        .await
+        .map_err(|e| Error::Registry(format!("Invalid registry index at {}: {}", url, e)))?;
+
+    Ok(index.into())
+}
+
+/// Cache file for a registry, keyed by a hash of its URL
+fn cache_path(url: &str) -> Option<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+
+    crate::yaof_home().map(|home| {
+        home.join("cache")
+            .join(format!("registry-{:016x}.json", hasher.finish()))
+    })
+}
+
+fn read_cache(url: &str) -> Option<CachedIndex> {
+    let content = fs::read_to_string(cache_path(url)?).ok()?;
+    serde_json::from_str::<CachedIndex>(&content)
+        .ok()
+        .filter(|cached| cached.url == url)
+}
+
+fn write_cache(url: &str, entries: &[RegistryEntry]) -> Result<(), Error> {
+    let path = cache_path(url).ok_or_else(|| {
+        Error::IoError(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "Could not find home directory",
+        ))
+    })?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let cached = CachedIndex {
+        url: url.to_string(),
+        fetched_at: now_secs(),
+        entries: entries.to_vec(),
+    };
+    let json = serde_json::to_string_pretty(&cached)
+        .map_err(|e| Error::Registry(format!("Failed to serialize index: {}", e)))?;
+    fs::write(path, json)?;
+
+    Ok(())
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,126 @@
+//! Plugin secrets kept in the OS keychain instead of the plaintext settings store
+//!
+//! Each secret is a keychain entry with service `yaof:<plugin-id>` and the secret's
+//! key as the account, so they show up per plugin in Keychain Access, Credential
+//! Manager or the Secret Service. Keychains can't list entries, so each plugin also
+//! has an index entry naming its keys, which lets uninstalling remove them all.
+
+use std::sync::{Mutex, PoisonError};
+
+use keyring::Entry;
+
+use crate::error::Error;
+
+/// Account of the entry listing a plugin's secret keys. Not a valid secret key.
+const KEY_INDEX_ACCOUNT: &str = ".yaof-keys";
+
+/// Serializes updates of the key indexes
+static KEY_INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+/// Keychain service name for a plugin's secrets. Components (`<plugin-id>:<component>`)
+/// share their plugin's secrets.
+fn secret_service(plugin_id: &str) -> String {
+    let plugin_id = plugin_id.split(':').next().unwrap_or(plugin_id);
+    format!("yaof:{}", plugin_id)
+}
+
+fn secret_entry(plugin_id: &str, key: &str) -> Result<Entry, Error> {
+    if key.is_empty() {
+        return Err(Error::Secret("secret key must not be empty".to_string()));
+    }
+    if key == KEY_INDEX_ACCOUNT {
+        return Err(Error::Secret(format!("{} is a reserved secret key", key)));
+    }
+    Entry::new(&secret_service(plugin_id), key).map_err(|e| Error::Secret(e.to_string()))
+}
+
+/// Store a secret for a plugin, replacing any previous value
+pub fn set_secret(plugin_id: &str, key: &str, value: &str) -> Result<(), Error> {
+    secret_entry(plugin_id, key)?
+        .set_password(value)
+        .map_err(|e| Error::Secret(e.to_string()))?;
+
+    update_key_index(plugin_id, |keys| {
+        if !keys.iter().any(|k| k == key) {
+            keys.push(key.to_string());
+        }
+    })
+}
+
+/// Read a plugin's secret, None if it was never set
+pub fn get_secret(plugin_id: &str, key: &str) -> Result<Option<String>, Error> {
+    match secret_entry(plugin_id, key)?.get_password() {
+        Ok(value) => Ok(Some(value)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(Error::Secret(e.to_string())),
+    }
+}
+
+/// Remove a plugin's secret, returns whether it existed
+pub fn delete_secret(plugin_id: &str, key: &str) -> Result<bool, Error> {
+    let existed = match secret_entry(plugin_id, key)?.delete_credential() {
+        Ok(()) => true,
+        Err(keyring::Error::NoEntry) => false,
+        Err(e) => return Err(Error::Secret(e.to_string())),
+    };
+
+    update_key_index(plugin_id, |keys| keys.retain(|k| k != key))?;
+    Ok(existed)
+}
+
+/// Remove all of a plugin's secrets, returns how many were removed
+pub fn delete_plugin_secrets(plugin_id: &str) -> Result<usize, Error> {
+    let _guard = KEY_INDEX_LOCK
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let index = key_index_entry(plugin_id)?;
+
+    let mut removed = 0;
+    for key in read_key_index(&index)? {
+        match secret_entry(plugin_id, &key)?.delete_credential() {
+            Ok(()) => removed += 1,
+            Err(keyring::Error::NoEntry) => {}
+            Err(e) => return Err(Error::Secret(e.to_string())),
+        }
+    }
+
+    match index.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(removed),
+        Err(e) => Err(Error::Secret(e.to_string())),
+    }
+}
+
+fn key_index_entry(plugin_id: &str) -> Result<Entry, Error> {
+    Entry::new(&secret_service(plugin_id), KEY_INDEX_ACCOUNT)
+        .map_err(|e| Error::Secret(e.to_string()))
+}
+
+fn read_key_index(index: &Entry) -> Result<Vec<String>, Error> {
+    match index.get_password() {
+        Ok(keys) => Ok(serde_json::from_str(&keys).unwrap_or_default()),
+        Err(keyring::Error::NoEntry) => Ok(Vec::new()),
+        Err(e) => Err(Error::Secret(e.to_string())),
+    }
+}
+
+/// Apply a change to the list of a plugin's secret keys, dropping the index once empty
+fn update_key_index(plugin_id: &str, update: impl FnOnce(&mut Vec<String>)) -> Result<(), Error> {
+    let _guard = KEY_INDEX_LOCK
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let index = key_index_entry(plugin_id)?;
+
+    let mut keys = read_key_index(&index)?;
+    update(&mut keys);
+
+    let result = if keys.is_empty() {
+        match index.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e),
+        }
+    } else {
+        let keys = serde_json::to_string(&keys).map_err(|e| Error::Secret(e.to_string()))?;
+        index.set_password(&keys)
+    };
+    result.map_err(|e| Error::Secret(e.to_string()))
+}
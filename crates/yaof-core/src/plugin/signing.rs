@@ -0,0 +1,160 @@
+//! Signature verification for native plugin libraries
+//!
+//! A library can ship with a detached ed25519 signature next to it
+//! (`libfoo.dylib` -> `libfoo.dylib.sig`), either as 64 raw bytes or base64 text.
+//! When "require signed plugins" is enabled, libraries without a signature from
+//! one of the trusted public keys are refused.
+
+use std::path::{Path, PathBuf};
+
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::Serialize;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use crate::error::Error;
+
+/// Store holding the signing settings
+const SIGNING_STORE: &str = "native-plugin-signing.json";
+
+/// Current signing settings, trusted keys are base64-encoded ed25519 public keys
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningPolicy {
+    pub require_signed: bool,
+    pub trusted_keys: Vec<String>,
+}
+
+impl SigningPolicy {
+    /// Load the signing settings from the store
+    pub fn load(app: &AppHandle) -> Result<Self, Error> {
+        let store = app
+            .store(SIGNING_STORE)
+            .map_err(|e| Error::Signature(e.to_string()))?;
+
+        let require_signed = store
+            .get("requireSigned")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let trusted_keys = store
+            .get("trustedKeys")
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default();
+
+        Ok(Self {
+            require_signed,
+            trusted_keys,
+        })
+    }
+
+    fn save(&self, app: &AppHandle) -> Result<(), Error> {
+        let store = app
+            .store(SIGNING_STORE)
+            .map_err(|e| Error::Signature(e.to_string()))?;
+
+        store.set("requireSigned", self.require_signed);
+        store.set("trustedKeys", serde_json::json!(self.trusted_keys));
+        store.save().map_err(|e| Error::Signature(e.to_string()))
+    }
+
+    /// Enable or disable refusing unsigned libraries
+    pub fn set_require_signed(app: &AppHandle, enabled: bool) -> Result<(), Error> {
+        let mut policy = Self::load(app)?;
+        policy.require_signed = enabled;
+        policy.save(app)
+    }
+
+    /// Trust a base64-encoded ed25519 public key
+    pub fn add_trusted_key(app: &AppHandle, key: &str) -> Result<(), Error> {
+        let key = key.trim();
+        parse_public_key(key)?;
+
+        let mut policy = Self::load(app)?;
+        if !policy.trusted_keys.iter().any(|k| k == key) {
+            policy.trusted_keys.push(key.to_string());
+        }
+        policy.save(app)
+    }
+
+    /// Stop trusting a public key
+    pub fn remove_trusted_key(app: &AppHandle, key: &str) -> Result<(), Error> {
+        let mut policy = Self::load(app)?;
+        policy.trusted_keys.retain(|k| k != key.trim());
+        policy.save(app)
+    }
+
+    /// Check a library's contents against the policy before they are loaded.
+    /// `contents` must be the bytes that are loaded, not re-read from `library`.
+    pub fn verify(&self, library: &Path, contents: &[u8]) -> Result<(), Error> {
+        if !self.require_signed {
+            return Ok(());
+        }
+
+        let sig_path = signature_path(library);
+        if !sig_path.exists() {
+            return Err(Error::Signature(format!(
+                "{} is not signed (missing {})",
+                library.display(),
+                sig_path.display()
+            )));
+        }
+
+        let signature = read_signature(&sig_path)?;
+
+        let trusted = self
+            .trusted_keys
+            .iter()
+            .filter_map(|key| match parse_public_key(key) {
+                Ok(key) => Some(key),
+                Err(e) => {
+                    tracing::warn!("Ignoring invalid trusted key: {}", e);
+                    None
+                }
+            })
+            .any(|key| key.verify(contents, &signature).is_ok());
+
+        if trusted {
+            Ok(())
+        } else {
+            Err(Error::Signature(format!(
+                "{} is not signed by a trusted key",
+                library.display()
+            )))
+        }
+    }
+}
+
+/// Path of a library's detached signature
+fn signature_path(library: &Path) -> PathBuf {
+    let mut path = library.as_os_str().to_owned();
+    path.push(".sig");
+    PathBuf::from(path)
+}
+
+fn read_signature(path: &Path) -> Result<Signature, Error> {
+    let bytes = std::fs::read(path)?;
+
+    // Accept raw bytes as well as base64 text
+    let bytes = if bytes.len() == Signature::BYTE_SIZE {
+        bytes
+    } else {
+        BASE64
+            .decode(String::from_utf8_lossy(&bytes).trim())
+            .map_err(|e| Error::Signature(format!("Invalid signature file: {}", e)))?
+    };
+
+    Signature::from_slice(&bytes)
+        .map_err(|e| Error::Signature(format!("Invalid signature file: {}", e)))
+}
+
+fn parse_public_key(key: &str) -> Result<VerifyingKey, Error> {
+    let bytes: [u8; 32] = BASE64
+        .decode(key)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| Error::Signature("Public key must be 32 bytes of base64".to_string()))?;
+
+    VerifyingKey::from_bytes(&bytes)
+        .map_err(|e| Error::Signature(format!("Invalid public key: {}", e)))
+}
@@ -0,0 +1,375 @@
+//! Local socket bridge for external programs
+//!
+//! When enabled, yaof listens on `127.0.0.1:<port>` for newline-delimited JSON so
+//! daemons and scripts can feed services without writing a native plugin:
+//!
+//! ```text
+//! {"token": "<token>"}
+//! {"service": "sensor", "data": {"temperature": 41.5}}
+//! ```
+//!
+//! A connection must present the bridge token before it can broadcast, either on
+//! its own line or as a `token` field of its first message. Every line is answered
+//! with `{"ok": true}` or `{"ok": false, "error": "..."}`. The bridge is off by
+//! default and its settings live in their own store.
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::{Arc, Mutex, RwLock};
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD as BASE64};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, async_runtime::JoinHandle};
+use tauri_plugin_store::StoreExt;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinSet;
+
+use crate::{OverlayState, error::Error};
+
+/// Store holding the bridge settings
+const BRIDGE_STORE: &str = "service-bridge.json";
+
+/// Port the bridge listens on unless configured otherwise
+pub const DEFAULT_BRIDGE_PORT: u16 = 47800;
+
+/// Longest line a client may send, longer lines close the connection
+const MAX_MESSAGE_BYTES: u64 = 1024 * 1024;
+
+/// Most clients connected at once, further connections are closed right away
+const MAX_CLIENTS: usize = 16;
+
+/// Bridge settings. The token is generated the first time the bridge is enabled.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceBridgeSettings {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: Option<String>,
+}
+
+impl Default for ServiceBridgeSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_BRIDGE_PORT,
+            token: None,
+        }
+    }
+}
+
+impl ServiceBridgeSettings {
+    /// Load the bridge settings from the store
+    pub fn load(app: &AppHandle) -> Result<Self, Error> {
+        let store = app
+            .store(BRIDGE_STORE)
+            .map_err(|e| Error::Bridge(e.to_string()))?;
+
+        let enabled = store
+            .get("enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let port = store
+            .get("port")
+            .and_then(|v| v.as_u64())
+            .and_then(|p| u16::try_from(p).ok())
+            .unwrap_or(DEFAULT_BRIDGE_PORT);
+        let token = store
+            .get("token")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .filter(|s| !s.is_empty());
+
+        Ok(Self {
+            enabled,
+            port,
+            token,
+        })
+    }
+
+    fn save(&self, app: &AppHandle) -> Result<(), Error> {
+        let store = app
+            .store(BRIDGE_STORE)
+            .map_err(|e| Error::Bridge(e.to_string()))?;
+
+        store.set("enabled", self.enabled);
+        store.set("port", self.port);
+        store.set("token", serde_json::json!(self.token));
+        store.save().map_err(|e| Error::Bridge(e.to_string()))
+    }
+}
+
+/// Status reported to the settings UI
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceBridgeStatus {
+    #[serde(flatten)]
+    pub settings: ServiceBridgeSettings,
+    pub running: bool,
+}
+
+/// State wrapper for the running bridge server
+#[derive(Default)]
+pub struct ServiceBridgeState(Mutex<Option<BridgeServer>>);
+
+struct BridgeServer {
+    port: u16,
+    /// Shared with the accept loop so a new token applies without rebinding
+    token: Arc<RwLock<String>>,
+    /// Connected clients, aborted when the bridge stops or its token changes
+    clients: Arc<Mutex<JoinSet<()>>>,
+    task: JoinHandle<()>,
+}
+
+impl BridgeServer {
+    fn disconnect_clients(&self) {
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.abort_all();
+        }
+    }
+}
+
+/// A line sent by a bridge client
+#[derive(Deserialize)]
+struct BridgeMessage {
+    #[serde(default)]
+    token: Option<String>,
+    #[serde(default)]
+    service: Option<String>,
+    #[serde(default)]
+    data: serde_json::Value,
+}
+
+/// Enable or disable the bridge (and optionally move it to another port), then
+/// restart it with the new settings
+pub fn configure(app: &AppHandle, enabled: bool, port: Option<u16>) -> Result<(), Error> {
+    let mut settings = ServiceBridgeSettings::load(app)?;
+    settings.enabled = enabled;
+    if let Some(port) = port {
+        settings.port = port;
+    }
+    if settings.enabled && settings.token.is_none() {
+        settings.token = Some(generate_token()?);
+    }
+    settings.save(app)?;
+
+    restart(app)
+}
+
+/// Replace the bridge token. Connected clients are dropped and have to
+/// authenticate again with the new one.
+pub fn reset_token(app: &AppHandle) -> Result<String, Error> {
+    let mut settings = ServiceBridgeSettings::load(app)?;
+    let token = generate_token()?;
+    settings.token = Some(token.clone());
+    settings.save(app)?;
+
+    restart(app)?;
+    Ok(token)
+}
+
+/// Get the bridge settings and whether the server is listening
+pub fn status(app: &AppHandle) -> Result<ServiceBridgeStatus, Error> {
+    let settings = ServiceBridgeSettings::load(app)?;
+    let running = app
+        .try_state::<ServiceBridgeState>()
+        .and_then(|state| state.0.lock().ok().map(|server| server.is_some()))
+        .unwrap_or(false);
+
+    Ok(ServiceBridgeStatus { settings, running })
+}
+
+/// Apply the stored settings: stop the bridge, start it, or move it to a new port.
+/// A running bridge whose port didn't change keeps its socket and only picks up the token,
+/// disconnecting its clients if the token changed.
+pub fn restart(app: &AppHandle) -> Result<(), Error> {
+    let state = app
+        .try_state::<ServiceBridgeState>()
+        .ok_or_else(|| Error::Bridge("Service bridge state is not managed".to_string()))?;
+    let mut server = state.0.lock().map_err(|e| Error::Bridge(e.to_string()))?;
+
+    let settings = ServiceBridgeSettings::load(app)?;
+    let token = settings.token.filter(|_| settings.enabled);
+
+    if let (Some(running), Some(token)) = (server.as_ref(), &token)
+        && running.port == settings.port
+    {
+        let changed = match running.token.write() {
+            Ok(mut current) if *current != *token => {
+                *current = token.clone();
+                true
+            }
+            _ => false,
+        };
+        if changed {
+            running.disconnect_clients();
+        }
+        return Ok(());
+    }
+
+    if let Some(running) = server.take() {
+        running.task.abort();
+        running.disconnect_clients();
+        tracing::info!("Service bridge stopped");
+    }
+
+    let Some(token) = token else {
+        return Ok(());
+    };
+
+    // Bind here so a taken port is reported to the caller instead of only logged
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, settings.port));
+    let listener = std::net::TcpListener::bind(addr)?;
+    listener.set_nonblocking(true)?;
+    tracing::info!("Service bridge listening on {}", addr);
+
+    let token = Arc::new(RwLock::new(token));
+    let clients = Arc::new(Mutex::new(JoinSet::new()));
+    let task = {
+        let app = app.clone();
+        let token = token.clone();
+        let clients = clients.clone();
+        tauri::async_runtime::spawn(async move {
+            let listener = match TcpListener::from_std(listener) {
+                Ok(listener) => listener,
+                Err(e) => {
+                    tracing::error!("Failed to start service bridge: {}", e);
+                    return;
+                }
+            };
+
+            loop {
+                match listener.accept().await {
+                    Ok((stream, peer)) => {
+                        let Ok(mut clients) = clients.lock() else {
+                            continue;
+                        };
+                        // Forget clients that already disconnected before counting
+                        while clients.try_join_next().is_some() {}
+                        if clients.len() >= MAX_CLIENTS {
+                            tracing::warn!(
+                                "Service bridge refused {}: {} clients already connected",
+                                peer,
+                                MAX_CLIENTS
+                            );
+                            continue;
+                        }
+
+                        tracing::debug!("Service bridge client connected from {}", peer);
+                        let app = app.clone();
+                        let token = token.clone();
+                        clients.spawn(async move {
+                            if let Err(e) = handle_client(stream, &app, &token).await {
+                                tracing::debug!(
+                                    "Service bridge client {} disconnected: {}",
+                                    peer,
+                                    e
+                                );
+                            }
+                        });
+                    }
+                    Err(e) => tracing::warn!("Service bridge failed to accept a client: {}", e),
+                }
+            }
+        })
+    };
+
+    *server = Some(BridgeServer {
+        port: settings.port,
+        token,
+        clients,
+        task,
+    });
+    Ok(())
+}
+
+async fn handle_client(
+    stream: TcpStream,
+    app: &AppHandle,
+    token: &RwLock<String>,
+) -> std::io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    let mut authenticated = false;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        let read = (&mut reader)
+            .take(MAX_MESSAGE_BYTES)
+            .read_line(&mut line)
+            .await?;
+        if read == 0 {
+            return Ok(());
+        }
+        if !line.ends_with('\n') && read as u64 == MAX_MESSAGE_BYTES {
+            reply(&mut writer, Err("Message too long".to_string())).await?;
+            return Ok(());
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let message: BridgeMessage = match serde_json::from_str(&line) {
+            Ok(message) => message,
+            Err(e) => {
+                reply(&mut writer, Err(format!("Invalid message: {}", e))).await?;
+                continue;
+            }
+        };
+
+        if let Some(presented) = &message.token {
+            authenticated = token
+                .read()
+                .is_ok_and(|token| tokens_match(presented, &token));
+        }
+        if !authenticated {
+            // Unauthenticated clients get one answer and are dropped
+            reply(&mut writer, Err("Invalid or missing token".to_string())).await?;
+            return Ok(());
+        }
+
+        let result = match message.service {
+            Some(service) => broadcast(app, &service, message.data),
+            None => Ok(()),
+        };
+        reply(&mut writer, result).await?;
+    }
+}
+
+fn broadcast(app: &AppHandle, service_id: &str, data: serde_json::Value) -> Result<(), String> {
+    let state = app
+        .try_state::<OverlayState>()
+        .ok_or_else(|| "Overlay state is not managed".to_string())?;
+    let mut manager = state.0.lock().map_err(|e| e.to_string())?;
+    manager.registry_mut().broadcast(service_id, data, app)
+}
+
+async fn reply(
+    writer: &mut tokio::net::tcp::OwnedWriteHalf,
+    result: Result<(), String>,
+) -> std::io::Result<()> {
+    let response = match result {
+        Ok(()) => serde_json::json!({ "ok": true }),
+        Err(error) => serde_json::json!({ "ok": false, "error": error }),
+    };
+    writer.write_all(format!("{}\n", response).as_bytes()).await
+}
+
+/// Compare a presented token without stopping at the first differing byte,
+/// so response timing doesn't reveal how much of it was right
+fn tokens_match(presented: &str, token: &str) -> bool {
+    let (presented, token) = (presented.as_bytes(), token.as_bytes());
+    presented.len() == token.len()
+        && presented
+            .iter()
+            .zip(token)
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Generate a random 256-bit token from the OS random number generator
+fn generate_token() -> Result<String, Error> {
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes)
+        .map_err(|e| Error::Bridge(format!("Failed to generate a token: {}", e)))?;
+    Ok(BASE64.encode(bytes))
+}
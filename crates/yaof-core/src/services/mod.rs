@@ -4,14 +4,86 @@
 //! - ServiceRegistry for plugin-to-plugin communication
 //! - Built-in system services (CPU, network, window, desktop, media)
 //! - JSON Schema validation for service data
+//! - One-shot request/response calls to service providers
+//! - An opt-in local socket bridge for external programs
 
+pub mod bridge;
 pub mod system;
 
 use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use jsonschema::Validator;
 use serde::Serialize;
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio::sync::oneshot;
+
+/// Message type passed to a native provider's `handle_message` for service requests
+/// (must match yaof-plugin). The payload is a JSON [`ServiceRequest`].
+pub const SERVICE_REQUEST_MESSAGE_TYPE: &str = "__request";
+
+/// Event name a native provider emits to answer a service request (must match yaof-plugin).
+/// The payload is JSON: `{"requestId": "...", "result": ...}` or `{"requestId": "...", "error": "..."}`
+pub const SERVICE_RESPONSE_EVENT: &str = "__response";
+
+/// Prefix of the event announcing a new raw payload on a service:
+/// `yaof:service-binary:{service_id}` with `{"byteLength": n}`.
+/// Tauri events only carry JSON, so overlays fetch the bytes with `service_get_binary`.
+pub const SERVICE_BINARY_EVENT_PREFIX: &str = "yaof:service-binary";
+
+/// Event name a native plugin emits to broadcast several services at once (must match yaof-plugin).
+/// The payload is JSON: `[["<service id>", <data>], ...]`
+pub const SERVICE_BATCH_EVENT: &str = "__batch";
+
+/// Name of the event emitted with the service ID after a provider unregistered:
+/// `yaof:service:{service_id}:unregistered`. Subscribers won't get data anymore.
+pub fn service_unregistered_event(service_id: &str) -> String {
+    format!("yaof:service:{}:unregistered", service_id)
+}
+
+/// Name of the event emitted with the service ID after a frontend provider registered:
+/// `yaof:service:{service_id}:registered`, so subscribers can reconnect
+pub fn service_registered_event(service_id: &str) -> String {
+    format!("yaof:service:{}:registered", service_id)
+}
+
+/// Event emitted when a plugin registers a service another plugin already provides
+pub const SERVICE_CONFLICT_EVENT: &str = "yaof:service:conflict";
+
+/// Default cap on broadcasts per service per second (0 disables the limit)
+pub const DEFAULT_BROADCAST_RATE_LIMIT: u32 = 60;
+
+/// Window the broadcast rate limit is counted over
+const BROADCAST_RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Result of a service request, either the provider's reply or an error message
+pub type ServiceResponse = Result<serde_json::Value, String>;
+
+/// A request waiting for its provider's reply
+struct PendingRequest {
+    sender: oneshot::Sender<ServiceResponse>,
+    /// Plugin that provides the service, the only one allowed to answer
+    provider: String,
+}
+
+/// A one-shot request sent to a service provider
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceRequest {
+    pub request_id: String,
+    pub service_id: String,
+    pub method: String,
+    pub params: serde_json::Value,
+}
+
+impl ServiceRequest {
+    /// Event a frontend provider listens on for requests to this service
+    pub fn event_name(&self) -> String {
+        format!("yaof:service-request:{}", self.service_id)
+    }
+}
 
 /// Information about a service provider
 #[derive(Debug, Clone, Serialize)]
@@ -19,12 +91,29 @@ pub struct ProviderInfo {
     pub service_id: String,
     pub plugin_id: String,
     pub schema: serde_json::Value,
+    /// Window that registered the provider, None for native plugins
+    #[serde(skip)]
+    pub window_label: Option<String>,
+}
+
+/// Payload of [`SERVICE_CONFLICT_EVENT`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceConflict {
+    pub service_id: String,
+    /// Plugin currently providing the service
+    pub existing_plugin_id: String,
+    /// Plugin that tried to register it
+    pub plugin_id: String,
+    /// Whether the registration was forced and replaced the existing provider
+    pub overridden: bool,
 }
 
 /// Compiled schema validator for a service
 pub struct ServiceValidator {
     pub service_id: String,
-    pub validator: Validator,
+    /// Shared with every other service registered with an identical schema
+    pub validator: Arc<Validator>,
 }
 
 impl ServiceValidator {
@@ -33,7 +122,7 @@ impl ServiceValidator {
         let validator = Validator::new(schema).map_err(|e| format!("Invalid schema: {}", e))?;
         Ok(Self {
             service_id,
-            validator,
+            validator: Arc::new(validator),
         })
     }
 
@@ -53,11 +142,43 @@ impl ServiceValidator {
     }
 }
 
+/// Broadcasts of one service in the current rate limit window
+struct BroadcastWindow {
+    started: Instant,
+    count: u32,
+    /// Latest value held back because the limit was hit, emitted when the window ends
+    pending: Option<serde_json::Value>,
+    coalesced: u32,
+}
+
+impl BroadcastWindow {
+    fn new(started: Instant) -> Self {
+        Self {
+            started,
+            count: 0,
+            pending: None,
+            coalesced: 0,
+        }
+    }
+}
+
 /// Registry for service providers and subscribers
 pub struct ServiceRegistry {
     providers: HashMap<String, ProviderInfo>,
     validators: HashMap<String, ServiceValidator>,
+    /// Compiled validators keyed by a hash of their schema, so services sharing a
+    /// schema (or re-registering on reload) don't recompile it
+    validator_cache: HashMap<u64, Arc<Validator>>,
     subscribers: HashMap<String, Vec<String>>, // service_id -> [window_labels]
+    /// Requests waiting for a provider's reply, keyed by request id
+    pending_requests: HashMap<String, PendingRequest>,
+    /// Last value broadcast on each service, replayed to late subscribers
+    last_values: HashMap<String, serde_json::Value>,
+    /// Latest raw payload per service, fetched by the frontend after a binary event
+    binary_values: HashMap<String, Arc<Vec<u8>>>,
+    broadcast_windows: HashMap<String, BroadcastWindow>,
+    /// Max broadcasts per service per second, excess ones are coalesced (0 disables)
+    pub broadcast_rate_limit: u32,
     /// Whether to validate service data against schemas (can be disabled for performance)
     pub validate_data: bool,
 }
@@ -67,29 +188,77 @@ impl ServiceRegistry {
         Self {
             providers: HashMap::new(),
             validators: HashMap::new(),
+            validator_cache: HashMap::new(),
             subscribers: HashMap::new(),
+            pending_requests: HashMap::new(),
+            last_values: HashMap::new(),
+            binary_values: HashMap::new(),
+            broadcast_windows: HashMap::new(),
+            broadcast_rate_limit: DEFAULT_BROADCAST_RATE_LIMIT,
             validate_data: true, // Enable validation by default
         }
     }
 
-    /// Register a service provider with optional schema validation
+    /// Check whether registering `service_id` for `plugin_id` would replace another plugin's provider.
+    /// Placeholders auto-registered by `subscribe` never conflict.
+    pub fn provider_conflict(
+        &self,
+        service_id: &str,
+        plugin_id: &str,
+        force: bool,
+    ) -> Option<ServiceConflict> {
+        let existing = self.providers.get(service_id)?;
+        if existing.plugin_id == plugin_id || existing.plugin_id.starts_with("native:") {
+            return None;
+        }
+
+        Some(ServiceConflict {
+            service_id: service_id.to_string(),
+            existing_plugin_id: existing.plugin_id.clone(),
+            plugin_id: plugin_id.to_string(),
+            overridden: force,
+        })
+    }
+
+    /// Register a service provider with optional schema validation.
+    /// Fails if another plugin already provides the service, unless `force` is set.
     pub fn register_provider(
         &mut self,
         service_id: String,
         plugin_id: String,
         schema: serde_json::Value,
+        window_label: Option<String>,
+        force: bool,
     ) -> Result<(), String> {
+        if let Some(conflict) = self.provider_conflict(&service_id, &plugin_id, force) {
+            if !force {
+                return Err(format!(
+                    "Service {} is already provided by plugin {}",
+                    service_id, conflict.existing_plugin_id
+                ));
+            }
+            tracing::warn!(
+                "Plugin {} is overriding service {} provided by plugin {}",
+                plugin_id,
+                service_id,
+                conflict.existing_plugin_id
+            );
+        }
+
         // Try to compile the schema validator if schema is not empty
         if !schema.is_null() && schema != serde_json::json!({}) {
-            match ServiceValidator::new(service_id.clone(), &schema) {
+            match self.compile_schema(&schema) {
                 Ok(validator) => {
-                    self.validators.insert(service_id.clone(), validator);
+                    self.validators.insert(
+                        service_id.clone(),
+                        ServiceValidator {
+                            service_id: service_id.clone(),
+                            validator,
+                        },
+                    );
                 }
                 Err(e) => {
-                    eprintln!(
-                        "[YAOF] Warning: Failed to compile schema for service {}: {}",
-                        service_id, e
-                    );
+                    tracing::warn!("Failed to compile schema for service {}: {}", service_id, e);
                     // Continue without validation - don't fail registration
                 }
             }
@@ -101,16 +270,123 @@ impl ServiceRegistry {
                 service_id,
                 plugin_id,
                 schema,
+                window_label,
             },
         );
 
         Ok(())
     }
 
-    /// Unregister a service provider
-    pub fn unregister_provider(&mut self, service_id: &str) {
-        self.providers.remove(service_id);
+    /// Record a native plugin as the provider of a service it broadcasts, so unloading
+    /// the plugin unregisters it. Fails if another plugin already provides the service.
+    pub fn claim_native_service(
+        &mut self,
+        service_id: &str,
+        plugin_id: &str,
+    ) -> Result<(), ServiceConflict> {
+        if let Some(conflict) = self.provider_conflict(service_id, plugin_id, false) {
+            return Err(conflict);
+        }
+
+        match self.providers.get_mut(service_id) {
+            // Auto-registered by `subscribe` before the plugin first broadcast
+            Some(provider) if provider.plugin_id.strip_prefix("native:") == Some(service_id) => {
+                provider.plugin_id = plugin_id.to_string();
+            }
+            Some(_) => {}
+            None => {
+                self.providers.insert(
+                    service_id.to_string(),
+                    ProviderInfo {
+                        service_id: service_id.to_string(),
+                        plugin_id: plugin_id.to_string(),
+                        schema: serde_json::json!({}),
+                        window_label: None,
+                    },
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get a compiled validator for a schema, reusing a cached one if the schema was seen before
+    fn compile_schema(&mut self, schema: &serde_json::Value) -> Result<Arc<Validator>, String> {
+        // serde_json objects serialize with sorted keys, so equal schemas hash equally
+        let mut hasher = DefaultHasher::new();
+        schema.to_string().hash(&mut hasher);
+        let hash = hasher.finish();
+
+        if let Some(validator) = self.validator_cache.get(&hash) {
+            return Ok(validator.clone());
+        }
+
+        let validator =
+            Arc::new(Validator::new(schema).map_err(|e| format!("Invalid schema: {}", e))?);
+        self.validator_cache.insert(hash, validator.clone());
+        Ok(validator)
+    }
+
+    /// Drop cached validators that no registered service uses anymore
+    /// (e.g. after a plugin is uninstalled)
+    pub fn clear_validator_cache(&mut self) {
+        self.validator_cache
+            .retain(|_, validator| Arc::strong_count(validator) > 1);
+    }
+
+    /// Unregister every service provided by a plugin
+    pub fn unregister_plugin(&mut self, plugin_id: &str, app: &AppHandle) {
+        let services: Vec<String> = self
+            .providers
+            .values()
+            .filter(|p| p.plugin_id == plugin_id)
+            .map(|p| p.service_id.clone())
+            .collect();
+
+        for service_id in services {
+            self.unregister_provider(&service_id, app);
+        }
+    }
+
+    /// Unregister the services a native plugin broadcast, once it is unloaded
+    pub fn unregister_native_plugin(&mut self, plugin_id: &str, app: &AppHandle) {
+        let services: Vec<String> = self
+            .providers
+            .values()
+            .filter(|p| p.plugin_id == plugin_id && p.window_label.is_none())
+            .map(|p| p.service_id.clone())
+            .collect();
+
+        for service_id in services {
+            self.unregister_provider(&service_id, app);
+        }
+    }
+
+    /// Unregister the services a window provided, once it is destroyed or reloaded
+    pub fn unregister_window<R: Runtime>(&mut self, window_label: &str, app: &AppHandle<R>) {
+        let services: Vec<String> = self
+            .providers
+            .values()
+            .filter(|p| p.window_label.as_deref() == Some(window_label))
+            .map(|p| p.service_id.clone())
+            .collect();
+
+        for service_id in services {
+            self.unregister_provider(&service_id, app);
+        }
+    }
+
+    /// Unregister a service provider, dropping its cached values, and tell
+    /// subscribers the data source is gone
+    pub fn unregister_provider<R: Runtime>(&mut self, service_id: &str, app: &AppHandle<R>) {
+        if self.providers.remove(service_id).is_some() {
+            let _ = app.emit(&service_unregistered_event(service_id), service_id);
+        }
+        self.validators.remove(service_id);
         self.subscribers.remove(service_id);
+        self.last_values.remove(service_id);
+        self.binary_values.remove(service_id);
+        self.broadcast_windows.remove(service_id);
     }
 
     /// List all registered providers
@@ -118,6 +394,87 @@ impl ServiceRegistry {
         self.providers.values().cloned().collect()
     }
 
+    /// Get the labels of the windows subscribed to a service
+    pub fn subscribers(&self, service_id: &str) -> Vec<String> {
+        self.subscribers
+            .get(service_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Get every service's subscribed window labels
+    pub fn all_subscriptions(&self) -> HashMap<String, Vec<String>> {
+        self.subscribers.clone()
+    }
+
+    /// Get the provider of a service
+    pub fn provider(&self, service_id: &str) -> Option<&ProviderInfo> {
+        self.providers.get(service_id)
+    }
+
+    /// Create a request to a service's provider and a receiver for its reply.
+    /// Request ids are random so other windows can't guess and answer them.
+    pub fn begin_request(
+        &mut self,
+        service_id: &str,
+        method: String,
+        params: serde_json::Value,
+    ) -> Result<(ServiceRequest, oneshot::Receiver<ServiceResponse>), String> {
+        let provider = self
+            .providers
+            .get(service_id)
+            .map(|p| p.plugin_id.clone())
+            .ok_or_else(|| format!("No provider registered for service {}", service_id))?;
+
+        let mut bytes = [0u8; 16];
+        getrandom::fill(&mut bytes).map_err(|e| format!("Failed to create request id: {}", e))?;
+        let request_id: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+
+        let (sender, receiver) = oneshot::channel();
+        self.pending_requests
+            .insert(request_id.clone(), PendingRequest { sender, provider });
+
+        let request = ServiceRequest {
+            request_id,
+            service_id: service_id.to_string(),
+            method,
+            params,
+        };
+
+        Ok((request, receiver))
+    }
+
+    /// Deliver a provider's reply to a pending request.
+    /// Fails if the request is unknown (e.g. it already timed out) or `responder`
+    /// isn't the plugin the request was sent to.
+    pub fn resolve_request(
+        &mut self,
+        request_id: &str,
+        responder: &str,
+        response: ServiceResponse,
+    ) -> Result<(), String> {
+        let unknown = || format!("Unknown or expired request {}", request_id);
+
+        let pending = self.pending_requests.get(request_id).ok_or_else(unknown)?;
+        if pending.provider != responder {
+            return Err(format!(
+                "{} can't answer request {}, it was sent to {}",
+                responder, request_id, pending.provider
+            ));
+        }
+
+        let pending = self
+            .pending_requests
+            .remove(request_id)
+            .ok_or_else(unknown)?;
+        pending.sender.send(response).map_err(|_| unknown())
+    }
+
+    /// Drop a pending request without replying
+    pub fn cancel_request(&mut self, request_id: &str) {
+        self.pending_requests.remove(request_id);
+    }
+
     /// Subscribe a window to a service
     /// If the service is not registered, it will be auto-registered as a native plugin service
     pub fn subscribe(&mut self, service_id: &str, window_label: &str) -> Result<(), String> {
@@ -128,13 +485,16 @@ impl ServiceRegistry {
                 service_id.to_string(),
                 format!("native:{}", service_id), // Mark as native plugin
                 serde_json::json!({}),
+                None,
+                false,
             )?;
         }
 
-        self.subscribers
-            .entry(service_id.to_string())
-            .or_default()
-            .push(window_label.to_string());
+        // Resubscribing after the provider registered again doesn't add the window twice
+        let subscribers = self.subscribers.entry(service_id.to_string()).or_default();
+        if !subscribers.iter().any(|label| label == window_label) {
+            subscribers.push(window_label.to_string());
+        }
 
         Ok(())
     }
@@ -146,6 +506,14 @@ impl ServiceRegistry {
         }
     }
 
+    /// Unsubscribe a window from every service, e.g. once it has been destroyed
+    pub fn unsubscribe_window(&mut self, window_label: &str) {
+        for subs in self.subscribers.values_mut() {
+            subs.retain(|l| l != window_label);
+        }
+        self.subscribers.retain(|_, subs| !subs.is_empty());
+    }
+
     /// Validate data against a service's schema
     pub fn validate_service_data(
         &self,
@@ -164,54 +532,190 @@ impl ServiceRegistry {
         }
     }
 
+    /// Remember the latest value broadcast on a service
+    pub fn record_value(&mut self, service_id: &str, data: &serde_json::Value) {
+        self.last_values
+            .insert(service_id.to_string(), data.clone());
+    }
+
+    /// Get the last value broadcast on a service
+    pub fn last_value(&self, service_id: &str) -> Option<&serde_json::Value> {
+        self.last_values.get(service_id)
+    }
+
+    /// Remember the latest raw payload emitted on a service
+    pub fn record_binary(&mut self, service_id: &str, data: Vec<u8>) {
+        self.binary_values
+            .insert(service_id.to_string(), Arc::new(data));
+    }
+
+    /// Get the latest raw payload emitted on a service
+    pub fn binary_value(&self, service_id: &str) -> Option<Arc<Vec<u8>>> {
+        self.binary_values.get(service_id).cloned()
+    }
+
     /// Broadcast data to all subscribers of a service with optional validation
     pub fn broadcast(
-        &self,
+        &mut self,
         service_id: &str,
         data: serde_json::Value,
         app: &AppHandle,
     ) -> Result<(), String> {
         // Validate data against schema if validation is enabled
-        if self.validate_data {
-            if let Err(errors) = self.validate_service_data(service_id, &data) {
-                let error_msg = format!(
+        if self.validate_data
+            && let Err(errors) = self.validate_service_data(service_id, &data)
+        {
+            let error_msg = format!(
+                "Service {} data validation failed: {}",
+                service_id,
+                errors.join(", ")
+            );
+            tracing::warn!("{}", error_msg);
+            // Log but don't block - validation errors are warnings in production
+        }
+
+        self.emit_broadcast(service_id, data, app)
+    }
+
+    /// Broadcast several service updates in one call, in order. Every update is
+    /// validated strictly first, so an invalid one rejects the batch before anything
+    /// is emitted. Stops at the first update that fails to emit.
+    pub fn broadcast_batch(
+        &mut self,
+        updates: Vec<(String, serde_json::Value)>,
+        app: &AppHandle,
+    ) -> Result<(), String> {
+        for (service_id, data) in &updates {
+            self.check_service_data(service_id, data)?;
+        }
+
+        for (service_id, data) in updates {
+            self.emit_broadcast(&service_id, data, app)?;
+        }
+        Ok(())
+    }
+
+    /// Cache a broadcast value and emit it to all windows, subject to the rate limit
+    fn emit_broadcast(
+        &mut self,
+        service_id: &str,
+        data: serde_json::Value,
+        app: &AppHandle,
+    ) -> Result<(), String> {
+        self.record_value(service_id, &data);
+        if !self.admit_broadcast(service_id, &data, app) {
+            return Ok(());
+        }
+
+        let event_name = format!("yaof:service:{}", service_id);
+
+        // Emit to all windows (subscribers can filter on their end)
+        app.emit(&event_name, data).map_err(|e| e.to_string())
+    }
+
+    /// Validate data against the service's schema, as a broadcast error message
+    fn check_service_data(&self, service_id: &str, data: &serde_json::Value) -> Result<(), String> {
+        self.validate_service_data(service_id, data)
+            .map_err(|errors| {
+                format!(
                     "Service {} data validation failed: {}",
                     service_id,
                     errors.join(", ")
-                );
-                eprintln!("[YAOF] Warning: {}", error_msg);
-                // Log but don't block - validation errors are warnings in production
-            }
+                )
+            })
+    }
+
+    /// Count a broadcast against the service's rate limit. Over the limit the value is
+    /// held back instead, and the latest held-back value is emitted when the window ends.
+    fn admit_broadcast(
+        &mut self,
+        service_id: &str,
+        data: &serde_json::Value,
+        app: &AppHandle,
+    ) -> bool {
+        let limit = self.broadcast_rate_limit;
+        if limit == 0 {
+            return true;
         }
 
-        let event_name = format!("yaof:service:{}", service_id);
+        let now = Instant::now();
+        let window = self
+            .broadcast_windows
+            .entry(service_id.to_string())
+            .or_insert_with(|| BroadcastWindow::new(now));
 
-        // Emit to all windows (subscribers can filter on their end)
-        app.emit(&event_name, data).map_err(|e| e.to_string())?;
+        if now.duration_since(window.started) >= BROADCAST_RATE_WINDOW {
+            // A held-back value is superseded by this one
+            *window = BroadcastWindow::new(now);
+        }
 
-        Ok(())
+        if window.count < limit {
+            window.count += 1;
+            return true;
+        }
+
+        let schedule_flush = window.pending.is_none();
+        window.pending = Some(data.clone());
+        window.coalesced += 1;
+
+        if schedule_flush {
+            let delay = BROADCAST_RATE_WINDOW.saturating_sub(now.duration_since(window.started));
+            let started = window.started;
+            let app = app.clone();
+            let service_id = service_id.to_string();
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(delay).await;
+                if let Some(state) = app.try_state::<crate::OverlayState>()
+                    && let Ok(mut manager) = state.0.lock()
+                {
+                    manager
+                        .registry_mut()
+                        .flush_held_broadcast(&service_id, started, &app);
+                }
+            });
+        }
+
+        false
+    }
+
+    /// Emit the value held back by the rate limit in the window that started at
+    /// `started`, if it hasn't been superseded since. A later window schedules its own flush.
+    fn flush_held_broadcast(&mut self, service_id: &str, started: Instant, app: &AppHandle) {
+        let Some(window) = self.broadcast_windows.get_mut(service_id) else {
+            return;
+        };
+        if window.started != started {
+            return;
+        }
+        let Some(data) = window.pending.take() else {
+            return;
+        };
+
+        tracing::warn!(
+            "Service {} exceeded {} broadcasts/s, coalesced {} update(s)",
+            service_id,
+            self.broadcast_rate_limit,
+            window.coalesced
+        );
+
+        // The flushed value opens the next window
+        *window = BroadcastWindow::new(Instant::now());
+        window.count = 1;
+
+        let _ = app.emit(&format!("yaof:service:{}", service_id), data);
     }
 
     /// Broadcast data with strict validation - returns error if validation fails
     pub fn broadcast_validated(
-        &self,
+        &mut self,
         service_id: &str,
         data: serde_json::Value,
         app: &AppHandle,
     ) -> Result<(), String> {
         // Validate data against schema
-        if let Err(errors) = self.validate_service_data(service_id, &data) {
-            return Err(format!(
-                "Service {} data validation failed: {}",
-                service_id,
-                errors.join(", ")
-            ));
-        }
+        self.check_service_data(service_id, &data)?;
 
-        let event_name = format!("yaof:service:{}", service_id);
-        app.emit(&event_name, data).map_err(|e| e.to_string())?;
-
-        Ok(())
+        self.emit_broadcast(service_id, data, app)
     }
 
     /// Get the schema for a service
@@ -0,0 +1,38 @@
+//! Accessibility permission (macOS)
+//!
+//! The window and desktop services query `System Events`, which returns nothing
+//! without the Accessibility permission. These helpers let the UI detect that and
+//! send the user to the right System Settings pane. Other platforms need no
+//! permission and always report trusted.
+
+/// System Settings pane listing apps with Accessibility access
+#[cfg(target_os = "macos")]
+const ACCESSIBILITY_SETTINGS_URL: &str =
+    "x-apple.systempreferences:com.apple.preference.security?Privacy_Accessibility";
+
+/// Check whether yaof has been granted the Accessibility permission
+pub fn is_trusted() -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        #[link(name = "ApplicationServices", kind = "framework")]
+        unsafe extern "C" {
+            fn AXIsProcessTrusted() -> bool;
+        }
+
+        unsafe { AXIsProcessTrusted() }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    true
+}
+
+/// Open the Accessibility privacy pane so the user can grant the permission.
+/// Does nothing on platforms without the permission.
+pub fn open_settings() -> std::io::Result<()> {
+    #[cfg(target_os = "macos")]
+    std::process::Command::new("open")
+        .arg(ACCESSIBILITY_SETTINGS_URL)
+        .spawn()?;
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! Subprocess helper for system services
+//!
+//! Services shell out to tools like `osascript`, `playerctl` or `nmcli` on every
+//! tick. A wedged tool must not stall the tick loop (and with it every system
+//! event), so these commands are killed once they run past a deadline.
+
+use std::io::{self, Read};
+use std::process::{Command, Output, Stdio};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// How long a service subprocess may run before it is killed
+pub const COMMAND_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// How often a running subprocess is checked for completion
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+pub trait CommandExt {
+    /// Like `Command::output`, but kills the process and returns a `TimedOut`
+    /// error when it runs longer than `COMMAND_TIMEOUT`
+    fn output_with_timeout(&mut self) -> io::Result<Output>;
+}
+
+impl CommandExt for Command {
+    fn output_with_timeout(&mut self) -> io::Result<Output> {
+        let mut child = self
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // Drain the pipes on their own threads so a chatty process can't block on a full pipe
+        let stdout = child.stdout.take().map(read_to_end);
+        let stderr = child.stderr.take().map(read_to_end);
+
+        let deadline = Instant::now() + COMMAND_TIMEOUT;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                tracing::warn!(
+                    "{:?} did not finish within {:?} and was killed",
+                    self.get_program(),
+                    COMMAND_TIMEOUT
+                );
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("{:?} timed out", self.get_program()),
+                ));
+            }
+
+            std::thread::sleep(POLL_INTERVAL);
+        };
+
+        Ok(Output {
+            status,
+            stdout: join(stdout),
+            stderr: join(stderr),
+        })
+    }
+}
+
+fn read_to_end<R: Read + Send + 'static>(mut pipe: R) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+fn join(reader: Option<JoinHandle<Vec<u8>>>) -> Vec<u8> {
+    reader
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default()
+}
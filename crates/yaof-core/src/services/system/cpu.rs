@@ -2,7 +2,7 @@
 
 use sysinfo::System;
 
-use super::CpuStatus;
+use super::{CpuStatus, SystemService};
 
 /// Service for monitoring CPU usage
 pub struct CpuService {
@@ -40,3 +40,13 @@ impl Default for CpuService {
         Self::new()
     }
 }
+
+impl SystemService for CpuService {
+    fn name(&self) -> &'static str {
+        "cpu"
+    }
+
+    fn tick(&mut self) -> serde_json::Value {
+        serde_json::to_value(self.get_status()).unwrap_or_default()
+    }
+}
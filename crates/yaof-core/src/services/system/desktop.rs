@@ -6,7 +6,7 @@
 //! - Windows: Virtual desktops
 //! - Linux: X11 workspaces or Wayland equivalents
 
-use super::DesktopStatus;
+use super::{DesktopStatus, SystemService};
 
 /// Service for monitoring active desktop/workspace
 pub struct DesktopService {
@@ -47,6 +47,7 @@ impl DesktopService {
 
     #[cfg(target_os = "macos")]
     fn get_status_macos(&self) -> DesktopStatus {
+        use super::CommandExt;
         use std::process::Command;
 
         // macOS doesn't have a straightforward way to get the current Space number
@@ -62,7 +63,9 @@ impl DesktopService {
             end tell
         "#;
 
-        let output = Command::new("osascript").args(["-e", script]).output();
+        let output = Command::new("osascript")
+            .args(["-e", script])
+            .output_with_timeout();
 
         match output {
             Ok(out) if out.status.success() => {
@@ -76,7 +79,7 @@ impl DesktopService {
                 // Fallback: try to read from defaults
                 let _defaults_output = Command::new("defaults")
                     .args(["read", "com.apple.dock", "workspaces"])
-                    .output();
+                    .output_with_timeout();
 
                 // If we can't determine the space, return 1 as default
                 DesktopStatus {
@@ -89,6 +92,7 @@ impl DesktopService {
 
     #[cfg(target_os = "windows")]
     fn get_status_windows(&self) -> DesktopStatus {
+        use super::CommandExt;
         use std::process::Command;
 
         // Windows virtual desktops are tricky to access
@@ -111,7 +115,7 @@ impl DesktopService {
 
         let output = Command::new("powershell")
             .args(["-Command", script])
-            .output();
+            .output_with_timeout();
 
         match output {
             Ok(out) if out.status.success() => {
@@ -130,10 +134,13 @@ impl DesktopService {
 
     #[cfg(target_os = "linux")]
     fn get_status_linux(&self) -> DesktopStatus {
+        use super::CommandExt;
         use std::process::Command;
 
         // Try xdotool first (works with X11)
-        let output = Command::new("xdotool").args(["get-desktop"]).output();
+        let output = Command::new("xdotool")
+            .args(["get-desktop"])
+            .output_with_timeout();
 
         if let Ok(out) = output {
             if out.status.success() {
@@ -148,7 +155,7 @@ impl DesktopService {
         }
 
         // Try wmctrl as fallback
-        let output = Command::new("wmctrl").args(["-d"]).output();
+        let output = Command::new("wmctrl").args(["-d"]).output_with_timeout();
 
         if let Ok(out) = output {
             if out.status.success() {
@@ -168,7 +175,7 @@ impl DesktopService {
         // Try reading from X11 directly using xprop
         let output = Command::new("xprop")
             .args(["-root", "_NET_CURRENT_DESKTOP"])
-            .output();
+            .output_with_timeout();
 
         if let Ok(out) = output {
             if out.status.success() {
@@ -198,3 +205,13 @@ impl Default for DesktopService {
         Self::new()
     }
 }
+
+impl SystemService for DesktopService {
+    fn name(&self) -> &'static str {
+        "desktop"
+    }
+
+    fn tick(&mut self) -> serde_json::Value {
+        serde_json::to_value(self.get_status()).unwrap_or_default()
+    }
+}
@@ -0,0 +1,173 @@
+//! Idle/AFK detection service
+//!
+//! Reports how long the user has been inactive.
+//! This is platform-specific:
+//! - macOS: `CGEventSourceSecondsSinceLastEventType`
+//! - Windows: `GetLastInputInfo`
+//! - Linux: X11 screensaver extension (via `xprintidle`), falling back to
+//!   `org.freedesktop.ScreenSaver.GetSessionIdleTime`
+
+use super::{IdleStatus, SystemService};
+
+/// Default number of inactive seconds before the user is considered idle
+pub const DEFAULT_IDLE_THRESHOLD_SECS: u64 = 300;
+
+/// Service for monitoring user idle time
+pub struct IdleService {
+    threshold_secs: u64,
+    is_idle: bool,
+    /// Set when the idle state flipped during the last `get_status` call
+    transitioned: bool,
+}
+
+impl IdleService {
+    pub fn new() -> Self {
+        Self {
+            threshold_secs: DEFAULT_IDLE_THRESHOLD_SECS,
+            is_idle: false,
+            transitioned: false,
+        }
+    }
+
+    /// Get the idle threshold in seconds
+    pub fn threshold_secs(&self) -> u64 {
+        self.threshold_secs
+    }
+
+    /// Set the number of inactive seconds before the user is considered idle
+    pub fn set_threshold_secs(&mut self, threshold_secs: u64) {
+        self.threshold_secs = threshold_secs;
+    }
+
+    /// Get current idle status
+    pub fn get_status(&mut self) -> IdleStatus {
+        let idle_secs = Self::idle_secs();
+        let is_idle = idle_secs >= self.threshold_secs;
+
+        self.transitioned = is_idle != self.is_idle;
+        self.is_idle = is_idle;
+
+        IdleStatus { idle_secs, is_idle }
+    }
+
+    /// Whether the last `get_status` call crossed the idle threshold
+    pub fn transitioned(&self) -> bool {
+        self.transitioned
+    }
+
+    fn idle_secs() -> u64 {
+        #[cfg(target_os = "macos")]
+        return Self::idle_secs_macos();
+
+        #[cfg(target_os = "windows")]
+        return Self::idle_secs_windows();
+
+        #[cfg(target_os = "linux")]
+        return Self::idle_secs_linux();
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+        return 0;
+    }
+
+    #[cfg(target_os = "macos")]
+    fn idle_secs_macos() -> u64 {
+        #[link(name = "CoreGraphics", kind = "framework")]
+        unsafe extern "C" {
+            fn CGEventSourceSecondsSinceLastEventType(state_id: i32, event_type: u32) -> f64;
+        }
+
+        // kCGEventSourceStateCombinedSessionState / kCGAnyInputEventType
+        const COMBINED_SESSION_STATE: i32 = 0;
+        const ANY_INPUT_EVENT_TYPE: u32 = !0;
+
+        let secs = unsafe {
+            CGEventSourceSecondsSinceLastEventType(COMBINED_SESSION_STATE, ANY_INPUT_EVENT_TYPE)
+        };
+
+        if secs.is_finite() && secs > 0.0 {
+            secs as u64
+        } else {
+            0
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn idle_secs_windows() -> u64 {
+        use windows::Win32::System::SystemInformation::GetTickCount;
+        use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+        let mut info = LASTINPUTINFO {
+            cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+            dwTime: 0,
+        };
+
+        unsafe {
+            if !GetLastInputInfo(&mut info).as_bool() {
+                return 0;
+            }
+
+            // Both values wrap after ~49.7 days, wrapping_sub keeps the difference correct
+            let idle_ms = GetTickCount().wrapping_sub(info.dwTime);
+            (idle_ms / 1000) as u64
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn idle_secs_linux() -> u64 {
+        use super::CommandExt;
+        use std::process::Command;
+
+        // X11: xprintidle queries the MIT-SCREEN-SAVER extension and prints milliseconds
+        if let Ok(out) = Command::new("xprintidle").output_with_timeout()
+            && out.status.success()
+            && let Ok(ms) = String::from_utf8_lossy(&out.stdout).trim().parse::<u64>()
+        {
+            return ms / 1000;
+        }
+
+        // Wayland / no X11: ask the session's screensaver service (milliseconds)
+        let output = Command::new("gdbus")
+            .args([
+                "call",
+                "--session",
+                "--dest",
+                "org.freedesktop.ScreenSaver",
+                "--object-path",
+                "/org/freedesktop/ScreenSaver",
+                "--method",
+                "org.freedesktop.ScreenSaver.GetSessionIdleTime",
+            ])
+            .output_with_timeout();
+
+        match output {
+            Ok(out) if out.status.success() => {
+                // Output looks like "(uint32 12345,)"
+                let stdout = String::from_utf8_lossy(&out.stdout);
+                stdout
+                    .split(|c: char| !c.is_ascii_digit())
+                    .filter(|s| !s.is_empty())
+                    .nth(1)
+                    .and_then(|ms| ms.parse::<u64>().ok())
+                    .map(|ms| ms / 1000)
+                    .unwrap_or(0)
+            }
+            _ => 0,
+        }
+    }
+}
+
+impl Default for IdleService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemService for IdleService {
+    fn name(&self) -> &'static str {
+        "idle"
+    }
+
+    fn tick(&mut self) -> serde_json::Value {
+        serde_json::to_value(self.get_status()).unwrap_or_default()
+    }
+}
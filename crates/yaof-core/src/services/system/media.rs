@@ -8,12 +8,15 @@
 //! - Position/Progress
 //! - Playing state
 //! - Application name
+//! - Album artwork (fetched on a background thread, once per track)
 
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
 use souvlaki::{MediaControlEvent, MediaControls, PlatformConfig};
 
-use super::MediaStatus;
+use super::{MediaStatus, SystemService};
 
 /// Cached media state from souvlaki events
 #[derive(Debug, Clone, Default)]
@@ -27,6 +30,23 @@ struct MediaState {
     app_name: Option<String>,
 }
 
+/// How long to wait before fetching a track's artwork again after a failed fetch
+const ARTWORK_RETRY: Duration = Duration::from_secs(10);
+
+/// Artwork of the current track, filled in by a background fetch
+#[derive(Debug, Default)]
+struct ArtworkState {
+    /// Track the artwork belongs to
+    track: String,
+    artwork: Option<String>,
+    /// Set while a fetch for `track` is running
+    fetching: bool,
+    /// When the last fetch for `track` failed
+    failed_at: Option<Instant>,
+    /// Whether `artwork` changed since it was last reported
+    changed: bool,
+}
+
 /// Service for monitoring media playback using souvlaki
 pub struct MediaService {
     /// Cached media state (updated via callbacks)
@@ -34,6 +54,7 @@ pub struct MediaService {
     /// Media controls handle (kept alive for event receiving)
     #[allow(dead_code)]
     controls: Option<MediaControls>,
+    artwork: Arc<Mutex<ArtworkState>>,
 }
 
 impl MediaService {
@@ -43,7 +64,11 @@ impl MediaService {
         // Try to initialize souvlaki media controls
         let controls = Self::init_media_controls(state.clone());
 
-        Self { state, controls }
+        Self {
+            state,
+            controls,
+            artwork: Arc::new(Mutex::new(ArtworkState::default())),
+        }
     }
 
     fn init_media_controls(state: Arc<Mutex<MediaState>>) -> Option<MediaControls> {
@@ -106,8 +131,86 @@ impl MediaService {
         }
     }
 
-    /// Get current media status
-    pub fn get_status(&self) -> MediaStatus {
+    /// Get current media status. `artwork` is only set when it changed since the
+    /// last call, fetching it never blocks the caller.
+    pub fn get_status(&mut self) -> MediaStatus {
+        let mut status = self.read_status();
+
+        // Stamp the position so overlays can interpolate between polls
+        if status.position_ms.is_some() {
+            status.position_updated_at_ms = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|d| d.as_millis() as u64);
+        }
+
+        if status.title.is_none() {
+            return status;
+        }
+
+        // Artwork is only fetched again when the track changes
+        let track = format!(
+            "{}|{}|{}|{}",
+            status.app_name.as_deref().unwrap_or_default(),
+            status.title.as_deref().unwrap_or_default(),
+            status.artist.as_deref().unwrap_or_default(),
+            status.album.as_deref().unwrap_or_default()
+        );
+
+        let Ok(mut artwork) = self.artwork.lock() else {
+            return status;
+        };
+
+        if artwork.track != track {
+            let had_artwork = artwork.artwork.is_some();
+            *artwork = ArtworkState {
+                track: track.clone(),
+                changed: had_artwork,
+                ..Default::default()
+            };
+        }
+
+        let retry_due = artwork
+            .failed_at
+            .is_none_or(|failed_at| failed_at.elapsed() >= ARTWORK_RETRY);
+        if artwork.artwork.is_none() && !artwork.fetching && retry_due {
+            artwork.fetching = true;
+            let shared = self.artwork.clone();
+            let app_name = status.app_name.clone();
+            std::thread::spawn(move || {
+                let fetched = Self::fetch_artwork(app_name.as_deref());
+                let Ok(mut artwork) = shared.lock() else {
+                    return;
+                };
+                // The track changed while fetching, this artwork is stale
+                if artwork.track != track {
+                    return;
+                }
+
+                artwork.fetching = false;
+                match fetched {
+                    Some(fetched) => {
+                        artwork.artwork = Some(fetched);
+                        artwork.changed = true;
+                    }
+                    None => artwork.failed_at = Some(Instant::now()),
+                }
+            });
+        }
+
+        if std::mem::take(&mut artwork.changed) {
+            status.artwork = Some(artwork.artwork.clone());
+        }
+
+        status
+    }
+
+    /// Get the current track's artwork, if it has been fetched
+    pub fn artwork(&self) -> Option<String> {
+        self.artwork.lock().ok()?.artwork.clone()
+    }
+
+    fn read_status(&self) -> MediaStatus {
         // First try to get status from souvlaki state
         if let Ok(state) = self.state.lock() {
             if state.title.is_some() {
@@ -118,7 +221,9 @@ impl MediaService {
                     album: state.album.clone(),
                     duration_ms: state.duration_ms,
                     position_ms: state.position_ms,
+                    position_updated_at_ms: None,
                     app_name: state.app_name.clone(),
+                    artwork: None,
                 };
             }
         }
@@ -139,6 +244,7 @@ impl MediaService {
 
     #[cfg(target_os = "macos")]
     fn get_status_macos(&self) -> MediaStatus {
+        use super::CommandExt;
         use std::process::Command;
 
         // Use AppleScript to get now playing info from Music app or other media players
@@ -188,7 +294,9 @@ impl MediaService {
             return output
         "#;
 
-        let output = Command::new("osascript").args(["-e", script]).output();
+        let output = Command::new("osascript")
+            .args(["-e", script])
+            .output_with_timeout();
 
         match output {
             Ok(out) if out.status.success() => {
@@ -231,7 +339,9 @@ impl MediaService {
                         album,
                         duration_ms,
                         position_ms,
+                        position_updated_at_ms: None,
                         app_name,
+                        artwork: None,
                     }
                 } else {
                     MediaStatus::default()
@@ -243,6 +353,7 @@ impl MediaService {
 
     #[cfg(target_os = "windows")]
     fn get_status_windows(&self) -> MediaStatus {
+        use super::CommandExt;
         use std::process::Command;
 
         // Use PowerShell to access Windows Media Session
@@ -286,7 +397,7 @@ impl MediaService {
 
         let output = Command::new("powershell")
             .args(["-ExecutionPolicy", "Bypass", "-Command", script])
-            .output();
+            .output_with_timeout();
 
         match output {
             Ok(out) if out.status.success() => {
@@ -322,7 +433,9 @@ impl MediaService {
                         album,
                         duration_ms,
                         position_ms,
+                        position_updated_at_ms: None,
                         app_name,
+                        artwork: None,
                     }
                 } else {
                     MediaStatus::default()
@@ -334,6 +447,7 @@ impl MediaService {
 
     #[cfg(target_os = "linux")]
     fn get_status_linux(&self) -> MediaStatus {
+        use super::CommandExt;
         use std::process::Command;
 
         // Use playerctl to get MPRIS media info
@@ -343,7 +457,7 @@ impl MediaService {
                 "--format",
                 "{{status}}|{{title}}|{{artist}}|{{album}}|{{mpris:length}}|{{position}}|{{playerName}}",
             ])
-            .output();
+            .output_with_timeout();
 
         match output {
             Ok(out) if out.status.success() => {
@@ -380,7 +494,9 @@ impl MediaService {
                         album,
                         duration_ms,
                         position_ms,
+                        position_updated_at_ms: None,
                         app_name,
+                        artwork: None,
                     }
                 } else {
                     MediaStatus::default()
@@ -389,6 +505,159 @@ impl MediaService {
             _ => MediaStatus::default(),
         }
     }
+
+    /// Fetch the current track's artwork
+    fn fetch_artwork(app_name: Option<&str>) -> Option<String> {
+        #[cfg(target_os = "macos")]
+        return Self::fetch_artwork_macos(app_name);
+
+        #[cfg(target_os = "windows")]
+        return {
+            let _ = app_name;
+            Self::fetch_artwork_windows()
+        };
+
+        #[cfg(target_os = "linux")]
+        return {
+            let _ = app_name;
+            Self::fetch_artwork_linux()
+        };
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+        return {
+            let _ = app_name;
+            None
+        };
+    }
+
+    #[cfg(target_os = "macos")]
+    fn fetch_artwork_macos(app_name: Option<&str>) -> Option<String> {
+        use super::CommandExt;
+        use std::process::Command;
+
+        match app_name {
+            // Spotify exposes a CDN URL for the cover
+            Some("Spotify") => {
+                let out = Command::new("osascript")
+                    .args([
+                        "-e",
+                        r#"tell application "Spotify" to return artwork url of current track"#,
+                    ])
+                    .output_with_timeout()
+                    .ok()?;
+                let url = String::from_utf8_lossy(&out.stdout).trim().to_string();
+                (out.status.success() && url.starts_with("http")).then_some(url)
+            }
+            // Music only hands out the raw image data, write it to a temp file
+            Some("Music") => {
+                let file = tempfile::NamedTempFile::new().ok()?;
+                let path = file.path();
+                let script = format!(
+                    r#"
+                    tell application "Music"
+                        set artData to raw data of artwork 1 of current track
+                    end tell
+                    set artFile to open for access (POSIX file "{}") with write permission
+                    set eof artFile to 0
+                    write artData to artFile
+                    close access artFile
+                    "#,
+                    path.display()
+                );
+
+                let out = Command::new("osascript")
+                    .args(["-e", &script])
+                    .output_with_timeout()
+                    .ok()?;
+                if !out.status.success() {
+                    return None;
+                }
+
+                let bytes = std::fs::read(path).ok()?;
+                Some(image_data_url(&bytes))
+            }
+            _ => None,
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn fetch_artwork_windows() -> Option<String> {
+        use super::CommandExt;
+        use std::process::Command;
+
+        // Read the session's thumbnail stream and print it as base64
+        let script = r#"
+            Add-Type -AssemblyName System.Runtime.WindowsRuntime
+
+            $asTaskGeneric = ([System.WindowsRuntimeSystemExtensions].GetMethods() | Where-Object { $_.Name -eq 'AsTask' -and $_.GetParameters().Count -eq 1 -and $_.GetParameters()[0].ParameterType.Name -eq 'IAsyncOperation`1' })[0]
+
+            Function Await($WinRtTask, $ResultType) {
+                $asTask = $asTaskGeneric.MakeGenericMethod($ResultType)
+                $netTask = $asTask.Invoke($null, @($WinRtTask))
+                $netTask.Wait(-1) | Out-Null
+                $netTask.Result
+            }
+
+            try {
+                [Windows.Media.Control.GlobalSystemMediaTransportControlsSessionManager, Windows.Media.Control, ContentType = WindowsRuntime] | Out-Null
+                [Windows.Storage.Streams.IRandomAccessStreamWithContentType, Windows.Storage.Streams, ContentType = WindowsRuntime] | Out-Null
+
+                $sessionManager = Await ([Windows.Media.Control.GlobalSystemMediaTransportControlsSessionManager]::RequestAsync()) ([Windows.Media.Control.GlobalSystemMediaTransportControlsSessionManager])
+                $session = $sessionManager.GetCurrentSession()
+
+                if ($session) {
+                    $mediaProperties = Await ($session.TryGetMediaPropertiesAsync()) ([Windows.Media.Control.GlobalSystemMediaTransportControlsSessionMediaProperties])
+                    if ($mediaProperties.Thumbnail) {
+                        $stream = Await ($mediaProperties.Thumbnail.OpenReadAsync()) ([Windows.Storage.Streams.IRandomAccessStreamWithContentType])
+                        $netStream = [System.IO.WindowsRuntimeStreamExtensions]::AsStreamForRead($stream)
+                        $memory = New-Object System.IO.MemoryStream
+                        $netStream.CopyTo($memory)
+                        Write-Output ([Convert]::ToBase64String($memory.ToArray()))
+                    }
+                }
+            } catch {
+                # Silently fail
+            }
+        "#;
+
+        let out = Command::new("powershell")
+            .args(["-ExecutionPolicy", "Bypass", "-Command", script])
+            .output_with_timeout()
+            .ok()?;
+        if !out.status.success() {
+            return None;
+        }
+
+        let encoded = String::from_utf8_lossy(&out.stdout);
+        let bytes = BASE64.decode(encoded.trim()).ok()?;
+        (!bytes.is_empty()).then(|| image_data_url(&bytes))
+    }
+
+    #[cfg(target_os = "linux")]
+    fn fetch_artwork_linux() -> Option<String> {
+        use super::CommandExt;
+        use std::process::Command;
+
+        let out = Command::new("playerctl")
+            .args(["metadata", "mpris:artUrl"])
+            .output_with_timeout()
+            .ok()?;
+        if !out.status.success() {
+            return None;
+        }
+
+        let url = String::from_utf8_lossy(&out.stdout).trim().to_string();
+
+        // Local files aren't reachable from the webview, inline them
+        if let Some(path) = url.strip_prefix("file://") {
+            let bytes = std::fs::read(percent_decode(path)?).ok()?;
+            Some(image_data_url(&bytes))
+        } else if url.starts_with("http://") || url.starts_with("https://") {
+            Some(url)
+        } else {
+            None
+        }
+    }
 }
 
 impl Default for MediaService {
@@ -396,3 +665,53 @@ impl Default for MediaService {
         Self::new()
     }
 }
+
+impl SystemService for MediaService {
+    fn name(&self) -> &'static str {
+        "media"
+    }
+
+    fn tick(&mut self) -> serde_json::Value {
+        serde_json::to_value(self.get_status()).unwrap_or_default()
+    }
+}
+
+/// Decode `%XX` escapes in a `file://` URL path
+#[cfg(target_os = "linux")]
+fn percent_decode(path: &str) -> Option<String> {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+/// Encode image bytes as a data URL, sniffing the format from its header
+#[cfg_attr(
+    not(any(target_os = "macos", target_os = "windows", target_os = "linux")),
+    allow(dead_code)
+)]
+fn image_data_url(bytes: &[u8]) -> String {
+    let mime = if bytes.starts_with(b"\x89PNG") {
+        "image/png"
+    } else if bytes.starts_with(b"GIF8") {
+        "image/gif"
+    } else if bytes.len() > 12 && &bytes[8..12] == b"WEBP" {
+        "image/webp"
+    } else {
+        "image/jpeg"
+    };
+
+    format!("data:{};base64,{}", mime, BASE64.encode(bytes))
+}
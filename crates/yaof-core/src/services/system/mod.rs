@@ -6,27 +6,64 @@
 //! - Focused window
 //! - Active desktop
 //! - Now playing media
+//! - User idle time
+//! - Top processes by CPU or memory
+//! - OS notifications (opt-in)
+//! - Suspend/resume
+//! - Current time and timezone
 
+pub mod accessibility;
+mod command;
 mod cpu;
 mod desktop;
+mod idle;
 mod media;
 mod network;
+mod notification;
+mod power;
+mod process;
+mod time;
 mod window;
 
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use serde::Serialize;
-use tauri::{AppHandle, Emitter};
-use tokio::sync::RwLock;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::{RwLock, watch};
 use tokio::time::interval;
 
+use command::CommandExt;
 pub use cpu::CpuService;
 pub use desktop::DesktopService;
+pub use idle::{DEFAULT_IDLE_THRESHOLD_SECS, IdleService};
 pub use media::MediaService;
-pub use network::NetworkService;
+pub use network::{NetworkService, get_interfaces};
+pub use notification::NotificationService;
+pub use power::PowerService;
+pub use process::{
+    DEFAULT_PROCESS_LIMIT, DEFAULT_PROCESS_REFRESH_MS, ProcessService, ProcessSort, focus_process,
+    kill_process,
+};
+pub use time::TimeService;
 pub use window::WindowService;
 
+/// Polling interval of services that don't declare their own
+pub const DEFAULT_SERVICE_INTERVAL_MS: u64 = 1000;
+
+/// Ticks may fire slightly early, don't skip a service over a few milliseconds
+const SERVICE_INTERVAL_SLACK: Duration = Duration::from_millis(50);
+
+/// Shortest tick interval the system services loop can be set to
+pub const MIN_TICK_INTERVAL_MS: u64 = 100;
+
+/// Longest tick interval the system services loop can be set to
+pub const MAX_TICK_INTERVAL_MS: u64 = 60_000;
+
+/// Message type broadcast to native plugins when the machine wakes from sleep
+pub const RESUME_MESSAGE_TYPE: &str = "__resume";
+
 /// Trait for system services that emit periodic updates
 pub trait SystemService: Send + Sync {
     /// The name of this service (used in event names)
@@ -34,16 +71,42 @@ pub trait SystemService: Send + Sync {
 
     /// Called periodically to collect and return current data
     fn tick(&mut self) -> serde_json::Value;
+
+    /// How often the service wants to be polled, in milliseconds.
+    /// It is never polled more often than the tick loop runs.
+    fn default_interval_ms(&self) -> u64 {
+        DEFAULT_SERVICE_INTERVAL_MS
+    }
+}
+
+/// Poll a service through `poll` if its interval elapsed since it was last polled
+fn poll_due<S: SystemService, T>(
+    last_polled: &mut HashMap<&'static str, Instant>,
+    now: Instant,
+    service: &mut S,
+    poll: impl FnOnce(&mut S) -> T,
+) -> Option<T> {
+    let interval = Duration::from_millis(service.default_interval_ms());
+    let due = last_polled
+        .get(service.name())
+        .is_none_or(|last| now.duration_since(*last) + SERVICE_INTERVAL_SLACK >= interval);
+    if !due {
+        return None;
+    }
+
+    last_polled.insert(service.name(), now);
+    Some(poll(service))
 }
 
 /// Combined system status emitted as a single event
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Default)]
 pub struct SystemStatus {
     pub cpu: CpuStatus,
     pub network: NetworkStatus,
     pub window: WindowStatus,
     pub desktop: DesktopStatus,
     pub media: MediaStatus,
+    pub idle: IdleStatus,
 }
 
 #[derive(Debug, Clone, Serialize, Default)]
@@ -68,11 +131,28 @@ impl Default for NetworkStatus {
     }
 }
 
+/// A network interface, returned by `network_get_interfaces`
+#[derive(Debug, Clone, Serialize)]
+pub struct InterfaceInfo {
+    pub name: String,
+    /// "wifi", "ethernet" or "loopback"
+    pub interface_type: String,
+    pub mac_address: Option<String>,
+    /// Addresses with their prefix length, e.g. "192.168.1.20/24"
+    pub addresses: Vec<String>,
+    /// Default gateway, when the default route goes through this interface
+    pub gateway: Option<String>,
+    /// Network name of a connected WiFi interface
+    pub ssid: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Default)]
 pub struct WindowStatus {
     pub title: Option<String>,
     pub app_name: Option<String>,
     pub process_id: Option<u32>,
+    /// Whether the focused app is fullscreen
+    pub is_fullscreen: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Default)]
@@ -89,7 +169,73 @@ pub struct MediaStatus {
     pub album: Option<String>,
     pub duration_ms: Option<u64>,
     pub position_ms: Option<u64>,
+    /// When `position_ms` was read, in milliseconds since the Unix epoch. While
+    /// `playing`, the current position is `position_ms + (now - position_updated_at_ms)`.
+    pub position_updated_at_ms: Option<u64>,
     pub app_name: Option<String>,
+    /// Album art as a data URL, or an http(s) URL when the player provides one.
+    /// Only present in the status where it changed (`null` when the new track has
+    /// none), use `system_media_artwork` for the current value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artwork: Option<Option<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct IdleStatus {
+    /// Seconds since the last keyboard/mouse input
+    pub idle_secs: u64,
+    /// Whether `idle_secs` has reached the configured threshold
+    pub is_idle: bool,
+}
+
+/// A running process, emitted in `yaof:system:processes`
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub name: String,
+    /// Percent of a single core, so it can exceed 100 on multi-core machines
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+}
+
+/// Current time, emitted in `yaof:system:time`
+#[derive(Debug, Clone, Serialize)]
+pub struct TimeStatus {
+    /// Milliseconds since the Unix epoch
+    pub epoch_ms: i64,
+    /// IANA timezone name (e.g. "Europe/Berlin"), if it can be determined
+    pub timezone: Option<String>,
+    /// Offset of local time from UTC in seconds, including DST
+    pub offset_secs: i32,
+}
+
+/// A single OS notification, emitted as its own event when it arrives
+#[derive(Debug, Clone, Serialize)]
+pub struct NotificationStatus {
+    pub app: String,
+    pub title: String,
+    pub body: String,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+/// Whether the machine is going to sleep or just woke up, or its displays
+/// turned off or back on while it keeps running
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerState {
+    Suspend,
+    Resume,
+    DisplaySleep,
+    DisplayWake,
+}
+
+/// A suspend/resume transition, emitted in `yaof:system:power`
+#[derive(Debug, Clone, Serialize)]
+pub struct PowerEvent {
+    pub state: PowerState,
+    /// How long the machine (or its displays) was asleep, on resume/wake when known
+    pub slept_ms: Option<u64>,
 }
 
 /// Manager for all system services
@@ -99,6 +245,16 @@ pub struct SystemServiceManager {
     window_service: WindowService,
     desktop_service: DesktopService,
     media_service: MediaService,
+    notification_service: NotificationService,
+    idle_service: IdleService,
+    process_service: ProcessService,
+    power_service: PowerService,
+    time_service: TimeService,
+    /// When each service was last polled, by service name
+    last_polled: HashMap<&'static str, Instant>,
+    /// Latest status, services not due this tick keep their previous value
+    status: SystemStatus,
+    idle_transitioned: bool,
 }
 
 impl SystemServiceManager {
@@ -109,18 +265,123 @@ impl SystemServiceManager {
             window_service: WindowService::new(),
             desktop_service: DesktopService::new(),
             media_service: MediaService::new(),
+            notification_service: NotificationService::new(),
+            idle_service: IdleService::new(),
+            process_service: ProcessService::new(),
+            power_service: PowerService::new(),
+            time_service: TimeService::new(),
+            last_polled: HashMap::new(),
+            status: SystemStatus::default(),
+            idle_transitioned: false,
         }
     }
 
-    /// Collect status from all services
+    /// Collect status from all services, polling each one at its own interval
     pub fn collect_status(&mut self) -> SystemStatus {
-        SystemStatus {
-            cpu: self.cpu_service.get_status(),
-            network: self.network_service.get_status(),
-            window: self.window_service.get_status(),
-            desktop: self.desktop_service.get_status(),
-            media: self.media_service.get_status(),
+        let now = Instant::now();
+        let polled = &mut self.last_polled;
+        let status = &mut self.status;
+
+        if let Some(cpu) = poll_due(polled, now, &mut self.cpu_service, |s| s.get_status()) {
+            status.cpu = cpu;
+        }
+        if let Some(network) = poll_due(polled, now, &mut self.network_service, |s| s.get_status())
+        {
+            status.network = network;
+        }
+        if let Some(window) = poll_due(polled, now, &mut self.window_service, |s| s.get_status()) {
+            status.window = window;
+        }
+        if let Some(desktop) = poll_due(polled, now, &mut self.desktop_service, |s| s.get_status())
+        {
+            status.desktop = desktop;
+        }
+        if let Some(media) = poll_due(polled, now, &mut self.media_service, |s| s.get_status()) {
+            status.media = media;
+        }
+
+        // A transition is only reported on the tick that polled it
+        self.idle_transitioned = false;
+        if let Some(idle) = poll_due(polled, now, &mut self.idle_service, |s| s.get_status()) {
+            status.idle = idle;
+            self.idle_transitioned = self.idle_service.transitioned();
+        }
+
+        // Artwork is only reported on the tick it changed, not repeated while media isn't due
+        let collected = self.status.clone();
+        self.status.media.artwork = None;
+        collected
+    }
+
+    /// Whether the last collected status crossed the idle threshold
+    pub fn idle_transitioned(&self) -> bool {
+        self.idle_transitioned
+    }
+
+    /// Get the idle threshold in seconds
+    pub fn idle_threshold_secs(&self) -> u64 {
+        self.idle_service.threshold_secs()
+    }
+
+    /// Set the number of inactive seconds before the user is considered idle
+    pub fn set_idle_threshold_secs(&mut self, threshold_secs: u64) {
+        self.idle_service.set_threshold_secs(threshold_secs);
+    }
+
+    /// Get the top processes if they are due for a refresh
+    pub fn poll_processes(&mut self) -> Option<Vec<ProcessInfo>> {
+        self.process_service.poll()
+    }
+
+    /// Configure the top processes service, None leaves a setting unchanged
+    pub fn configure_processes(
+        &mut self,
+        limit: Option<usize>,
+        sort_by: Option<ProcessSort>,
+        refresh_interval_ms: Option<u64>,
+    ) {
+        self.process_service
+            .configure(limit, sort_by, refresh_interval_ms);
+    }
+
+    /// Get the suspend/resume transitions since the last tick.
+    /// A resume makes the time service report right away.
+    pub fn poll_power(&mut self, tick_interval: Duration) -> Vec<PowerEvent> {
+        let events = self.power_service.poll(tick_interval);
+        if events.iter().any(|e| e.state == PowerState::Resume) {
+            self.last_polled.remove(self.time_service.name());
         }
+        events
+    }
+
+    /// Get the current time if it is due for a report
+    pub fn poll_time(&mut self) -> Option<TimeStatus> {
+        poll_due(
+            &mut self.last_polled,
+            Instant::now(),
+            &mut self.time_service,
+            |s| s.get_status(),
+        )
+    }
+
+    /// Get the current track's artwork, if it has been fetched
+    pub fn media_artwork(&self) -> Option<String> {
+        self.media_service.artwork()
+    }
+
+    /// Take all OS notifications received since the last call
+    pub fn take_notifications(&mut self) -> Vec<NotificationStatus> {
+        self.notification_service.drain()
+    }
+
+    /// Check if OS notification monitoring is enabled
+    pub fn notifications_enabled(&self) -> bool {
+        self.notification_service.is_enabled()
+    }
+
+    /// Enable or disable OS notification monitoring
+    pub fn set_notifications_enabled(&mut self, enabled: bool) {
+        self.notification_service.set_enabled(enabled);
     }
 }
 
@@ -133,32 +394,108 @@ impl Default for SystemServiceManager {
 /// Thread-safe handle for the system service manager
 pub struct SystemServiceHandle {
     inner: Arc<RwLock<SystemServiceManager>>,
+    /// Interval of the running tick loop (0 until the loop is started).
+    /// The loop watches it, so a change applies right away
+    tick_interval_ms: watch::Sender<u64>,
+    /// Set once the first system status has been collected and emitted
+    ready: watch::Sender<bool>,
 }
 
 impl SystemServiceHandle {
     pub fn new(manager: SystemServiceManager) -> Self {
         Self {
             inner: Arc::new(RwLock::new(manager)),
+            tick_interval_ms: watch::Sender::new(0),
+            ready: watch::Sender::new(false),
         }
     }
 
+    /// Wait until the tick loop has emitted the first system status
+    pub async fn wait_ready(&self) {
+        let mut ready = self.ready.subscribe();
+        let _ = ready.wait_for(|ready| *ready).await;
+    }
+
+    /// Check whether the first system status has been emitted
+    pub fn is_ready(&self) -> bool {
+        *self.ready.borrow()
+    }
+
     /// Start the tick loop that emits system status events
     pub fn start_tick_loop(self: Arc<Self>, app: AppHandle, tick_interval_ms: u64) {
+        if !accessibility::is_trusted() {
+            tracing::warn!(
+                "Accessibility permission not granted, window and desktop info will be empty"
+            );
+        }
+
+        self.tick_interval_ms.send_replace(tick_interval_ms);
+        let mut interval_changed = self.tick_interval_ms.subscribe();
         let handle = self.clone();
         tokio::spawn(async move {
+            let mut tick_interval_ms = tick_interval_ms;
             let mut interval = interval(Duration::from_millis(tick_interval_ms));
+            let mut was_fullscreen = false;
             loop {
-                interval.tick().await;
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    // Restart the schedule from now when set_tick_interval_ms changes it,
+                    // instead of waiting out a long current tick
+                    Ok(()) = interval_changed.changed() => {
+                        let configured = *interval_changed.borrow_and_update();
+                        if configured != tick_interval_ms {
+                            tick_interval_ms = configured;
+                            let period = Duration::from_millis(tick_interval_ms);
+                            let start = tokio::time::Instant::now() + period;
+                            interval = tokio::time::interval_at(start, period);
+                        }
+                        continue;
+                    }
+                }
+
+                // Collect status from all services. Power is polled first so a wake
+                // is reported alongside the fresh status collected right after it.
+                // Collection spawns subprocesses and waits on them, so it runs on the
+                // blocking pool instead of stalling the async runtime.
+                let inner = handle.inner.clone();
+                let collected = tokio::task::spawn_blocking(move || {
+                    let mut manager = inner.blocking_write();
+                    let power_events = manager.poll_power(Duration::from_millis(tick_interval_ms));
+                    let status = manager.collect_status();
+                    (
+                        power_events,
+                        status,
+                        manager.idle_transitioned(),
+                        manager.take_notifications(),
+                        manager.poll_processes(),
+                        manager.poll_time(),
+                    )
+                })
+                .await;
 
-                // Collect status from all services
-                let status = {
-                    let mut manager = handle.inner.write().await;
-                    manager.collect_status()
-                };
+                let (power_events, status, idle_transitioned, notifications, processes, time) =
+                    match collected {
+                        Ok(collected) => collected,
+                        Err(e) => {
+                            tracing::error!("Failed to collect system status: {}", e);
+                            // Don't hold startup up on a broken service
+                            handle.ready.send_replace(true);
+                            continue;
+                        }
+                    };
+
+                for event in &power_events {
+                    let _ = app.emit("yaof:system:power", event);
+
+                    if event.state == PowerState::Resume {
+                        tracing::info!("System resumed from sleep");
+                        notify_native_plugins_resumed(&app, event).await;
+                    }
+                }
 
                 // Emit the combined status event
                 if let Err(e) = app.emit("yaof:system:status", &status) {
-                    eprintln!("[YAOF] Failed to emit system status: {}", e);
+                    tracing::error!("Failed to emit system status: {}", e);
                 }
 
                 // Also emit individual service events for granular subscriptions
@@ -167,10 +504,51 @@ impl SystemServiceHandle {
                 let _ = app.emit("yaof:system:window", &status.window);
                 let _ = app.emit("yaof:system:desktop", &status.desktop);
                 let _ = app.emit("yaof:system:media", &status.media);
+                let _ = app.emit("yaof:system:idle", &status.idle);
+                handle.ready.send_replace(true);
+
+                // Hide desktop-only overlays while an app is fullscreen
+                if status.window.is_fullscreen != was_fullscreen {
+                    was_fullscreen = status.window.is_fullscreen;
+                    crate::overlay::sync_fullscreen_visibility(&app, was_fullscreen);
+                }
+
+                // Let overlays react when the user goes idle or comes back
+                if idle_transitioned {
+                    let _ = app.emit("yaof:system:idle-changed", &status.idle);
+                }
+
+                if let Some(time) = &time {
+                    let _ = app.emit("yaof:system:time", time);
+                }
+
+                // Processes refresh on their own, slower interval
+                if let Some(processes) = &processes {
+                    let _ = app.emit("yaof:system:processes", processes);
+                }
+
+                // Notifications are discrete events, emit each one as it arrives
+                for notification in &notifications {
+                    let _ = app.emit("yaof:system:notification", notification);
+                }
             }
         });
     }
 
+    /// Get the interval of the running tick loop in milliseconds
+    pub fn tick_interval_ms(&self) -> u64 {
+        *self.tick_interval_ms.borrow()
+    }
+
+    /// Change the interval of the running tick loop, clamped between
+    /// `MIN_TICK_INTERVAL_MS` and `MAX_TICK_INTERVAL_MS`.
+    /// The next tick is one new interval from now. Returns the interval actually used.
+    pub fn set_tick_interval_ms(&self, tick_interval_ms: u64) -> u64 {
+        let tick_interval_ms = tick_interval_ms.clamp(MIN_TICK_INTERVAL_MS, MAX_TICK_INTERVAL_MS);
+        self.tick_interval_ms.send_replace(tick_interval_ms);
+        tick_interval_ms
+    }
+
     /// Get read access to the manager
     pub async fn read(&self) -> tokio::sync::RwLockReadGuard<'_, SystemServiceManager> {
         self.inner.read().await
@@ -186,6 +564,22 @@ impl Clone for SystemServiceHandle {
     fn clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
+            tick_interval_ms: self.tick_interval_ms.clone(),
+            ready: self.ready.clone(),
         }
     }
 }
+
+/// Tell native plugins the machine woke up, so they can refresh stale state
+async fn notify_native_plugins_resumed(app: &AppHandle, event: &PowerEvent) {
+    let Some(native_state) = app.try_state::<crate::NativePluginState>() else {
+        return;
+    };
+
+    let payload = serde_json::json!({ "sleptMs": event.slept_ms }).to_string();
+    native_state
+        .0
+        .write()
+        .await
+        .broadcast_message(RESUME_MESSAGE_TYPE, payload.as_bytes());
+}
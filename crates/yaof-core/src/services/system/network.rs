@@ -4,8 +4,15 @@
 //! - Connection state (connected/disconnected)
 //! - Signal strength (for WiFi)
 //! - Connection type (wifi, ethernet, disconnected)
+//!
+//! Interface details (addresses, gateway, SSID) change rarely, so they are
+//! fetched on demand with [`get_interfaces`] instead of on every tick.
+
+use std::collections::HashMap;
+
+use sysinfo::Networks;
 
-use super::NetworkStatus;
+use super::{InterfaceInfo, NetworkStatus, SystemService};
 
 /// Service for monitoring network status
 pub struct NetworkService {
@@ -46,10 +53,11 @@ impl NetworkService {
 
     #[cfg(target_os = "macos")]
     fn get_status_macos(&self) -> NetworkStatus {
+        use super::CommandExt;
         use std::process::Command;
 
         // Check if we have an active network connection using scutil
-        let output = Command::new("scutil").args(["--nwi"]).output();
+        let output = Command::new("scutil").args(["--nwi"]).output_with_timeout();
 
         let connected = match &output {
             Ok(out) => {
@@ -67,7 +75,7 @@ impl NetworkService {
         // BSSID presence indicates WiFi connection
         let ipconfig_output = Command::new("ipconfig")
             .args(["getsummary", "en0"])
-            .output();
+            .output_with_timeout();
 
         let is_wifi = match &ipconfig_output {
             Ok(out) => {
@@ -97,12 +105,13 @@ impl NetworkService {
 
     #[cfg(target_os = "windows")]
     fn get_status_windows(&self) -> NetworkStatus {
+        use super::CommandExt;
         use std::process::Command;
 
         // Use netsh to check WiFi status
         let output = Command::new("netsh")
             .args(["wlan", "show", "interfaces"])
-            .output();
+            .output_with_timeout();
 
         match output {
             Ok(out) => {
@@ -128,7 +137,7 @@ impl NetworkService {
                     // Check for ethernet connection
                     let ping_result = Command::new("ping")
                         .args(["-n", "1", "-w", "1000", "8.8.8.8"])
-                        .output();
+                        .output_with_timeout();
 
                     if ping_result.map(|o| o.status.success()).unwrap_or(false) {
                         NetworkStatus {
@@ -147,12 +156,13 @@ impl NetworkService {
 
     #[cfg(target_os = "linux")]
     fn get_status_linux(&self) -> NetworkStatus {
+        use super::CommandExt;
         use std::process::Command;
 
         // Try nmcli first (NetworkManager)
         let output = Command::new("nmcli")
             .args(["-t", "-f", "TYPE,STATE,SIGNAL", "device"])
-            .output();
+            .output_with_timeout();
 
         match output {
             Ok(out) => {
@@ -220,8 +230,278 @@ impl NetworkService {
     }
 }
 
+/// Get details of every network interface: addresses, default gateway and WiFi SSID
+pub fn get_interfaces() -> Vec<InterfaceInfo> {
+    let networks = Networks::new_with_refreshed_list();
+    let gateway = default_gateway(&networks);
+    let names: Vec<String> = networks.list().keys().cloned().collect();
+    let ssids = wifi_ssids(&names);
+
+    let mut interfaces: Vec<InterfaceInfo> = networks
+        .list()
+        .iter()
+        .map(|(name, data)| {
+            let ip_networks = data.ip_networks();
+            // lo0 on macOS also has a link-local fe80::1, so one loopback address is enough
+            let is_loopback =
+                ip_networks.iter().any(|n| n.addr.is_loopback()) || is_loopback_name(name);
+            let ssid = ssids.get(name).cloned();
+
+            let interface_type = if is_loopback {
+                "loopback"
+            } else if ssid.is_some() || is_wireless(name) {
+                "wifi"
+            } else {
+                "ethernet"
+            };
+
+            let mac = data.mac_address();
+
+            InterfaceInfo {
+                name: name.clone(),
+                interface_type: interface_type.to_string(),
+                mac_address: (!mac.is_unspecified()).then(|| mac.to_string()),
+                addresses: ip_networks.iter().map(|n| n.to_string()).collect(),
+                gateway: gateway
+                    .as_ref()
+                    .filter(|(interface, _)| interface == name)
+                    .map(|(_, gateway)| gateway.clone()),
+                ssid,
+            }
+        })
+        .collect();
+
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    interfaces
+}
+
+/// Get the default route as (interface name, gateway address)
+#[cfg_attr(not(target_os = "windows"), allow(unused_variables))]
+fn default_gateway(networks: &Networks) -> Option<(String, String)> {
+    #[cfg(target_os = "macos")]
+    return default_gateway_macos();
+
+    #[cfg(target_os = "windows")]
+    return default_gateway_windows(networks);
+
+    #[cfg(target_os = "linux")]
+    return default_gateway_linux();
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    return None;
+}
+
+#[cfg(target_os = "macos")]
+fn default_gateway_macos() -> Option<(String, String)> {
+    use super::CommandExt;
+    use std::process::Command;
+
+    let output = Command::new("route")
+        .args(["-n", "get", "default"])
+        .output_with_timeout()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let field = |key: &str| {
+        stdout.lines().find_map(|line| {
+            let (k, v) = line.split_once(':')?;
+            (k.trim() == key).then(|| v.trim().to_string())
+        })
+    };
+
+    Some((field("interface")?, field("gateway")?))
+}
+
+#[cfg(target_os = "windows")]
+fn default_gateway_windows(networks: &Networks) -> Option<(String, String)> {
+    use super::CommandExt;
+    use std::process::Command;
+
+    let output = Command::new("route")
+        .args(["print", "-4", "0.0.0.0"])
+        .output_with_timeout()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // "Network Destination  Netmask  Gateway  Interface  Metric", the interface given by address
+    let (gateway, interface_addr) = stdout.lines().find_map(|line| {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        match parts.as_slice() {
+            ["0.0.0.0", "0.0.0.0", gateway, interface, _] if *gateway != "On-link" => {
+                let interface_addr: std::net::IpAddr = interface.parse().ok()?;
+                Some((gateway.to_string(), interface_addr))
+            }
+            _ => None,
+        }
+    })?;
+
+    let interface = networks.list().iter().find_map(|(name, data)| {
+        data.ip_networks()
+            .iter()
+            .any(|n| n.addr == interface_addr)
+            .then(|| name.clone())
+    })?;
+
+    Some((interface, gateway))
+}
+
+#[cfg(target_os = "linux")]
+fn default_gateway_linux() -> Option<(String, String)> {
+    // Columns: Iface Destination Gateway Flags ..., addresses as little-endian hex
+    let routes = std::fs::read_to_string("/proc/net/route").ok()?;
+
+    routes.lines().skip(1).find_map(|line| {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 3 || parts[1] != "00000000" {
+            return None;
+        }
+
+        let gateway = u32::from_str_radix(parts[2], 16).ok()?;
+        let gateway = std::net::Ipv4Addr::from(gateway.to_le_bytes());
+        Some((parts[0].to_string(), gateway.to_string()))
+    })
+}
+
+/// Get the SSID of each connected WiFi interface, keyed by interface name
+#[cfg_attr(not(target_os = "macos"), allow(unused_variables))]
+fn wifi_ssids(interfaces: &[String]) -> HashMap<String, String> {
+    #[cfg(target_os = "macos")]
+    return wifi_ssids_macos(interfaces);
+
+    #[cfg(target_os = "windows")]
+    return wifi_ssids_windows();
+
+    #[cfg(target_os = "linux")]
+    return wifi_ssids_linux();
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    return HashMap::new();
+}
+
+#[cfg(target_os = "macos")]
+fn wifi_ssids_macos(interfaces: &[String]) -> HashMap<String, String> {
+    use super::CommandExt;
+    use std::process::Command;
+
+    // WiFi is always an "en" interface, skip the rest to save a subprocess each
+    interfaces
+        .iter()
+        .filter(|name| name.starts_with("en"))
+        .filter_map(|name| {
+            let output = Command::new("ipconfig")
+                .args(["getsummary", name])
+                .output_with_timeout()
+                .ok()?;
+            let stdout = String::from_utf8_lossy(&output.stdout);
+
+            let ssid = stdout.lines().find_map(|line| {
+                let (k, v) = line.split_once(':')?;
+                (k.trim() == "SSID").then(|| v.trim().to_string())
+            })?;
+
+            Some((name.clone(), ssid))
+        })
+        .collect()
+}
+
+#[cfg(target_os = "windows")]
+fn wifi_ssids_windows() -> HashMap<String, String> {
+    use super::CommandExt;
+    use std::process::Command;
+
+    let mut ssids = HashMap::new();
+
+    let Ok(output) = Command::new("netsh")
+        .args(["wlan", "show", "interfaces"])
+        .output_with_timeout()
+    else {
+        return ssids;
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // One "Name : ..." block per wireless interface, SSID only present while connected
+    let mut name = None;
+    for line in stdout.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        match key.trim() {
+            "Name" => name = Some(value.trim().to_string()),
+            "SSID" => {
+                if let Some(name) = name.take() {
+                    ssids.insert(name, value.trim().to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    ssids
+}
+
+#[cfg(target_os = "linux")]
+fn wifi_ssids_linux() -> HashMap<String, String> {
+    use super::CommandExt;
+    use std::process::Command;
+
+    let Ok(output) = Command::new("nmcli")
+        .args(["-t", "-f", "DEVICE,TYPE,STATE,CONNECTION", "device"])
+        .output_with_timeout()
+    else {
+        return HashMap::new();
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // The connection is named after the SSID unless the user renamed it
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.splitn(4, ':').collect();
+            match parts.as_slice() {
+                [device, "wifi", "connected", connection] => {
+                    Some((device.to_string(), connection.replace("\\:", ":")))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Check whether an interface is the loopback one by name, even without addresses
+fn is_loopback_name(name: &str) -> bool {
+    name == "lo" || name == "lo0" || name.starts_with("Loopback")
+}
+
+/// Check whether an interface is a wireless one, even while disconnected
+#[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+fn is_wireless(name: &str) -> bool {
+    #[cfg(target_os = "linux")]
+    return std::path::Path::new("/sys/class/net")
+        .join(name)
+        .join("wireless")
+        .exists();
+
+    #[cfg(not(target_os = "linux"))]
+    return false;
+}
+
 impl Default for NetworkService {
     fn default() -> Self {
         Self::new()
     }
 }
+
+impl SystemService for NetworkService {
+    fn name(&self) -> &'static str {
+        "network"
+    }
+
+    fn tick(&mut self) -> serde_json::Value {
+        serde_json::to_value(self.get_status()).unwrap_or_default()
+    }
+
+    /// Connectivity rarely changes and checking it shells out, so poll it less often
+    fn default_interval_ms(&self) -> u64 {
+        5000
+    }
+}
@@ -0,0 +1,333 @@
+//! OS notification monitoring service
+//!
+//! Mirrors incoming OS notifications so overlays can display them:
+//! - Linux: Monitors `org.freedesktop.Notifications` on the session bus via `dbus-monitor`
+//! - Windows: Polls the `UserNotificationListener` via PowerShell on a background thread
+//! - macOS: Not supported - `UNUserNotificationCenter` only exposes the app's own notifications
+//!
+//! Notifications are privacy sensitive, so the service is disabled by default
+//! and must be enabled explicitly through `system_notifications_set_enabled`.
+
+#[cfg(target_os = "windows")]
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::NotificationStatus;
+
+/// Service for monitoring OS notifications
+pub struct NotificationService {
+    enabled: bool,
+    /// Notifications received since the last drain
+    pending: Arc<Mutex<Vec<NotificationStatus>>>,
+    #[cfg(target_os = "linux")]
+    monitor: Option<std::process::Child>,
+    /// Stop flag of the polling thread, set while it runs
+    #[cfg(target_os = "windows")]
+    poller: Option<Arc<AtomicBool>>,
+}
+
+/// How often the Windows polling thread asks PowerShell for new notifications
+#[cfg(target_os = "windows")]
+const WINDOWS_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+impl NotificationService {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            pending: Arc::new(Mutex::new(Vec::new())),
+            #[cfg(target_os = "linux")]
+            monitor: None,
+            #[cfg(target_os = "windows")]
+            poller: None,
+        }
+    }
+
+    /// Check if notification monitoring is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enable or disable notification monitoring
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if self.enabled == enabled {
+            return;
+        }
+        self.enabled = enabled;
+
+        #[cfg(target_os = "linux")]
+        {
+            if enabled {
+                self.start_monitor_linux();
+            } else {
+                self.stop_monitor_linux();
+            }
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            if enabled {
+                self.start_poller_windows();
+            } else {
+                self.stop_poller_windows();
+            }
+        }
+
+        if !enabled && let Ok(mut pending) = self.pending.lock() {
+            pending.clear();
+        }
+    }
+
+    /// Take all notifications received since the last call
+    pub fn drain(&mut self) -> Vec<NotificationStatus> {
+        if !self.enabled {
+            return Vec::new();
+        }
+
+        match self.pending.lock() {
+            Ok(mut pending) => std::mem::take(&mut *pending),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn start_monitor_linux(&mut self) {
+        use std::io::{BufRead, BufReader};
+        use std::process::{Command, Stdio};
+
+        let child = Command::new("dbus-monitor")
+            .args([
+                "--session",
+                "interface='org.freedesktop.Notifications',member='Notify'",
+            ])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn();
+
+        let mut child = match child {
+            Ok(child) => child,
+            Err(e) => {
+                tracing::error!("Failed to start notification monitor: {}", e);
+                return;
+            }
+        };
+
+        let Some(stdout) = child.stdout.take() else {
+            let _ = child.kill();
+            return;
+        };
+
+        let pending = self.pending.clone();
+        std::thread::spawn(move || {
+            let mut args: Vec<String> = Vec::new();
+            let mut in_notify = false;
+            let mut partial: Option<String> = None;
+
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+
+                // Continuation of a multi-line string argument
+                if let Some(mut value) = partial.take() {
+                    value.push('\n');
+                    match line.strip_suffix('"') {
+                        Some(rest) => {
+                            value.push_str(rest);
+                            args.push(value);
+                        }
+                        None => {
+                            value.push_str(&line);
+                            partial = Some(value);
+                        }
+                    }
+                    continue;
+                }
+
+                if line.starts_with("method call") {
+                    flush_linux_notification(&pending, in_notify, &mut args);
+                    in_notify = line.contains("member=Notify");
+                    continue;
+                }
+
+                if !in_notify {
+                    continue;
+                }
+
+                // Top-level arguments are indented by exactly three spaces:
+                // app_name, replaces_id, app_icon, summary, body, actions, hints, timeout
+                if let Some(value) = line.strip_prefix("   string \"") {
+                    match value.strip_suffix('"') {
+                        Some(value) => args.push(value.to_string()),
+                        None => partial = Some(value.to_string()),
+                    }
+                } else if line.starts_with("   uint32 ") {
+                    args.push(String::new());
+                } else if line.starts_with("   int32 ") {
+                    // expire_timeout is the last argument, the call is complete
+                    flush_linux_notification(&pending, in_notify, &mut args);
+                    in_notify = false;
+                }
+            }
+
+            flush_linux_notification(&pending, in_notify, &mut args);
+        });
+
+        self.monitor = Some(child);
+    }
+
+    #[cfg(target_os = "linux")]
+    fn stop_monitor_linux(&mut self) {
+        if let Some(mut child) = self.monitor.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn start_poller_windows(&mut self) {
+        self.stop_poller_windows();
+
+        let running = Arc::new(AtomicBool::new(true));
+        let pending = self.pending.clone();
+        let flag = running.clone();
+        std::thread::spawn(move || {
+            let mut last_seen_ms = now_ms();
+            while flag.load(Ordering::Relaxed) {
+                poll_windows(&pending, &mut last_seen_ms);
+                std::thread::sleep(WINDOWS_POLL_INTERVAL);
+            }
+        });
+
+        self.poller = Some(running);
+    }
+
+    #[cfg(target_os = "windows")]
+    fn stop_poller_windows(&mut self) {
+        if let Some(running) = self.poller.take() {
+            running.store(false, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Push toast notifications created after `last_seen_ms` onto the pending queue
+#[cfg(target_os = "windows")]
+fn poll_windows(pending: &Mutex<Vec<NotificationStatus>>, last_seen_ms: &mut u64) {
+    use super::CommandExt;
+    use std::process::Command;
+
+    // Use PowerShell to read toast notifications from the UserNotificationListener
+    // Requires the user to grant notification access to the app
+    let script = format!(
+        r#"
+        Add-Type -AssemblyName System.Runtime.WindowsRuntime
+
+        $asTaskGeneric = ([System.WindowsRuntimeSystemExtensions].GetMethods() | Where-Object {{ $_.Name -eq 'AsTask' -and $_.GetParameters().Count -eq 1 -and $_.GetParameters()[0].ParameterType.Name -eq 'IAsyncOperation`1' }})[0]
+
+        Function Await($WinRtTask, $ResultType) {{
+            $asTask = $asTaskGeneric.MakeGenericMethod($ResultType)
+            $netTask = $asTask.Invoke($null, @($WinRtTask))
+            $netTask.Wait(-1) | Out-Null
+            $netTask.Result
+        }}
+
+        try {{
+            [Windows.UI.Notifications.Management.UserNotificationListener, Windows.UI.Notifications, ContentType = WindowsRuntime] | Out-Null
+            [Windows.UI.Notifications.NotificationKinds, Windows.UI.Notifications, ContentType = WindowsRuntime] | Out-Null
+
+            $listener = [Windows.UI.Notifications.Management.UserNotificationListener]::Current
+            $notifications = Await ($listener.GetNotificationsAsync([Windows.UI.Notifications.NotificationKinds]::Toast)) ([System.Collections.Generic.IReadOnlyList[Windows.UI.Notifications.UserNotification]])
+
+            foreach ($n in $notifications) {{
+                $timestamp = $n.CreationTime.ToUnixTimeMilliseconds()
+                if ($timestamp -le {since}) {{ continue }}
+
+                $app = $n.AppInfo.DisplayInfo.DisplayName
+                $binding = $n.Notification.Visual.GetBinding('ToastGeneric')
+                $texts = @($binding.GetTextElements() | ForEach-Object {{ $_.Text -replace '[\r\n|]', ' ' }})
+                $title = if ($texts.Count -gt 0) {{ $texts[0] }} else {{ '' }}
+                $body = if ($texts.Count -gt 1) {{ $texts[1..($texts.Count - 1)] -join ' ' }} else {{ '' }}
+
+                Write-Output "$app|$title|$body|$timestamp"
+            }}
+        }} catch {{
+            # Silently fail
+        }}
+    "#,
+        since = *last_seen_ms
+    );
+
+    let output = Command::new("powershell")
+        .args(["-ExecutionPolicy", "Bypass", "-Command", &script])
+        .output_with_timeout();
+
+    let Ok(out) = output else { return };
+    if !out.status.success() {
+        return;
+    }
+
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let Ok(mut pending) = pending.lock() else {
+        return;
+    };
+
+    for line in stdout.lines() {
+        let parts: Vec<&str> = line.trim().split('|').collect();
+        if parts.len() < 4 {
+            continue;
+        }
+
+        let timestamp = parts[3].parse::<u64>().unwrap_or_else(|_| now_ms());
+        *last_seen_ms = (*last_seen_ms).max(timestamp);
+
+        pending.push(NotificationStatus {
+            app: parts[0].to_string(),
+            title: parts[1].to_string(),
+            body: parts[2].to_string(),
+            timestamp,
+        });
+    }
+}
+
+/// Push a parsed `Notify` call onto the pending queue
+#[cfg(target_os = "linux")]
+fn flush_linux_notification(
+    pending: &Arc<Mutex<Vec<NotificationStatus>>>,
+    in_notify: bool,
+    args: &mut Vec<String>,
+) {
+    let parsed = std::mem::take(args);
+    if !in_notify || parsed.len() < 5 {
+        return;
+    }
+
+    if let Ok(mut pending) = pending.lock() {
+        pending.push(NotificationStatus {
+            app: parsed[0].clone(),
+            title: parsed[3].clone(),
+            body: parsed[4].clone(),
+            timestamp: now_ms(),
+        });
+    }
+}
+
+/// Current time in milliseconds since the Unix epoch
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+impl Default for NotificationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for NotificationService {
+    fn drop(&mut self) {
+        #[cfg(target_os = "linux")]
+        self.stop_monitor_linux();
+        #[cfg(target_os = "windows")]
+        self.stop_poller_windows();
+    }
+}
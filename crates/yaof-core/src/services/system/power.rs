@@ -0,0 +1,317 @@
+//! Suspend/resume detection service
+//!
+//! Reports when the machine goes to sleep and wakes up again, and when its
+//! displays turn off and back on. This is platform-specific:
+//! - Linux: logind's `PrepareForSleep` signal (via `gdbus monitor`), reporting
+//!   both suspend and resume. Display sleep isn't reported
+//! - macOS: `NSWorkspace` will-sleep/did-wake and screens-did-sleep/did-wake
+//!   notifications
+//! - Windows: `PowerRegisterSuspendResumeNotification` for suspend/resume and
+//!   the `GUID_CONSOLE_DISPLAY_STATE` power setting for display sleep
+//!
+//! When no watcher could be started (or it died), the service falls back to a
+//! jump in time between two ticks. Timers don't fire while the machine sleeps,
+//! so a tick arriving much later than scheduled means it just woke up. This only
+//! reports the resume, after the fact. On Linux the gap is measured on the boot
+//! clock, which keeps counting while asleep but ignores NTP and manual clock
+//! changes; elsewhere it has to use the wall clock.
+
+use std::sync::Mutex;
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::time::{Duration, Instant, SystemTime};
+
+use super::{PowerEvent, PowerState};
+
+/// How much later than scheduled a tick must arrive to be treated as a wake from sleep
+const SLEEP_GAP_THRESHOLD: Duration = Duration::from_secs(5);
+
+/// Service for detecting system suspend and resume
+pub struct PowerService {
+    last_tick: SystemTime,
+    last_boot_time: Option<Duration>,
+    /// Power signals from the OS, when a watcher is available.
+    /// Behind a mutex only because `Receiver` isn't `Sync`, `poll` takes `&mut self`
+    signals: Option<Mutex<Receiver<PowerState>>>,
+    suspended_at: Option<Instant>,
+    display_off_at: Option<Instant>,
+}
+
+impl PowerService {
+    pub fn new() -> Self {
+        let signals = Self::watch_signals();
+        if signals.is_none() {
+            tracing::debug!("No power watcher available, detecting resume from tick gaps");
+        }
+
+        Self {
+            last_tick: SystemTime::now(),
+            last_boot_time: boot_time(),
+            signals: signals.map(Mutex::new),
+            suspended_at: None,
+            display_off_at: None,
+        }
+    }
+
+    /// Get the power events since the last call, given the tick loop's interval
+    pub fn poll(&mut self, tick_interval: Duration) -> Vec<PowerEvent> {
+        let gap = self.tick_gap();
+
+        let mut signals = Vec::new();
+        if let Some(rx) = self.signals.as_mut().and_then(|rx| rx.get_mut().ok()) {
+            loop {
+                match rx.try_recv() {
+                    Ok(state) => signals.push(state),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        tracing::warn!(
+                            "Power watcher stopped, detecting resume from tick gaps instead"
+                        );
+                        self.signals = None;
+                        break;
+                    }
+                }
+            }
+        }
+
+        let mut events = Vec::new();
+        for state in signals {
+            let slept_ms = match state {
+                PowerState::Suspend => {
+                    self.suspended_at = Some(Instant::now());
+                    None
+                }
+                PowerState::Resume => self.suspended_at.take().map(elapsed_ms),
+                PowerState::DisplaySleep => {
+                    self.display_off_at = Some(Instant::now());
+                    None
+                }
+                PowerState::DisplayWake => self.display_off_at.take().map(elapsed_ms),
+            };
+            events.push(PowerEvent { state, slept_ms });
+        }
+
+        if self.signals.is_none() && gap > tick_interval + SLEEP_GAP_THRESHOLD {
+            self.suspended_at = None;
+            events.push(PowerEvent {
+                state: PowerState::Resume,
+                slept_ms: Some(gap.saturating_sub(tick_interval).as_millis() as u64),
+            });
+        }
+
+        events
+    }
+
+    /// Time since the previous tick, on the boot clock when there is one
+    fn tick_gap(&mut self) -> Duration {
+        let now = SystemTime::now();
+        let wall_gap = now.duration_since(self.last_tick).unwrap_or_default();
+        self.last_tick = now;
+
+        let boot = boot_time();
+        let boot_gap = match (boot, self.last_boot_time) {
+            (Some(now), Some(last)) => Some(now.saturating_sub(last)),
+            _ => None,
+        };
+        self.last_boot_time = boot;
+
+        boot_gap.unwrap_or(wall_gap)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn watch_signals() -> Option<Receiver<PowerState>> {
+        use std::io::{BufRead, BufReader};
+        use std::process::{Command, Stdio};
+
+        let mut child = Command::new("gdbus")
+            .args([
+                "monitor",
+                "--system",
+                "--dest",
+                "org.freedesktop.login1",
+                "--object-path",
+                "/org/freedesktop/login1",
+            ])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        let stdout = child.stdout.take()?;
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::Builder::new()
+            .name("yaof-power-monitor".to_string())
+            .spawn(move || {
+                // Lines look like "...Manager.PrepareForSleep (true,)"
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    if !line.contains("PrepareForSleep") {
+                        continue;
+                    }
+                    let state = if line.contains("true") {
+                        PowerState::Suspend
+                    } else {
+                        PowerState::Resume
+                    };
+                    if tx.send(state).is_err() {
+                        break;
+                    }
+                }
+                let _ = child.kill();
+            })
+            .ok()?;
+
+        Some(rx)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn watch_signals() -> Option<Receiver<PowerState>> {
+        use std::ptr::NonNull;
+
+        use block2::RcBlock;
+        use objc2_app_kit::{
+            NSWorkspace, NSWorkspaceDidWakeNotification, NSWorkspaceScreensDidSleepNotification,
+            NSWorkspaceScreensDidWakeNotification, NSWorkspaceWillSleepNotification,
+        };
+        use objc2_foundation::NSNotification;
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let center = NSWorkspace::sharedWorkspace().notificationCenter();
+
+        let notifications = unsafe {
+            [
+                (NSWorkspaceWillSleepNotification, PowerState::Suspend),
+                (NSWorkspaceDidWakeNotification, PowerState::Resume),
+                (
+                    NSWorkspaceScreensDidSleepNotification,
+                    PowerState::DisplaySleep,
+                ),
+                (
+                    NSWorkspaceScreensDidWakeNotification,
+                    PowerState::DisplayWake,
+                ),
+            ]
+        };
+        for (name, state) in notifications {
+            let tx = tx.clone();
+            let block = RcBlock::new(move |_: NonNull<NSNotification>| {
+                let _ = tx.send(state);
+            });
+            // Posted on the main thread, which runs the app's event loop.
+            // The observer lives as long as the process, like the watchers elsewhere
+            let observer = unsafe {
+                center.addObserverForName_object_queue_usingBlock(Some(name), None, None, &block)
+            };
+            std::mem::forget(observer);
+        }
+
+        Some(rx)
+    }
+
+    #[cfg(target_os = "windows")]
+    fn watch_signals() -> Option<Receiver<PowerState>> {
+        use std::ffi::c_void;
+        use std::sync::mpsc::Sender;
+
+        use windows::Win32::Foundation::{ERROR_SUCCESS, HANDLE};
+        use windows::Win32::System::Power::{
+            DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS, HPOWERNOTIFY, POWERBROADCAST_SETTING,
+            PowerRegisterSuspendResumeNotification, RegisterPowerSettingNotification,
+        };
+        use windows::Win32::System::SystemServices::GUID_CONSOLE_DISPLAY_STATE;
+        use windows::Win32::UI::WindowsAndMessaging::{
+            DEVICE_NOTIFY_CALLBACK, PBT_APMRESUMEAUTOMATIC, PBT_APMSUSPEND, PBT_POWERSETTINGCHANGE,
+        };
+
+        // Runs on a system thread with the `WM_POWERBROADCAST` type and data
+        unsafe extern "system" fn on_power_broadcast(
+            context: *const c_void,
+            kind: u32,
+            setting: *const c_void,
+        ) -> u32 {
+            let tx = unsafe { &*(context as *const Sender<PowerState>) };
+            let state = match kind {
+                PBT_APMSUSPEND => Some(PowerState::Suspend),
+                PBT_APMRESUMEAUTOMATIC => Some(PowerState::Resume),
+                PBT_POWERSETTINGCHANGE if !setting.is_null() => {
+                    let setting = unsafe { &*(setting as *const POWERBROADCAST_SETTING) };
+                    // 0 = off, 1 = on, 2 = dimmed
+                    match setting.Data[0] {
+                        0 => Some(PowerState::DisplaySleep),
+                        1 => Some(PowerState::DisplayWake),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            if let Some(state) = state {
+                let _ = tx.send(state);
+            }
+            0
+        }
+
+        let (tx, rx) = std::sync::mpsc::channel::<PowerState>();
+        // Both registrations live as long as the process, so the context and the
+        // parameters they point at are leaked on purpose
+        let params: &'static DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS =
+            Box::leak(Box::new(DEVICE_NOTIFY_SUBSCRIBE_PARAMETERS {
+                Callback: Some(on_power_broadcast),
+                Context: Box::into_raw(Box::new(tx)) as *mut c_void,
+            }));
+        let recipient = HANDLE(params as *const _ as *mut c_void);
+
+        let mut registration = HPOWERNOTIFY::default();
+        let result = unsafe {
+            PowerRegisterSuspendResumeNotification(
+                DEVICE_NOTIFY_CALLBACK,
+                recipient,
+                &mut registration,
+            )
+        };
+        if result != ERROR_SUCCESS {
+            tracing::warn!(
+                "Failed to register for suspend/resume notifications: {:?}",
+                result
+            );
+            return None;
+        }
+
+        if let Err(e) = unsafe {
+            RegisterPowerSettingNotification(
+                recipient,
+                &GUID_CONSOLE_DISPLAY_STATE,
+                DEVICE_NOTIFY_CALLBACK,
+            )
+        } {
+            tracing::warn!("Failed to register for display state notifications: {}", e);
+        }
+
+        Some(rx)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+    fn watch_signals() -> Option<Receiver<PowerState>> {
+        None
+    }
+}
+
+impl Default for PowerService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn elapsed_ms(since: Instant) -> u64 {
+    since.elapsed().as_millis() as u64
+}
+
+/// Time since boot, including time spent asleep (`CLOCK_BOOTTIME`)
+#[cfg(target_os = "linux")]
+fn boot_time() -> Option<Duration> {
+    let uptime = std::fs::read_to_string("/proc/uptime").ok()?;
+    let secs: f64 = uptime.split_whitespace().next()?.parse().ok()?;
+    Some(Duration::from_secs_f64(secs))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn boot_time() -> Option<Duration> {
+    None
+}
@@ -0,0 +1,277 @@
+//! Top processes service
+//!
+//! Enumerating every process is much more expensive than the other system
+//! services, so it refreshes on its own (slower) interval instead of every tick.
+//!
+//! Also provides acting on a process from an overlay (kill, focus).
+//! Focusing is platform-specific:
+//! - macOS: AppleScript `set frontmost`
+//! - Windows: `SetForegroundWindow` on the process's first visible top-level window
+//! - Linux: `wmctrl -ia` on the process's first window (X11 only)
+
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+use super::ProcessInfo;
+
+/// Default number of processes reported
+pub const DEFAULT_PROCESS_LIMIT: usize = 5;
+
+/// Default time between process refreshes
+pub const DEFAULT_PROCESS_REFRESH_MS: u64 = 5000;
+
+/// What the reported processes are ranked by
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessSort {
+    #[default]
+    Cpu,
+    Memory,
+}
+
+/// Service for reporting the top processes by CPU or memory
+pub struct ProcessService {
+    system: System,
+    limit: usize,
+    sort_by: ProcessSort,
+    refresh_interval: Duration,
+    last_refresh: Option<Instant>,
+}
+
+impl ProcessService {
+    pub fn new() -> Self {
+        Self {
+            system: System::new(),
+            limit: DEFAULT_PROCESS_LIMIT,
+            sort_by: ProcessSort::default(),
+            refresh_interval: Duration::from_millis(DEFAULT_PROCESS_REFRESH_MS),
+            last_refresh: None,
+        }
+    }
+
+    /// Set how many processes are reported and what they are ranked by
+    pub fn configure(
+        &mut self,
+        limit: Option<usize>,
+        sort_by: Option<ProcessSort>,
+        refresh_interval_ms: Option<u64>,
+    ) {
+        if let Some(limit) = limit {
+            self.limit = limit;
+        }
+        if let Some(sort_by) = sort_by {
+            self.sort_by = sort_by;
+        }
+        if let Some(ms) = refresh_interval_ms {
+            self.refresh_interval = Duration::from_millis(ms.max(1000));
+        }
+    }
+
+    /// Get the top processes if the refresh interval has elapsed, None otherwise
+    pub fn poll(&mut self) -> Option<Vec<ProcessInfo>> {
+        let now = Instant::now();
+        if self
+            .last_refresh
+            .is_some_and(|last| now.duration_since(last) < self.refresh_interval)
+        {
+            return None;
+        }
+        self.last_refresh = Some(now);
+
+        Some(self.top_processes())
+    }
+
+    fn top_processes(&mut self) -> Vec<ProcessInfo> {
+        // CPU usage is measured between two refreshes, so the first report shows 0%
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::new().with_cpu().with_memory(),
+        );
+
+        let mut processes: Vec<ProcessInfo> = self
+            .system
+            .processes()
+            .values()
+            .map(|p| ProcessInfo {
+                pid: p.pid().as_u32(),
+                name: p.name().to_string_lossy().to_string(),
+                cpu_percent: p.cpu_usage(),
+                memory_bytes: p.memory(),
+            })
+            .collect();
+
+        match self.sort_by {
+            ProcessSort::Cpu => {
+                processes.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent));
+            }
+            ProcessSort::Memory => {
+                processes.sort_by_key(|p| std::cmp::Reverse(p.memory_bytes));
+            }
+        }
+        processes.truncate(self.limit);
+
+        processes
+    }
+}
+
+impl Default for ProcessService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Terminate a process
+pub fn kill_process(pid: u32) -> Result<(), String> {
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+
+    let process = system
+        .process(pid)
+        .ok_or_else(|| format!("Process {} no longer exists", pid))?;
+
+    if process.kill() {
+        Ok(())
+    } else {
+        Err(format!("Failed to kill process {}", pid))
+    }
+}
+
+/// Bring a process's main window to the front
+pub fn focus_process(pid: u32) -> Result<(), String> {
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::Some(&[Pid::from_u32(pid)]), true);
+    if system.process(Pid::from_u32(pid)).is_none() {
+        return Err(format!("Process {} no longer exists", pid));
+    }
+
+    #[cfg(target_os = "macos")]
+    return focus_process_macos(pid);
+
+    #[cfg(target_os = "windows")]
+    return focus_process_windows(pid);
+
+    #[cfg(target_os = "linux")]
+    return focus_process_linux(pid);
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    return Err("Focusing processes is not supported on this platform".to_string());
+}
+
+#[cfg(target_os = "macos")]
+fn focus_process_macos(pid: u32) -> Result<(), String> {
+    use super::CommandExt;
+    use std::process::Command;
+
+    let script = format!(
+        r#"tell application "System Events" to set frontmost of (first process whose unix id is {}) to true"#,
+        pid
+    );
+
+    let output = Command::new("osascript")
+        .args(["-e", &script])
+        .output_with_timeout()
+        .map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Failed to focus process {}: {}",
+            pid,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn focus_process_windows(pid: u32) -> Result<(), String> {
+    use windows::Win32::Foundation::{BOOL, HWND, LPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GW_OWNER, GetWindow, GetWindowThreadProcessId, IsIconic, IsWindowVisible,
+        SW_RESTORE, SetForegroundWindow, ShowWindow,
+    };
+
+    struct Search {
+        pid: u32,
+        found: Option<HWND>,
+    }
+
+    unsafe extern "system" fn find_window(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        unsafe {
+            let search = &mut *(lparam.0 as *mut Search);
+
+            let mut window_pid = 0u32;
+            GetWindowThreadProcessId(hwnd, Some(&mut window_pid));
+
+            // The main window is a visible top-level window without an owner
+            let is_main = IsWindowVisible(hwnd).as_bool()
+                && GetWindow(hwnd, GW_OWNER)
+                    .ok()
+                    .is_none_or(|owner| owner.is_invalid());
+            if window_pid == search.pid && is_main {
+                search.found = Some(hwnd);
+                return BOOL(0);
+            }
+
+            BOOL(1)
+        }
+    }
+
+    let mut search = Search { pid, found: None };
+    unsafe {
+        // Stopping the enumeration early reports an error, so the result is ignored
+        let _ = EnumWindows(
+            Some(find_window),
+            LPARAM(&mut search as *mut Search as isize),
+        );
+
+        let hwnd = search
+            .found
+            .ok_or_else(|| format!("Process {} has no visible window", pid))?;
+
+        if IsIconic(hwnd).as_bool() {
+            let _ = ShowWindow(hwnd, SW_RESTORE);
+        }
+
+        if SetForegroundWindow(hwnd).as_bool() {
+            Ok(())
+        } else {
+            Err(format!("Failed to focus process {}", pid))
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn focus_process_linux(pid: u32) -> Result<(), String> {
+    use super::CommandExt;
+    use std::process::Command;
+
+    // `wmctrl -lp` lists "<window id> <desktop> <pid> <host> <title>"
+    let output = Command::new("wmctrl")
+        .arg("-lp")
+        .output_with_timeout()
+        .map_err(|e| format!("wmctrl is required to focus windows: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let window_id = stdout
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .find(|cols| cols.len() >= 3 && cols[2].parse::<u32>() == Ok(pid))
+        .map(|cols| cols[0].to_string())
+        .ok_or_else(|| format!("Process {} has no visible window", pid))?;
+
+    let status = Command::new("wmctrl")
+        .args(["-ia", &window_id])
+        .status()
+        .map_err(|e| e.to_string())?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("Failed to focus process {}", pid))
+    }
+}
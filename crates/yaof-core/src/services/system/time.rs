@@ -0,0 +1,45 @@
+//! Current time service
+//!
+//! Emits the wall-clock time with the local timezone so clock overlays don't each
+//! reimplement time handling. The offset is recomputed on every report, so DST
+//! changes and clock adjustments (e.g. after sleep) show up on the next tick.
+
+use chrono::{Local, Offset};
+
+use super::{SystemService, TimeStatus};
+
+/// Service for reporting the current time and timezone
+pub struct TimeService;
+
+impl TimeService {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Get the current time and UTC offset
+    pub fn get_status(&self) -> TimeStatus {
+        let now = Local::now();
+
+        TimeStatus {
+            epoch_ms: now.timestamp_millis(),
+            timezone: iana_time_zone::get_timezone().ok(),
+            offset_secs: now.offset().fix().local_minus_utc(),
+        }
+    }
+}
+
+impl Default for TimeService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemService for TimeService {
+    fn name(&self) -> &'static str {
+        "time"
+    }
+
+    fn tick(&mut self) -> serde_json::Value {
+        serde_json::to_value(self.get_status()).unwrap_or_default()
+    }
+}
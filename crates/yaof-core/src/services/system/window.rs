@@ -4,8 +4,18 @@
 //! - Window title
 //! - Application name
 //! - Process ID
+//! - Whether it is fullscreen (macOS fullscreen space, Windows fullscreen/presentation
+//!   notification state, Linux `_NET_WM_STATE_FULLSCREEN`)
+//!
+//! On Linux (X11) the active window is read with one `xprop` call per poll, falling
+//! back to `wmctrl`. When neither works, e.g. under Wayland or without the tools
+//! installed, probing backs off so a process isn't spawned on every tick.
+
+use super::{SystemService, WindowStatus};
 
-use super::WindowStatus;
+/// Longest wait between probes while the Linux window tools keep failing
+#[cfg(target_os = "linux")]
+const MAX_PROBE_BACKOFF: std::time::Duration = std::time::Duration::from_secs(60);
 
 /// Service for monitoring focused window
 pub struct WindowService {
@@ -14,7 +24,15 @@ pub struct WindowService {
     #[cfg(target_os = "windows")]
     _windows_state: (),
     #[cfg(target_os = "linux")]
-    _linux_state: (),
+    linux_probe: LinuxProbe,
+}
+
+/// Backoff state for the Linux window tools
+#[cfg(target_os = "linux")]
+#[derive(Default)]
+struct LinuxProbe {
+    failures: u32,
+    next_attempt: Option<std::time::Instant>,
 }
 
 impl WindowService {
@@ -25,12 +43,25 @@ impl WindowService {
             #[cfg(target_os = "windows")]
             _windows_state: (),
             #[cfg(target_os = "linux")]
-            _linux_state: (),
+            linux_probe: LinuxProbe::default(),
         }
     }
 
     /// Get current focused window status
-    pub fn get_status(&self) -> WindowStatus {
+    pub fn get_status(&mut self) -> WindowStatus {
+        // macOS and Linux report fullscreen together with the focused window
+        let status = self.focused_window();
+
+        #[cfg(target_os = "windows")]
+        let status = WindowStatus {
+            is_fullscreen: Self::is_fullscreen_windows(),
+            ..status
+        };
+
+        status
+    }
+
+    fn focused_window(&mut self) -> WindowStatus {
         #[cfg(target_os = "macos")]
         return self.get_status_macos();
 
@@ -46,6 +77,7 @@ impl WindowService {
 
     #[cfg(target_os = "macos")]
     fn get_status_macos(&self) -> WindowStatus {
+        use super::CommandExt;
         use std::process::Command;
 
         // Use AppleScript to get the frontmost application and window title
@@ -58,11 +90,17 @@ impl WindowService {
                     set windowTitle to name of front window of frontApp
                 end try
                 set pid to unix id of frontApp
-                return appName & "|" & windowTitle & "|" & pid
+                set isFullscreen to false
+                try
+                    set isFullscreen to value of attribute "AXFullScreen" of front window of frontApp
+                end try
+                return appName & "|" & windowTitle & "|" & pid & "|" & isFullscreen
             end tell
         "#;
 
-        let output = Command::new("osascript").args(["-e", script]).output();
+        let output = Command::new("osascript")
+            .args(["-e", script])
+            .output_with_timeout();
 
         match output {
             Ok(out) if out.status.success() => {
@@ -77,11 +115,13 @@ impl WindowService {
                         Some(parts[1].to_string())
                     };
                     let process_id = parts[2].parse::<u32>().ok();
+                    let is_fullscreen = parts.get(3) == Some(&"true");
 
                     WindowStatus {
                         title,
                         app_name: Some(app_name),
                         process_id,
+                        is_fullscreen,
                     }
                 } else {
                     WindowStatus::default()
@@ -124,12 +164,14 @@ impl WindowService {
                 title,
                 app_name,
                 process_id: Some(pid),
+                is_fullscreen: false,
             }
         }
     }
 
     #[cfg(target_os = "windows")]
     fn get_process_name_windows(pid: u32) -> Option<String> {
+        use super::CommandExt;
         use std::process::Command;
 
         // Use WMIC to get process name
@@ -142,7 +184,7 @@ impl WindowService {
                 "Name",
                 "/value",
             ])
-            .output()
+            .output_with_timeout()
             .ok()?;
 
         if output.status.success() {
@@ -158,76 +200,146 @@ impl WindowService {
     }
 
     #[cfg(target_os = "linux")]
-    fn get_status_linux(&self) -> WindowStatus {
-        use std::process::Command;
+    fn get_status_linux(&mut self) -> WindowStatus {
+        use std::time::{Duration, Instant};
 
-        // Try using xdotool first
-        let output = Command::new("xdotool")
-            .args(["getactivewindow", "getwindowname"])
-            .output();
-
-        if let Ok(out) = output {
-            if out.status.success() {
-                let title = String::from_utf8_lossy(&out.stdout).trim().to_string();
-
-                // Get PID
-                let pid_output = Command::new("xdotool")
-                    .args(["getactivewindow", "getwindowpid"])
-                    .output();
-
-                let process_id = pid_output
-                    .ok()
-                    .filter(|o| o.status.success())
-                    .and_then(|o| {
-                        String::from_utf8_lossy(&o.stdout)
-                            .trim()
-                            .parse::<u32>()
-                            .ok()
-                    });
-
-                // Try to get app name from WM_CLASS
-                let class_output = Command::new("xdotool")
-                    .args(["getactivewindow", "getwindowclassname"])
-                    .output();
-
-                let app_name = class_output
-                    .ok()
-                    .filter(|o| o.status.success())
-                    .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
-
-                return WindowStatus {
-                    title: Some(title),
-                    app_name,
-                    process_id,
-                };
+        let probe = &mut self.linux_probe;
+        if probe.next_attempt.is_some_and(|at| Instant::now() < at) {
+            return WindowStatus::default();
+        }
+
+        match Self::active_window_xprop().or_else(Self::active_window_wmctrl) {
+            Some(status) => {
+                probe.failures = 0;
+                probe.next_attempt = None;
+                status
+            }
+            None => {
+                probe.failures = probe.failures.saturating_add(1);
+                let backoff = Duration::from_secs(1u64 << probe.failures.min(6));
+                probe.next_attempt = Some(Instant::now() + backoff.min(MAX_PROBE_BACKOFF));
+                WindowStatus::default()
             }
         }
+    }
+
+    /// Read the active window's title, class, PID and fullscreen state from X11
+    #[cfg(target_os = "linux")]
+    fn active_window_xprop() -> Option<WindowStatus> {
+        use super::CommandExt;
+        use std::process::Command;
 
-        // Fallback: try using wmctrl
-        let output = Command::new("wmctrl").args(["-l", "-p"]).output();
+        let xprop = |args: &[&str]| {
+            Command::new("xprop")
+                .args(args)
+                .output_with_timeout()
+                .ok()
+                .filter(|o| o.status.success())
+                .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+        };
 
-        if let Ok(out) = output {
-            if out.status.success() {
-                let stdout = String::from_utf8_lossy(&out.stdout);
-                // wmctrl output format: window_id desktop_id pid host title
-                if let Some(line) = stdout.lines().next() {
-                    let parts: Vec<&str> = line.split_whitespace().collect();
-                    if parts.len() >= 5 {
-                        let process_id = parts[2].parse::<u32>().ok();
-                        let title = parts[4..].join(" ");
-
-                        return WindowStatus {
-                            title: Some(title),
-                            app_name: None,
-                            process_id,
-                        };
-                    }
+        // "_NET_ACTIVE_WINDOW(WINDOW): window id # 0x3a00007"
+        let root = xprop(&["-root", "_NET_ACTIVE_WINDOW"])?;
+        let window_id = root.split_whitespace().last()?.to_string();
+        if !window_id.starts_with("0x") || window_id == "0x0" {
+            return Some(WindowStatus::default());
+        }
+
+        let props = xprop(&[
+            "-id",
+            &window_id,
+            "_NET_WM_NAME",
+            "WM_NAME",
+            "WM_CLASS",
+            "_NET_WM_PID",
+            "_NET_WM_STATE",
+        ])?;
+
+        let mut status = WindowStatus::default();
+        let mut wm_name = None;
+        for line in props.lines() {
+            let Some((name, value)) = line.split_once(" = ") else {
+                continue;
+            };
+            match name.split('(').next().unwrap_or(name) {
+                "_NET_WM_NAME" => status.title = xprop_strings(value).into_iter().next(),
+                "WM_NAME" => wm_name = xprop_strings(value).into_iter().next(),
+                // The instance name, then the class name `xdotool getwindowclassname` reports
+                "WM_CLASS" => status.app_name = xprop_strings(value).into_iter().nth(1),
+                "_NET_WM_PID" => status.process_id = value.trim().parse().ok(),
+                "_NET_WM_STATE" => {
+                    status.is_fullscreen = value.contains("_NET_WM_STATE_FULLSCREEN")
                 }
+                _ => {}
             }
         }
+        if status.title.is_none() {
+            status.title = wm_name;
+        }
+
+        Some(status)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn active_window_wmctrl() -> Option<WindowStatus> {
+        use super::CommandExt;
+        use std::process::Command;
+
+        let out = Command::new("wmctrl")
+            .args(["-l", "-p"])
+            .output_with_timeout()
+            .ok()
+            .filter(|o| o.status.success())?;
+        let stdout = String::from_utf8_lossy(&out.stdout);
+
+        // wmctrl output format: window_id desktop_id pid host title
+        let line = stdout.lines().next()?;
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 5 {
+            return None;
+        }
+
+        Some(WindowStatus {
+            title: Some(parts[4..].join(" ")),
+            app_name: None,
+            process_id: parts[2].parse::<u32>().ok(),
+            is_fullscreen: false,
+        })
+    }
+
+    /// Check whether a fullscreen app, game or presentation is in the foreground
+    #[cfg(target_os = "windows")]
+    fn is_fullscreen_windows() -> bool {
+        use windows::Win32::UI::Shell::{
+            QUNS_BUSY, QUNS_PRESENTATION_MODE, QUNS_RUNNING_D3D_FULL_SCREEN,
+            SHQueryUserNotificationState,
+        };
+
+        unsafe { SHQueryUserNotificationState() }.is_ok_and(|state| {
+            state == QUNS_BUSY
+                || state == QUNS_RUNNING_D3D_FULL_SCREEN
+                || state == QUNS_PRESENTATION_MODE
+        })
+    }
+}
 
-        WindowStatus::default()
+/// The quoted strings of an `xprop` value like `"instance", "Class"`
+#[cfg(target_os = "linux")]
+fn xprop_strings(value: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut chars = value.chars();
+    while chars.by_ref().any(|c| c == '"') {
+        let mut string = String::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => break,
+                '\\' => string.extend(chars.next()),
+                c => string.push(c),
+            }
+        }
+        strings.push(string);
     }
+    strings
 }
 
 impl Default for WindowService {
@@ -235,3 +347,13 @@ impl Default for WindowService {
         Self::new()
     }
 }
+
+impl SystemService for WindowService {
+    fn name(&self) -> &'static str {
+        "window"
+    }
+
+    fn tick(&mut self) -> serde_json::Value {
+        serde_json::to_value(self.get_status()).unwrap_or_default()
+    }
+}
@@ -4,6 +4,7 @@ use std::path::Path;
 use serde::{Deserialize, Serialize};
 
 use crate::manifest::{OverlayDefinition, PositionPreset};
+use crate::overlay::{DesktopLevel, OverlayBorder, ScheduleWindow};
 
 /// Overlay settings that are persisted per-overlay.
 /// These match the TypeScript OverlaySettings interface in the SDK.
@@ -20,10 +21,45 @@ pub struct OverlaySettings {
     pub position_preset: Option<String>,
     #[serde(default = "default_opacity")]
     pub opacity: f64,
+    /// Opacity while hovered, None disables the hover fade
+    #[serde(default)]
+    pub hover_opacity: Option<f64>,
     #[serde(default)]
     pub click_through: bool,
     #[serde(default = "default_true")]
     pub always_on_top: bool,
+    /// Desktop layer the overlay is pinned to, None floats it normally
+    #[serde(default)]
+    pub desktop_level: Option<DesktopLevel>,
+    #[serde(default = "default_zoom")]
+    pub zoom: f64,
+    #[serde(default)]
+    pub frameless: bool,
+    #[serde(default)]
+    pub group: Option<String>,
+    #[serde(default)]
+    pub locked: bool,
+    #[serde(default)]
+    pub exclude_from_bulk_toggle: bool,
+    #[serde(default = "default_true")]
+    pub show_on_fullscreen: bool,
+    #[serde(default)]
+    pub hide_on_fullscreen: bool,
+    #[serde(default)]
+    pub background_color: [u8; 4],
+    #[serde(default)]
+    pub corner_radius: f64,
+    #[serde(default)]
+    pub border: Option<OverlayBorder>,
+    /// Edge padding for position presets, None uses the manifest's
+    #[serde(default)]
+    pub padding: Option<Padding>,
+    /// User CSS (or a path to a .css file) injected into the overlay
+    #[serde(default)]
+    pub inject_css: Option<String>,
+    /// Local time windows the overlay is visible in, always visible when empty
+    #[serde(default)]
+    pub schedule: Vec<ScheduleWindow>,
 }
 
 fn default_true() -> bool {
@@ -34,6 +70,10 @@ fn default_opacity() -> f64 {
     100.0
 }
 
+fn default_zoom() -> f64 {
+    1.0
+}
+
 impl Default for OverlaySettings {
     fn default() -> Self {
         Self {
@@ -44,10 +84,59 @@ impl Default for OverlaySettings {
             y: None,
             position_preset: None,
             opacity: 100.0,
+            hover_opacity: None,
             click_through: false,
             always_on_top: true,
+            desktop_level: None,
+            zoom: 1.0,
+            frameless: false,
+            group: None,
+            locked: false,
+            exclude_from_bulk_toggle: false,
+            show_on_fullscreen: true,
+            hide_on_fullscreen: false,
+            background_color: [0, 0, 0, 0],
+            corner_radius: 0.0,
+            border: None,
+            padding: None,
+            inject_css: None,
+            schedule: Vec::new(),
+        }
+    }
+}
+
+/// Distance kept from the screen edges when placing an overlay from a position preset.
+/// Either one value for both axes or separate horizontal/vertical values
+/// (`20` or `{ "x": 0, "y": 8 }`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Padding {
+    Uniform(f64),
+    Axes { x: f64, y: f64 },
+}
+
+impl Padding {
+    /// Horizontal padding
+    pub fn x(&self) -> f64 {
+        match *self {
+            Padding::Uniform(padding) => padding,
+            Padding::Axes { x, .. } => x,
         }
     }
+
+    /// Vertical padding
+    pub fn y(&self) -> f64 {
+        match *self {
+            Padding::Uniform(padding) => padding,
+            Padding::Axes { y, .. } => y,
+        }
+    }
+}
+
+impl Default for Padding {
+    fn default() -> Self {
+        Padding::Uniform(20.0)
+    }
 }
 
 /// Screen dimensions for position calculations
@@ -61,19 +150,12 @@ impl OverlaySettings {
     /// Load overlay settings from the tauri-plugin-store JSON file.
     /// Uses the manifest definition as fallback for default values.
     /// Returns None if the file doesn't exist or can't be parsed.
-    pub fn load(
-        app_data_dir: &Path,
-        plugin_id: &str,
-        overlay_id: &str,
-        manifest_definition: &OverlayDefinition,
-    ) -> Option<Self> {
-        let store_path = app_data_dir.join(format!("{}-{}-overlay.json", plugin_id, overlay_id));
-
+    pub fn load(store_path: &Path, manifest_definition: &OverlayDefinition) -> Option<Self> {
         if !store_path.exists() {
             return None;
         }
 
-        let content = fs::read_to_string(&store_path).ok()?;
+        let content = fs::read_to_string(store_path).ok()?;
 
         // The tauri-plugin-store format stores values directly as key-value pairs
         let store_data: serde_json::Value = serde_json::from_str(&content).ok()?;
@@ -103,6 +185,8 @@ impl OverlaySettings {
             .and_then(|v| v.as_f64())
             .unwrap_or(100.0);
 
+        let hover_opacity = store_data.get("hoverOpacity").and_then(|v| v.as_f64());
+
         // Use manifest's click_through value as the default fallback
         let click_through = store_data
             .get("clickThrough")
@@ -114,6 +198,79 @@ impl OverlaySettings {
             .and_then(|v| v.as_bool())
             .unwrap_or(true);
 
+        // An explicit null turns off the manifest's desktop level
+        let desktop_level = match store_data.get("desktopLevel") {
+            Some(value) => serde_json::from_value(value.clone()).ok().flatten(),
+            None => manifest_definition.desktop_level,
+        };
+
+        let zoom = store_data
+            .get("zoom")
+            .and_then(|v| v.as_f64())
+            .filter(|z| *z > 0.0)
+            .unwrap_or(1.0);
+
+        // Use manifest's frameless value as the default fallback
+        let frameless = store_data
+            .get("frameless")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(manifest_definition.frameless);
+
+        let group = store_data
+            .get("group")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        let locked = store_data
+            .get("locked")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let exclude_from_bulk_toggle = store_data
+            .get("excludeFromBulkToggle")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let show_on_fullscreen = store_data
+            .get("showOnFullscreen")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        let hide_on_fullscreen = store_data
+            .get("hideOnFullscreen")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let background_color = store_data
+            .get("backgroundColor")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or([0, 0, 0, 0]);
+
+        let corner_radius = store_data
+            .get("cornerRadius")
+            .and_then(|v| v.as_f64())
+            .filter(|r| *r >= 0.0)
+            .unwrap_or(0.0);
+
+        let border = store_data
+            .get("border")
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+
+        let padding = store_data
+            .get("padding")
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+
+        let inject_css = store_data
+            .get("injectCss")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string());
+
+        let schedule = store_data
+            .get("schedule")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+
         Some(Self {
             enabled,
             width,
@@ -122,8 +279,23 @@ impl OverlaySettings {
             y,
             position_preset,
             opacity,
+            hover_opacity,
             click_through,
             always_on_top,
+            desktop_level,
+            zoom,
+            frameless,
+            group,
+            locked,
+            exclude_from_bulk_toggle,
+            show_on_fullscreen,
+            hide_on_fullscreen,
+            background_color,
+            corner_radius,
+            border,
+            padding,
+            inject_css,
+            schedule,
         })
     }
 }
@@ -135,20 +307,20 @@ pub fn calculate_position_from_preset(
     screen: &ScreenInfo,
     window_width: f64,
     window_height: f64,
-    padding: f64,
+    padding: Padding,
 ) -> (f64, f64) {
     let x = if preset.contains("left") {
-        padding
+        padding.x()
     } else if preset.contains("right") {
-        screen.width - window_width - padding
+        screen.width - window_width - padding.x()
     } else {
         (screen.width - window_width) / 2.0
     };
 
     let y = if preset.contains("top") {
-        padding
+        padding.y()
     } else if preset.contains("bottom") {
-        screen.height - window_height - padding
+        screen.height - window_height - padding.y()
     } else {
         (screen.height - window_height) / 2.0
     };
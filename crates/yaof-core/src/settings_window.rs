@@ -0,0 +1,43 @@
+//! The settings window, opened from the tray or by overlays
+
+use tauri::{AppHandle, Manager, Runtime, WebviewUrl, WebviewWindowBuilder};
+
+/// Window label of the settings window
+pub const SETTINGS_WINDOW_LABEL: &str = "settings";
+
+/// Opens the settings window, or focuses it if already open
+pub fn open_settings_window<R: Runtime>(app: &AppHandle<R>) -> Result<(), tauri::Error> {
+    // Check if settings window already exists
+    if let Some(window) = app.get_webview_window(SETTINGS_WINDOW_LABEL) {
+        // Window exists - show and focus it
+        window.show()?;
+        window.unminimize()?;
+        window.set_focus()?;
+        return Ok(());
+    }
+
+    // Create new settings window
+    let _window = WebviewWindowBuilder::new(
+        app,
+        SETTINGS_WINDOW_LABEL,
+        WebviewUrl::App("index.html".into()),
+    )
+    .title("YAOF Settings")
+    .inner_size(900.0, 650.0)
+    .min_inner_size(700.0, 500.0)
+    .center()
+    .decorations(true)
+    .resizable(true)
+    .visible(true)
+    .build()?;
+
+    Ok(())
+}
+
+/// Hides the settings window, keeping it around so it reopens where it was
+pub fn hide_settings_window<R: Runtime>(app: &AppHandle<R>) -> Result<(), tauri::Error> {
+    match app.get_webview_window(SETTINGS_WINDOW_LABEL) {
+        Some(window) => window.hide(),
+        None => Ok(()),
+    }
+}
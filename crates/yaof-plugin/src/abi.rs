@@ -1,6 +1,46 @@
 use std::ffi::c_void;
 
-pub const ABI_VERSION: u32 = 1;
+/// Version 2 appended the timer functions to `PluginContext`,
+/// version 3 appended the key-value store functions,
+/// version 4 appended `emit_event_binary`,
+/// version 5 appended the keychain secret functions
+pub const ABI_VERSION: u32 = 5;
+
+/// Message type passed to `handle_message` when a timer fires.
+/// The payload is JSON: `{"id": "<timer id>"}`
+pub const TIMER_MESSAGE_TYPE: &str = "__timer";
+
+/// Message type passed to `handle_message` when an overlay calls `service_request`.
+/// The payload is JSON: `{"requestId": "...", "serviceId": "...", "method": "...", "params": ...}`.
+/// Answer it with `Context::respond`.
+pub const SERVICE_REQUEST_MESSAGE_TYPE: &str = "__request";
+
+/// Event name used by `Context::respond` to answer a service request
+pub const SERVICE_RESPONSE_EVENT: &str = "__response";
+
+/// Event name used by `Context::emit_batch` to emit several service updates at once.
+/// The payload is JSON: `[["<service id>", <data>], ...]`
+pub const SERVICE_BATCH_EVENT: &str = "__batch";
+
+/// Message type passed to `handle_message` when the machine wakes from sleep.
+/// The payload is JSON: `{"sleptMs": <milliseconds asleep, or null if unknown>}`
+pub const RESUME_MESSAGE_TYPE: &str = "__resume";
+
+/// Message type passed to `handle_message` after the plugin's settings were written.
+/// The payload is JSON: `{"pluginId": "...", "keys": ["<key>", ...]}`, with `keys`
+/// null when all settings were cleared
+pub const SETTINGS_CHANGED_MESSAGE_TYPE: &str = "__settings_changed";
+
+/// Prefix of the message type passed to `handle_message` when one of the plugin's
+/// tray items (manifest `tray`) is clicked: `tray:<item-id>`, with an empty payload
+pub const TRAY_MESSAGE_PREFIX: &str = "tray:";
+
+/// Whether a message type is reserved for messages sent by the host: `__`-prefixed
+/// types like [`TIMER_MESSAGE_TYPE`] and tray clicks ([`TRAY_MESSAGE_PREFIX`]).
+/// The host refuses to forward reserved types from the frontend.
+pub fn is_reserved_message_type(msg_type: &str) -> bool {
+    msg_type.starts_with("__") || msg_type.starts_with(TRAY_MESSAGE_PREFIX)
+}
 
 #[repr(C)]
 pub struct PluginVTable {
@@ -15,6 +55,7 @@ pub struct PluginVTable {
     pub tick: Option<unsafe extern "C" fn(ctx: *mut PluginContext) -> i32>,
 
     /// Called when plugin is unloaded
+    /// Must not block indefinitely - the host abandons plugins that take too long
     pub shutdown: unsafe extern "C" fn(cts: *mut PluginContext) -> i32,
 
     /// Handle a message from the frontend
@@ -51,6 +92,78 @@ pub struct PluginContext {
         message: *const u8,
         message_len: usize,
     ),
+
+    /// Schedule a timer that fires `handle_message` with `TIMER_MESSAGE_TYPE`
+    /// after `delay_ms`, repeating every `delay_ms` if `repeat` is non-zero.
+    /// Scheduling an existing id replaces that timer.
+    pub set_timer: unsafe extern "C" fn(
+        host_data: *mut c_void,
+        timer_id: *const u8,
+        timer_id_len: usize,
+        delay_ms: u64,
+        repeat: u32,
+    ) -> i32,
+
+    /// Cancel a timer. Returns 0 if it existed, non-zero otherwise
+    pub clear_timer: unsafe extern "C" fn(
+        host_data: *mut c_void,
+        timer_id: *const u8,
+        timer_id_len: usize,
+    ) -> i32,
+
+    /// Read a value from the plugin's persistent store.
+    /// If the key exists the host calls `write(out, value, value_len)` once with
+    /// the value and returns 0. Returns 1 if the key is missing, negative on error.
+    pub store_get: unsafe extern "C" fn(
+        host_data: *mut c_void,
+        key: *const u8,
+        key_len: usize,
+        out: *mut c_void,
+        write: unsafe extern "C" fn(out: *mut c_void, value: *const u8, value_len: usize),
+    ) -> i32,
+
+    /// Write a value to the plugin's persistent store
+    /// Returns 0 on success, non-zero on error
+    pub store_set: unsafe extern "C" fn(
+        host_data: *mut c_void,
+        key: *const u8,
+        key_len: usize,
+        value: *const u8,
+        value_len: usize,
+    ) -> i32,
+
+    /// Emit raw bytes on a service without JSON encoding.
+    /// Overlays are notified and fetch the bytes as an `ArrayBuffer`.
+    /// Returns 0 on success, non-zero on error
+    pub emit_event_binary: unsafe extern "C" fn(
+        host_data: *mut c_void,
+        event_name: *const u8,
+        event_name_len: usize,
+        data: *const u8,
+        data_len: usize,
+    ) -> i32,
+
+    /// Read a secret from the OS keychain, shared with the `plugin_secret_*` commands.
+    /// If the secret exists the host calls `write(out, value, value_len)` once with
+    /// it and returns 0. Returns 1 if the secret is missing, negative on error.
+    /// Blocks on the keychain; don't call it from `tick` or `handle_message`.
+    pub secret_get: unsafe extern "C" fn(
+        host_data: *mut c_void,
+        key: *const u8,
+        key_len: usize,
+        out: *mut c_void,
+        write: unsafe extern "C" fn(out: *mut c_void, value: *const u8, value_len: usize),
+    ) -> i32,
+
+    /// Store a secret in the OS keychain, replacing any previous value
+    /// Returns 0 on success, non-zero on error. Blocks like `secret_get`.
+    pub secret_set: unsafe extern "C" fn(
+        host_data: *mut c_void,
+        key: *const u8,
+        key_len: usize,
+        value: *const u8,
+        value_len: usize,
+    ) -> i32,
 }
 
 pub const PLUGIN_SYMBOL: &str = "YAOF_PLUGIN";
@@ -1,4 +1,6 @@
-use crate::abi::PluginContext;
+use std::ffi::c_void;
+
+use crate::abi::{PluginContext, SERVICE_BATCH_EVENT, SERVICE_RESPONSE_EVENT};
 
 pub struct Context {
     inner: *mut PluginContext,
@@ -26,6 +28,160 @@ impl Context {
         }
     }
 
+    /// Emit raw bytes on a service, e.g. a framebuffer, without base64 encoding them
+    pub fn emit_binary(&self, event_name: &str, data: &[u8]) -> Result<(), i32> {
+        unsafe {
+            let ctx = &*self.inner;
+            let result = (ctx.emit_event_binary)(
+                ctx.host_data,
+                event_name.as_ptr(),
+                event_name.len(),
+                data.as_ptr(),
+                data.len(),
+            );
+
+            if result == 0 { Ok(()) } else { Err(result) }
+        }
+    }
+
+    /// Emit several service updates with a single call into the host
+    pub fn emit_batch(&self, updates: &[(&str, serde_json::Value)]) -> Result<(), i32> {
+        let payload: Vec<serde_json::Value> = updates
+            .iter()
+            .map(|(service_id, data)| serde_json::json!([service_id, data]))
+            .collect();
+        self.emit(SERVICE_BATCH_EVENT, &serde_json::Value::Array(payload))
+    }
+
+    /// Answer a `SERVICE_REQUEST_MESSAGE_TYPE` message, either with a result or an error
+    pub fn respond(
+        &self,
+        request_id: &str,
+        result: Result<serde_json::Value, String>,
+    ) -> Result<(), i32> {
+        let payload = match result {
+            Ok(result) => serde_json::json!({ "requestId": request_id, "result": result }),
+            Err(error) => serde_json::json!({ "requestId": request_id, "error": error }),
+        };
+        self.emit(SERVICE_RESPONSE_EVENT, &payload)
+    }
+
+    /// Fire a `TIMER_MESSAGE_TYPE` message with this timer id once, after `ms` milliseconds
+    pub fn set_timeout(&self, ms: u64, id: &str) -> Result<(), i32> {
+        self.set_timer(ms, id, false)
+    }
+
+    /// Fire a `TIMER_MESSAGE_TYPE` message with this timer id every `ms` milliseconds
+    pub fn set_interval(&self, ms: u64, id: &str) -> Result<(), i32> {
+        self.set_timer(ms, id, true)
+    }
+
+    /// Cancel a timeout or interval
+    pub fn clear_timer(&self, id: &str) -> Result<(), i32> {
+        unsafe {
+            let ctx = &*self.inner;
+            let result = (ctx.clear_timer)(ctx.host_data, id.as_ptr(), id.len());
+
+            if result == 0 { Ok(()) } else { Err(result) }
+        }
+    }
+
+    /// Read a value persisted with `store_set`
+    pub fn store_get(&self, key: &str) -> Option<Vec<u8>> {
+        unsafe extern "C" fn write(out: *mut c_void, value: *const u8, value_len: usize) {
+            unsafe {
+                let out = &mut *(out as *mut Option<Vec<u8>>);
+                *out = Some(std::slice::from_raw_parts(value, value_len).to_vec());
+            }
+        }
+
+        let mut value: Option<Vec<u8>> = None;
+        unsafe {
+            let ctx = &*self.inner;
+            let result = (ctx.store_get)(
+                ctx.host_data,
+                key.as_ptr(),
+                key.len(),
+                &mut value as *mut Option<Vec<u8>> as *mut c_void,
+                write,
+            );
+
+            if result == 0 { value } else { None }
+        }
+    }
+
+    /// Persist a value across restarts in the host's per-plugin store
+    pub fn store_set(&self, key: &str, value: &[u8]) -> Result<(), i32> {
+        unsafe {
+            let ctx = &*self.inner;
+            let result = (ctx.store_set)(
+                ctx.host_data,
+                key.as_ptr(),
+                key.len(),
+                value.as_ptr(),
+                value.len(),
+            );
+
+            if result == 0 { Ok(()) } else { Err(result) }
+        }
+    }
+
+    /// Read a secret from the OS keychain, e.g. an API token the user entered in settings.
+    ///
+    /// Blocks until the keychain answers, which can take a while if the OS asks the user
+    /// to unlock it. The host holds its native plugin lock while calling `tick` and
+    /// `handle_message`, so calling this from them stalls every native plugin; call it
+    /// from a thread of your own.
+    pub fn secret_get(&self, key: &str) -> Option<String> {
+        unsafe extern "C" fn write(out: *mut c_void, value: *const u8, value_len: usize) {
+            unsafe {
+                let out = &mut *(out as *mut Option<String>);
+                let value = std::slice::from_raw_parts(value, value_len);
+                *out = Some(String::from_utf8_lossy(value).into_owned());
+            }
+        }
+
+        let mut value: Option<String> = None;
+        unsafe {
+            let ctx = &*self.inner;
+            let result = (ctx.secret_get)(
+                ctx.host_data,
+                key.as_ptr(),
+                key.len(),
+                &mut value as *mut Option<String> as *mut c_void,
+                write,
+            );
+
+            if result == 0 { value } else { None }
+        }
+    }
+
+    /// Store a secret in the OS keychain instead of the plaintext store.
+    /// Blocks like [`Context::secret_get`], so don't call it from `tick` or `handle_message`.
+    pub fn secret_set(&self, key: &str, value: &str) -> Result<(), i32> {
+        unsafe {
+            let ctx = &*self.inner;
+            let result = (ctx.secret_set)(
+                ctx.host_data,
+                key.as_ptr(),
+                key.len(),
+                value.as_ptr(),
+                value.len(),
+            );
+
+            if result == 0 { Ok(()) } else { Err(result) }
+        }
+    }
+
+    fn set_timer(&self, ms: u64, id: &str, repeat: bool) -> Result<(), i32> {
+        unsafe {
+            let ctx = &*self.inner;
+            let result = (ctx.set_timer)(ctx.host_data, id.as_ptr(), id.len(), ms, repeat as u32);
+
+            if result == 0 { Ok(()) } else { Err(result) }
+        }
+    }
+
     pub fn trace(&self, message: &str) {
         self.log_internal(0, message);
     }
@@ -104,9 +104,20 @@ pub trait NativePlugin: Send + Sync {
     }
 
     /// Called when plugin is being unloaded
+    ///
+    /// Must not block indefinitely (e.g. joining a thread that never exits).
+    /// The host waits a couple of seconds per plugin and then moves on.
     fn shutdown(&mut self, ctx: &Context) {}
 
     /// Handle a message from the frontend
+    ///
+    /// Timers scheduled with `Context::set_timeout`/`set_interval` arrive here as
+    /// `TIMER_MESSAGE_TYPE` messages with a `{"id": "..."}` JSON payload.
+    /// Service requests arrive as `SERVICE_REQUEST_MESSAGE_TYPE` messages and are
+    /// answered with `Context::respond`.
+    /// A `RESUME_MESSAGE_TYPE` message is sent when the machine wakes from sleep,
+    /// and a `SETTINGS_CHANGED_MESSAGE_TYPE` message when the user changed settings.
+    /// Clicking one of the plugin's tray items sends a `TRAY_MESSAGE_PREFIX` message.
     fn handle_message(&mut self, ctx: &Context, msg_type: &str, payload: &[u8]) -> i32 {
         0
     }
@@ -1,25 +1,59 @@
 //! Hot reload support for development mode.
 //!
 //! This module watches plugin dist/ directories for changes and automatically
-//! reloads the affected overlay webviews when files are modified.
+//! reloads the affected overlay webviews when files are modified. The plugins
+//! directory itself is watched too, so plugins installed or removed while
+//! running are picked up without a restart.
 //!
 //! Only active when YAOF_DEV=1 environment variable is set.
+//!
+//! With YAOF_HMR=1, overlays are sent a `yaof:plugin:changed` event listing the
+//! changed files instead of being reloaded, so the SDK can apply a targeted HMR
+//! update. Changes to assets that can't be hot-swapped still trigger a full reload,
+//! and so do overlays that don't use the SDK's `OverlayProvider`.
+//!
+//! Native libraries loaded as dev plugins (`YAOF_NATIVE_DEV` or `native_plugin_load_dev`)
+//! are watched as well and reloaded when they are rebuilt.
 
-use notify_debouncer_mini::{DebouncedEventKind, new_debouncer, notify::RecursiveMode};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use notify_debouncer_mini::{
+    DebouncedEventKind, Debouncer, new_debouncer,
+    notify::{RecommendedWatcher, RecursiveMode, Watcher},
+};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::channel;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tauri::{AppHandle, Manager, Runtime};
+use tauri::{AppHandle, Emitter, Listener, Manager, Runtime};
+
+/// Event emitted to a plugin's overlays when its files change in HMR mode
+pub const PLUGIN_CHANGED_EVENT: &str = "yaof:plugin:changed";
+
+/// Payload of the `yaof:plugin:changed` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PluginChangedEvent {
+    plugin_id: String,
+    paths: Vec<String>,
+}
 
 /// Check if we're running in development mode
 pub fn is_dev_mode() -> bool {
-    std::env::var("YAOF_DEV").map(|v| v == "1").unwrap_or(false)
+    yaof_core::is_dev_mode()
+}
+
+/// Check if changes should be delivered as HMR events instead of full reloads
+pub fn is_hmr_mode() -> bool {
+    std::env::var("YAOF_HMR").map(|v| v == "1").unwrap_or(false)
 }
 
-/// Get the plugins directory path
-fn get_plugins_dir() -> Option<PathBuf> {
-    dirs::home_dir().map(|h| h.join(".yaof").join("plugins"))
+/// Check if a changed file can be applied by the SDK without a full page reload
+fn is_hmr_capable(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("js" | "mjs" | "css" | "map")
+    )
 }
 
 /// Discover all plugins and resolve their actual paths (following symlinks).
@@ -58,14 +92,11 @@ fn discover_plugin_paths(plugins_dir: &std::path::Path) -> HashMap<PathBuf, Stri
 
             // Only add if dist directory exists
             if dist_path.exists() {
-                println!(
-                    "[HotReload] Discovered plugin '{}': {:?}",
-                    plugin_id, dist_path
-                );
+                tracing::debug!("Discovered plugin '{}': {:?}", plugin_id, dist_path);
                 path_to_plugin.insert(dist_path, plugin_id);
             } else {
-                println!(
-                    "[HotReload] Plugin '{}' has no dist/ directory yet, will watch parent",
+                tracing::debug!(
+                    "Plugin '{}' has no dist/ directory yet, will watch parent",
                     plugin_id
                 );
                 // Watch the plugin directory itself so we catch when dist/ is created
@@ -91,6 +122,48 @@ fn extract_plugin_id_from_path(
     None
 }
 
+/// Update the watched plugin paths to match a fresh discovery result.
+/// Paths that disappeared are unwatched and new ones are watched recursively.
+fn sync_plugin_watches(
+    watcher: &mut dyn Watcher,
+    path_to_plugin: &mut HashMap<PathBuf, String>,
+    discovered: HashMap<PathBuf, String>,
+) {
+    for (watch_path, plugin_id) in path_to_plugin.iter() {
+        if !discovered.contains_key(watch_path) {
+            // The path may already be gone, so failures here are expected
+            let _ = watcher.unwatch(watch_path);
+            tracing::info!(
+                "Stopped watching plugin '{}' at {:?}",
+                plugin_id,
+                watch_path
+            );
+        }
+    }
+
+    for (watch_path, plugin_id) in &discovered {
+        if path_to_plugin.contains_key(watch_path) {
+            continue;
+        }
+
+        match watcher.watch(watch_path, RecursiveMode::Recursive) {
+            Ok(_) => {
+                tracing::debug!("Watching plugin '{}' at {:?}", plugin_id, watch_path);
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to watch plugin '{}' at {:?}: {}",
+                    plugin_id,
+                    watch_path,
+                    e
+                );
+            }
+        }
+    }
+
+    *path_to_plugin = discovered;
+}
+
 /// Start the hot reload file watcher.
 /// This spawns a background thread that watches for file changes in plugin dist/ directories.
 pub fn start_hot_reload_watcher<R: Runtime>(app: AppHandle<R>) {
@@ -98,23 +171,20 @@ pub fn start_hot_reload_watcher<R: Runtime>(app: AppHandle<R>) {
         return;
     }
 
-    let plugins_dir = match get_plugins_dir() {
+    let plugins_dir = match yaof_core::yaof_plugins_dir() {
         Some(dir) => dir,
         None => {
-            eprintln!("[HotReload] Failed to determine plugins directory");
+            tracing::error!("Failed to determine plugins directory");
             return;
         }
     };
 
     if !plugins_dir.exists() {
-        eprintln!(
-            "[HotReload] Plugins directory does not exist: {:?}",
-            plugins_dir
-        );
+        tracing::warn!("Plugins directory does not exist: {:?}", plugins_dir);
         return;
     }
 
-    println!("[HotReload] Starting file watcher (dev mode)");
+    tracing::info!("Starting file watcher (dev mode)");
 
     // Spawn the watcher in a separate thread
     std::thread::spawn(move || {
@@ -124,42 +194,33 @@ pub fn start_hot_reload_watcher<R: Runtime>(app: AppHandle<R>) {
         let mut debouncer = match new_debouncer(Duration::from_millis(500), tx) {
             Ok(d) => d,
             Err(e) => {
-                eprintln!("[HotReload] Failed to create file watcher: {}", e);
+                tracing::error!("Failed to create file watcher: {}", e);
                 return;
             }
         };
 
-        // Discover all plugins and their resolved paths
-        let path_to_plugin = discover_plugin_paths(&plugins_dir);
-
-        if path_to_plugin.is_empty() {
-            println!("[HotReload] No plugins found to watch");
-            return;
+        // Watch the top-level plugins directory so newly installed/removed
+        // plugins are picked up without restarting
+        if let Err(e) = debouncer
+            .watcher()
+            .watch(&plugins_dir, RecursiveMode::NonRecursive)
+        {
+            tracing::warn!("Failed to watch plugins directory {:?}: {}", plugins_dir, e);
         }
+        let canonical_plugins_dir = plugins_dir
+            .canonicalize()
+            .unwrap_or_else(|_| plugins_dir.clone());
 
-        // Watch each plugin's resolved dist directory individually
-        for (watch_path, plugin_id) in &path_to_plugin {
-            match debouncer
-                .watcher()
-                .watch(watch_path, RecursiveMode::Recursive)
-            {
-                Ok(_) => {
-                    println!(
-                        "[HotReload] Watching plugin '{}' at {:?}",
-                        plugin_id, watch_path
-                    );
-                }
-                Err(e) => {
-                    eprintln!(
-                        "[HotReload] Failed to watch plugin '{}' at {:?}: {}",
-                        plugin_id, watch_path, e
-                    );
-                }
-            }
-        }
+        // Discover all plugins and watch their resolved dist directories
+        let mut path_to_plugin = HashMap::new();
+        sync_plugin_watches(
+            debouncer.watcher(),
+            &mut path_to_plugin,
+            discover_plugin_paths(&plugins_dir),
+        );
 
-        println!(
-            "[HotReload] File watcher started, monitoring {} plugin(s)",
+        tracing::info!(
+            "File watcher started, monitoring {} plugin(s)",
             path_to_plugin.len()
         );
 
@@ -167,11 +228,19 @@ pub fn start_hot_reload_watcher<R: Runtime>(app: AppHandle<R>) {
         loop {
             match rx.recv() {
                 Ok(Ok(events)) => {
-                    // Collect unique plugin IDs that had changes
-                    let mut changed_plugins: std::collections::HashSet<String> =
-                        std::collections::HashSet::new();
+                    // Collect the changed paths for each plugin
+                    let mut changed_plugins: HashMap<String, Vec<PathBuf>> = HashMap::new();
+                    let mut plugins_changed = false;
 
                     for event in events {
+                        // A direct child of the plugins directory was added or removed
+                        if event.path.parent().is_some_and(|parent| {
+                            parent == plugins_dir || parent == canonical_plugins_dir
+                        }) {
+                            plugins_changed = true;
+                            continue;
+                        }
+
                         // Only process write/create events
                         if event.kind != DebouncedEventKind::Any {
                             continue;
@@ -188,21 +257,136 @@ pub fn start_hot_reload_watcher<R: Runtime>(app: AppHandle<R>) {
                         // Extract the plugin ID using our mapping
                         if let Some(plugin_id) = extract_plugin_id_from_path(path, &path_to_plugin)
                         {
-                            changed_plugins.insert(plugin_id);
+                            changed_plugins
+                                .entry(plugin_id)
+                                .or_default()
+                                .push(path.clone());
+                        }
+                    }
+
+                    // Re-run discovery to add/drop watches for installed/removed plugins
+                    if plugins_changed {
+                        sync_plugin_watches(
+                            debouncer.watcher(),
+                            &mut path_to_plugin,
+                            discover_plugin_paths(&plugins_dir),
+                        );
+                    }
+
+                    // Reload (or hot-update) overlays for each changed plugin
+                    for (plugin_id, paths) in changed_plugins {
+                        tracing::info!("Detected changes in plugin: {}", plugin_id);
+                        if is_hmr_mode() && paths.iter().all(|p| is_hmr_capable(p)) {
+                            notify_plugin_overlays(&app, &plugin_id, &paths);
+                        } else {
+                            reload_plugin_overlays(&app, &plugin_id);
                         }
                     }
+                }
+                Ok(Err(e)) => {
+                    tracing::error!("Watch error: {:?}", e);
+                }
+                Err(e) => {
+                    tracing::error!("Channel error: {:?}", e);
+                    break;
+                }
+            }
+        }
+    });
+}
+
+/// Directories watched for dev native plugin rebuilds. Build tools usually replace
+/// the library rather than write to it, so its directory is watched, not the file.
+struct NativeDevWatches {
+    debouncer: Debouncer<RecommendedWatcher>,
+    dirs: HashSet<PathBuf>,
+}
+
+impl NativeDevWatches {
+    fn watch(&mut self, library: &Path) {
+        let Some(dir) = library.parent() else {
+            return;
+        };
+        if self.dirs.contains(dir) {
+            return;
+        }
+
+        match self
+            .debouncer
+            .watcher()
+            .watch(dir, RecursiveMode::NonRecursive)
+        {
+            Ok(_) => {
+                tracing::info!("Watching dev native plugin {:?}", library);
+                self.dirs.insert(dir.to_path_buf());
+            }
+            Err(e) => {
+                tracing::warn!("Failed to watch dev native plugin {:?}: {}", library, e);
+            }
+        }
+    }
+}
 
-                    // Reload overlays for each changed plugin
-                    for plugin_id in changed_plugins {
-                        println!("[HotReload] Detected changes in plugin: {}", plugin_id);
-                        reload_plugin_overlays(&app, &plugin_id);
+/// Start watching dev native plugins, reloading each one when its library is rebuilt.
+/// Libraries from `YAOF_NATIVE_DEV` are watched right away, ones loaded later through
+/// `native_plugin_load_dev` once they announce themselves.
+pub fn start_native_dev_watcher<R: Runtime>(app: AppHandle<R>) {
+    if !is_dev_mode() {
+        return;
+    }
+
+    let (tx, rx) = channel();
+    let debouncer = match new_debouncer(Duration::from_millis(500), tx) {
+        Ok(d) => d,
+        Err(e) => {
+            tracing::error!("Failed to create native plugin watcher: {}", e);
+            return;
+        }
+    };
+
+    let watches = Arc::new(Mutex::new(NativeDevWatches {
+        debouncer,
+        dirs: HashSet::new(),
+    }));
+
+    if let Ok(mut watches) = watches.lock() {
+        for path in yaof_core::native_dev_paths() {
+            watches.watch(&path.canonicalize().unwrap_or(path));
+        }
+    }
+
+    let listener_watches = watches.clone();
+    app.listen(yaof_core::NATIVE_DEV_LOADED_EVENT, move |event| {
+        let Ok(path) = serde_json::from_str::<PathBuf>(event.payload()) else {
+            return;
+        };
+        if let Ok(mut watches) = listener_watches.lock() {
+            watches.watch(&path);
+        }
+    });
+
+    std::thread::spawn(move || {
+        // Keeps the debouncer alive for as long as events are processed
+        let _watches = watches;
+
+        loop {
+            match rx.recv() {
+                Ok(Ok(events)) => {
+                    let changed: HashSet<PathBuf> = events
+                        .into_iter()
+                        .filter(|event| event.kind == DebouncedEventKind::Any)
+                        .map(|event| event.path)
+                        .collect();
+
+                    for path in changed {
+                        reload_native_dev_plugin(&app, &path);
                     }
                 }
                 Ok(Err(e)) => {
-                    eprintln!("[HotReload] Watch error: {:?}", e);
+                    tracing::error!("Native plugin watch error: {:?}", e);
                 }
                 Err(e) => {
-                    eprintln!("[HotReload] Channel error: {:?}", e);
+                    tracing::error!("Native plugin watch channel error: {:?}", e);
                     break;
                 }
             }
@@ -210,24 +394,104 @@ pub fn start_hot_reload_watcher<R: Runtime>(app: AppHandle<R>) {
     });
 }
 
+/// Reload the dev native plugin built to `path`, if there is one
+fn reload_native_dev_plugin<R: Runtime>(app: &AppHandle<R>, path: &Path) {
+    // Only the library itself matters, not other build outputs next to it
+    if !path.is_file() {
+        return;
+    }
+
+    let native_state = app.state::<yaof_core::NativePluginState>();
+    let result = tauri::async_runtime::block_on(async {
+        native_state.0.write().await.reload_dev_plugin(path)
+    });
+
+    match result {
+        Some(Ok(plugin_id)) => {
+            tracing::info!("Reloaded dev native plugin {} after rebuild", plugin_id);
+        }
+        Some(Err(e)) => {
+            tracing::error!("Failed to reload dev native plugin {:?}: {}", path, e);
+        }
+        None => {}
+    }
+}
+
+/// Check if a webview belongs to a plugin
+/// The URL format is: yaof-plugin://{plugin-id}/...
+fn is_plugin_window<R: Runtime>(window: &tauri::WebviewWindow<R>, plugin_id: &str) -> bool {
+    match window.url() {
+        Ok(url) => {
+            let url_str = url.to_string();
+            url_str.contains(&format!("yaof-plugin://{}/", plugin_id))
+                || url_str.contains(&format!("yaof-plugin://{}", plugin_id))
+        }
+        Err(_) => false,
+    }
+}
+
+/// Drop the services a window provided before reloading it, the reloaded page
+/// registers them again
+fn unregister_window_services<R: Runtime>(app: &AppHandle<R>, label: &str) {
+    if let Some(state) = app.try_state::<yaof_core::OverlayState>()
+        && let Ok(mut manager) = state.0.lock()
+    {
+        manager.registry_mut().unregister_window(label, app);
+    }
+}
+
 /// Reload all overlay webviews for a specific plugin
 fn reload_plugin_overlays<R: Runtime>(app: &AppHandle<R>, plugin_id: &str) {
     // Get all windows and find ones that belong to this plugin
-    // Window labels for plugin overlays typically include the plugin ID
     for (label, window) in app.webview_windows() {
-        // Check if this window's URL contains the plugin ID
-        // The URL format is: yaof-plugin://{plugin-id}/...
-        if let Ok(url) = window.url() {
-            let url_str = url.to_string();
-            if url_str.contains(&format!("yaof-plugin://{}/", plugin_id))
-                || url_str.contains(&format!("yaof-plugin://{}", plugin_id))
-            {
-                println!("[HotReload] Reloading overlay: {}", label);
+        if is_plugin_window(&window, plugin_id) {
+            tracing::info!("Reloading overlay: {}", label);
+            unregister_window_services(app, &label);
 
-                // Execute JavaScript to reload the page
-                if let Err(e) = window.eval("location.reload()") {
-                    eprintln!("[HotReload] Failed to reload {}: {}", label, e);
-                }
+            // Execute JavaScript to reload the page
+            if let Err(e) = window.eval("location.reload()") {
+                tracing::error!("Failed to reload {}: {}", label, e);
+            }
+        }
+    }
+}
+
+/// Reload every overlay webview (all windows except settings)
+pub fn reload_all_overlays<R: Runtime>(app: &AppHandle<R>) {
+    for (label, window) in app.webview_windows() {
+        if label == yaof_core::SETTINGS_WINDOW_LABEL {
+            continue;
+        }
+
+        tracing::info!("Reloading overlay: {}", label);
+        unregister_window_services(app, &label);
+        if let Err(e) = window.eval("location.reload()") {
+            tracing::error!("Failed to reload {}: {}", label, e);
+        }
+    }
+}
+
+/// Send a `yaof:plugin:changed` event to all overlay webviews for a specific plugin
+fn notify_plugin_overlays<R: Runtime>(app: &AppHandle<R>, plugin_id: &str, paths: &[PathBuf]) {
+    let payload = PluginChangedEvent {
+        plugin_id: plugin_id.to_string(),
+        paths: paths
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect(),
+    };
+
+    for (label, window) in app.webview_windows() {
+        if is_plugin_window(&window, plugin_id) {
+            tracing::info!("Sending HMR update to overlay: {}", label);
+
+            if let Err(e) = app.emit_to(label.as_str(), PLUGIN_CHANGED_EVENT, &payload) {
+                tracing::error!("Failed to notify {}: {}", label, e);
+            }
+
+            // Overlays without the SDK's listener (no OverlayProvider) still need a reload
+            if let Err(e) = window.eval("if (!window.__YAOF_HMR__) location.reload()") {
+                tracing::error!("Failed to reload {}: {}", label, e);
             }
         }
     }
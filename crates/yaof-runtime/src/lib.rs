@@ -1,6 +1,7 @@
 mod embedded;
 mod hot_reload;
 mod protocol;
+mod schedule;
 mod tray;
 
 use std::path::Path;
@@ -16,15 +17,15 @@ pub use protocol::PLUGIN_PROTOCOL;
 
 /// Helper function to spawn enabled overlays
 async fn spawn_overlays(app_handle: &AppHandle) {
-    println!("[YAOF] Spawning enabled overlays...");
-    match yaof_core::AutostartManager::spawn_enabled_overlays(app_handle) {
+    tracing::info!("Spawning enabled overlays...");
+    match yaof_core::AutostartManager::spawn_enabled_overlays(app_handle).await {
         Ok(spawned) => {
             if !spawned.is_empty() {
-                println!("[YAOF] Auto-started {} overlay(s)", spawned.len());
+                tracing::info!("Auto-started {} overlay(s)", spawned.len());
             }
         }
         Err(e) => {
-            eprintln!("[YAOF] Failed to auto-start overlays: {}", e);
+            tracing::error!("Failed to auto-start overlays: {}", e);
         }
     }
 }
@@ -65,19 +66,49 @@ pub fn should_extract_plugins(plugins_dir: &Path) -> bool {
         .exists()
 }
 
+/// Set YAOF_HOME from a `--yaof-home <dir>` or `--yaof-home=<dir>` argument
+/// and YAOF_SAFE_MODE from `--safe-mode`
+fn apply_args() {
+    let mut args = std::env::args().skip(1);
+    let mut home = None;
+    let mut safe_mode = false;
+    while let Some(arg) = args.next() {
+        if arg == "--yaof-home" {
+            home = args.next();
+        } else if let Some(value) = arg.strip_prefix("--yaof-home=") {
+            home = Some(value.to_string());
+        } else if arg == yaof_core::SAFE_MODE_ARG {
+            safe_mode = true;
+        }
+    }
+
+    // SAFETY: called at the very start of `run`, before any other threads are spawned
+    if let Some(home) = home {
+        unsafe { std::env::set_var(yaof_core::HOME_ENV_VAR, home) };
+    }
+    if safe_mode {
+        unsafe { std::env::set_var(yaof_core::SAFE_MODE_ENV_VAR, "1") };
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // `--yaof-home <dir>` is shorthand for YAOF_HOME, so profiles can be picked per launch,
+    // and `--safe-mode` for YAOF_SAFE_MODE=1
+    apply_args();
+
+    // Install the log subscriber before anything else logs (filter via YAOF_LOG)
+    yaof_core::logging::init();
+
     // Extract embedded plugins on startup if needed
-    if let Some(home) = dirs::home_dir() {
-        let plugins_dir = home.join(".yaof").join("plugins");
-
-        if should_extract_plugins(&plugins_dir) {
-            println!("Extracting embedded plugins to {:?}", plugins_dir);
-            if let Err(e) = extract_embedded_plugins(&plugins_dir) {
-                eprintln!("Warning: Failed to extract embedded plugins: {}", e);
-            } else {
-                println!("Successfully extracted embedded plugins");
-            }
+    if let Some(plugins_dir) = yaof_core::yaof_plugins_dir()
+        && should_extract_plugins(&plugins_dir)
+    {
+        tracing::info!("Extracting embedded plugins to {:?}", plugins_dir);
+        if let Err(e) = extract_embedded_plugins(&plugins_dir) {
+            tracing::warn!("Failed to extract embedded plugins: {}", e);
+        } else {
+            tracing::info!("Successfully extracted embedded plugins");
         }
     }
 
@@ -96,40 +127,43 @@ pub fn run() {
         .setup(|app| {
             tray::setup_tray(app)?;
 
+            // Safe mode only brings up the settings window, to disable a broken plugin
+            if yaof_core::is_safe_mode() {
+                tracing::warn!("Starting in safe mode: plugins and overlays are not loaded");
+                yaof_core::open_settings_window(app.handle())?;
+            }
+
             // Start hot reload watcher in dev mode
             hot_reload::start_hot_reload_watcher(app.handle().clone());
+            hot_reload::start_native_dev_watcher(app.handle().clone());
 
             // Initialize and start services, then spawn overlays
             // The yaof_core plugin already initializes the native plugin manager and system services
             // We just need to:
             // 1. Load native plugins from installed plugins (in addition to standalone ones)
-            // 2. Wait for services to be ready
-            // 3. Spawn overlays
+            // 2. Wait for system services to be ready
+            // 3. Spawn overlays and announce readiness
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
                 // Step 1: Load native plugins from installed plugins
                 // This supplements the standalone native plugins already loaded by yaof_core
-                println!("[YAOF] Loading native plugins from installed plugins...");
+                tracing::info!("Loading native plugins from installed plugins...");
 
                 let native_state = app_handle.state::<yaof_core::NativePluginState>();
                 let plugin_state = app_handle.state::<yaof_core::PluginState>();
 
+                // Standalone plugins are discovered first, so both loads don't race
+                native_state.0.wait_ready().await;
+
                 // Load native components from installed plugins
                 // Note: We need to be careful not to hold the MutexGuard across await points
                 let load_result = {
-                    let mut native_manager = native_state.0.write().await;
+                    // Takes the native manager before the plugin loader, see NativePluginState
+                    let (mut native_manager, mut plugin_loader) =
+                        native_state.0.write_with_loader(&plugin_state.0).await;
 
-                    // Try to lock the plugin loader - if it fails, we'll skip native loading
-                    match plugin_state.0.lock() {
-                        Ok(mut plugin_loader) => {
-                            // Do all the synchronous work here, before any await
-                            native_manager.load_from_installed_plugins(&mut plugin_loader)
-                        }
-                        Err(e) => {
-                            eprintln!("[YAOF] Failed to lock plugin loader: {}", e);
-                            Ok(vec![]) // Return empty vec to continue
-                        }
-                    }
+                    // Do all the synchronous work here, before any await
+                    native_manager.load_from_installed_plugins(&mut plugin_loader)
                     // MutexGuard is dropped here when the block ends
                 };
 
@@ -137,24 +171,28 @@ pub fn run() {
                 match load_result {
                     Ok(loaded) => {
                         if !loaded.is_empty() {
-                            println!(
-                                "[YAOF] Loaded {} native plugin(s) from installed: {:?}",
+                            tracing::info!(
+                                "Loaded {} native plugin(s) from installed: {:?}",
                                 loaded.len(),
                                 loaded
                             );
                         }
                     }
                     Err(e) => {
-                        eprintln!("[YAOF] Failed to load native plugins from installed: {}", e);
+                        tracing::error!("Failed to load native plugins from installed: {}", e);
                     }
                 }
 
-                // Step 2: Wait for services to initialize
-                // Give native plugins time to start and emit initial data
-                tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+                // Step 2: Wait for the first system status, so overlays start with data
+                let system_state = app_handle.state::<yaof_core::SystemServiceState>();
+                system_state.0.wait_ready().await;
 
                 // Step 3: Spawn enabled overlays
                 spawn_overlays(&app_handle).await;
+                yaof_core::mark_ready(&app_handle);
+
+                // Step 4: Keep scheduled overlays in sync with the time of day
+                schedule::start_schedule_task(app_handle);
             });
 
             Ok(())
@@ -162,7 +200,7 @@ pub fn run() {
         .on_window_event(|window, event| {
             if let WindowEvent::CloseRequested { api, .. } = event {
                 // Only intercept close for the settings window
-                if window.label() == "settings" {
+                if window.label() == yaof_core::SETTINGS_WINDOW_LABEL {
                     api.prevent_close();
 
                     let window_clone = window.clone();
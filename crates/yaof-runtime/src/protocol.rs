@@ -1,12 +1,11 @@
 //! Custom protocol handler for serving plugin assets.
 //!
 //! This module registers a `yaof-plugin://` protocol that maps to plugin files
-//! in the ~/.yaof/plugins/ directory.
+//! in the plugins directory (`<yaof home>/plugins`, see `yaof_core::yaof_plugins_dir`).
 //!
 //! URL format: yaof-plugin://{plugin-id}/{path}
-//! Example: yaof-plugin://topbar/index.html -> ~/.yaof/plugins/topbar/dist/index.html
+//! Example: yaof-plugin://topbar/index.html -> <yaof home>/plugins/topbar/dist/index.html
 
-use std::path::PathBuf;
 use tauri::{
     AppHandle, Runtime,
     http::{Request, Response, StatusCode},
@@ -15,11 +14,6 @@ use tauri::{
 /// The custom protocol scheme for plugin assets
 pub const PLUGIN_PROTOCOL: &str = "yaof-plugin";
 
-/// Get the plugins directory path
-fn get_plugins_dir() -> Option<PathBuf> {
-    dirs::home_dir().map(|h| h.join(".yaof").join("plugins"))
-}
-
 /// Determine MIME type from file extension
 fn get_mime_type(path: &str) -> &'static str {
     let ext = path.rsplit('.').next().unwrap_or("");
@@ -93,7 +87,7 @@ pub fn handle_plugin_protocol<R: Runtime>(
     }
 
     // Build the full filesystem path
-    let plugins_dir = match get_plugins_dir() {
+    let plugins_dir = match yaof_core::yaof_plugins_dir() {
         Some(dir) => dir,
         None => {
             return Response::builder()
@@ -104,7 +98,7 @@ pub fn handle_plugin_protocol<R: Runtime>(
         }
     };
 
-    // The file is in: ~/.yaof/plugins/{plugin-id}/dist/{file_path}
+    // The file is in: {plugins_dir}/{plugin-id}/dist/{file_path}
     // The plugin directory might be a symlink (for development), so we need to
     // check that the plugin exists first, then resolve the full path
     let plugin_dir = plugins_dir.join(host);
@@ -124,7 +118,7 @@ pub fn handle_plugin_protocol<R: Runtime>(
     let canonical_file = match full_path.canonicalize() {
         Ok(p) => p,
         Err(e) => {
-            eprintln!("[Protocol] File not found: {} ({})", full_path.display(), e);
+            tracing::warn!("File not found: {} ({})", full_path.display(), e);
             return Response::builder()
                 .status(StatusCode::NOT_FOUND)
                 .header("Content-Type", "text/plain")
@@ -171,11 +165,7 @@ pub fn handle_plugin_protocol<R: Runtime>(
                 .unwrap()
         }
         Err(e) => {
-            eprintln!(
-                "[Protocol] Failed to read file: {} ({})",
-                canonical_file.display(),
-                e
-            );
+            tracing::error!("Failed to read file: {} ({})", canonical_file.display(), e);
             Response::builder()
                 .status(StatusCode::INTERNAL_SERVER_ERROR)
                 .header("Content-Type", "text/plain")
@@ -0,0 +1,22 @@
+//! Scheduled overlay visibility.
+//!
+//! Periodically shows and hides overlays that have a `schedule` in their
+//! settings, based on the system's local time.
+
+use std::time::Duration;
+
+use tauri::AppHandle;
+
+/// How often overlay schedules are re-evaluated
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Start the background task applying overlay schedules
+pub fn start_schedule_task(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(SCHEDULE_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            yaof_core::overlay::sync_scheduled_visibility(&app);
+        }
+    });
+}
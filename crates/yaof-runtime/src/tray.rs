@@ -1,43 +1,38 @@
 use tauri::{
-    App, Manager, Runtime, WebviewUrl, WebviewWindowBuilder,
+    App, AppHandle, Emitter, Listener, Manager, WebviewWindow, Wry,
     image::Image,
-    menu::{MenuBuilder, MenuItemBuilder, PredefinedMenuItem, SubmenuBuilder},
+    menu::{
+        CheckMenuItemBuilder, Menu, MenuBuilder, MenuItemBuilder, PredefinedMenuItem,
+        SubmenuBuilder,
+    },
     tray::{TrayIconBuilder, TrayIconEvent},
 };
+use yaof_core::{
+    OVERLAY_CLOSED_EVENT, OVERLAY_SPAWNED_EVENT, PLUGINS_CHANGED_EVENT, READY_EVENT,
+    open_settings_window,
+};
+
+use crate::hot_reload;
 
 const TRAY_ICON: &[u8] = include_bytes!("../icons/32x32.png");
 
-pub fn setup_tray(app: &App) -> Result<(), Box<dyn std::error::Error>> {
-    // Build menu items
-    let show_all = MenuItemBuilder::with_id("show_all", "Show All Overlays").build(app)?;
-    let hide_all = MenuItemBuilder::with_id("hide_all", "Hide All Overlays").build(app)?;
-    let separator1 = PredefinedMenuItem::separator(app)?;
+/// ID of the app's tray icon, used to swap in a rebuilt menu
+const TRAY_ID: &str = "main";
 
-    // Plugins submenu
-    let plugins_submenu = SubmenuBuilder::new(app, "Plugins")
-        .item(&MenuItemBuilder::with_id("plugins_manage", "Manage Plugins...").build(app)?)
-        .build()?;
+/// Prefix of the per-overlay menu item IDs ("overlay:<overlay-id>")
+const OVERLAY_ITEM_PREFIX: &str = "overlay:";
 
-    let separator2 = PredefinedMenuItem::separator(app)?;
-    let settings = MenuItemBuilder::with_id("settings", "Settings...").build(app)?;
-    let quit = MenuItemBuilder::with_id("quit", "Quit YAOF").build(app)?;
+/// Prefix of the menu item IDs plugins declare ("plugin:<plugin-id>:<item-id>")
+const PLUGIN_ITEM_PREFIX: &str = "plugin:";
 
-    // Build the menu
-    let menu = MenuBuilder::new(app)
-        .item(&show_all)
-        .item(&hide_all)
-        .item(&separator1)
-        .item(&plugins_submenu)
-        .item(&separator2)
-        .item(&settings)
-        .item(&quit)
-        .build()?;
+pub fn setup_tray(app: &App) -> Result<(), Box<dyn std::error::Error>> {
+    let menu = build_menu(app.handle())?;
 
     // Load tray icon
     let icon = Image::from_bytes(TRAY_ICON)?;
 
     // Create tray
-    let _tray = TrayIconBuilder::new()
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
         .icon(icon)
         .menu(&menu)
         .tooltip("YAOF - Yet Another Overlay Framework")
@@ -52,56 +47,294 @@ pub fn setup_tray(app: &App) -> Result<(), Box<dyn std::error::Error>> {
         })
         .build(app)?;
 
+    // Keep the per-overlay and plugin entries in sync with open overlays and installed
+    // plugins. Plugins are first scanned after the tray is built, so refresh once ready.
+    for event in [
+        OVERLAY_SPAWNED_EVENT,
+        OVERLAY_CLOSED_EVENT,
+        PLUGINS_CHANGED_EVENT,
+        READY_EVENT,
+    ] {
+        let app_handle = app.handle().clone();
+        app.listen(event, move |_| refresh_tray_menu(&app_handle));
+    }
+
     Ok(())
 }
 
-/// Opens the settings window, or focuses it if already open
-pub fn open_settings_window<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<(), tauri::Error> {
-    // Check if settings window already exists
-    if let Some(window) = app.get_webview_window("settings") {
-        // Window exists - show and focus it
-        window.show()?;
-        window.set_focus()?;
-        return Ok(());
+/// Build the tray menu, including a checkable entry per open overlay
+fn build_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    // Build menu items
+    let show_all = MenuItemBuilder::with_id("show_all", "Show All Overlays").build(app)?;
+    let hide_all = MenuItemBuilder::with_id("hide_all", "Hide All Overlays").build(app)?;
+    let separator1 = PredefinedMenuItem::separator(app)?;
+
+    // Overlays submenu
+    let mut overlays_submenu = SubmenuBuilder::new(app, "Overlays");
+    let overlays = list_overlay_windows(app);
+    if overlays.is_empty() {
+        overlays_submenu = overlays_submenu.item(
+            &MenuItemBuilder::with_id("overlays_none", "No Open Overlays")
+                .enabled(false)
+                .build(app)?,
+        );
     }
+    for (id, window) in overlays {
+        let item = CheckMenuItemBuilder::with_id(format!("{}{}", OVERLAY_ITEM_PREFIX, id), &id)
+            .checked(window.is_visible().unwrap_or(false))
+            .build(app)?;
+        overlays_submenu = overlays_submenu.item(&item);
+    }
+    let overlays_submenu = overlays_submenu.build()?;
 
-    // Create new settings window
-    let _window = WebviewWindowBuilder::new(app, "settings", WebviewUrl::App("index.html".into()))
-        .title("YAOF Settings")
-        .inner_size(900.0, 650.0)
-        .min_inner_size(700.0, 500.0)
-        .center()
-        .decorations(true)
-        .resizable(true)
-        .visible(true)
-        .build()?;
+    // Plugins submenu, followed by the items plugins declare in their manifest
+    let mut plugins_submenu = SubmenuBuilder::new(app, "Plugins")
+        .item(&MenuItemBuilder::with_id("plugins_manage", "Manage Plugins...").build(app)?);
+    let plugin_items = list_plugin_tray_items(app);
+    if !plugin_items.is_empty() {
+        plugins_submenu = plugins_submenu.separator();
+    }
+    for (plugin_id, item) in plugin_items {
+        let id = format!("{}{}:{}", PLUGIN_ITEM_PREFIX, plugin_id, item.id);
+        plugins_submenu =
+            plugins_submenu.item(&MenuItemBuilder::with_id(id, &item.label).build(app)?);
+    }
+    let plugins_submenu = plugins_submenu.build()?;
 
-    Ok(())
+    let separator2 = PredefinedMenuItem::separator(app)?;
+    let settings = MenuItemBuilder::with_id("settings", "Settings...").build(app)?;
+    let safe_mode = if yaof_core::is_safe_mode() {
+        MenuItemBuilder::with_id("restart_normal", "Restart Normally").build(app)?
+    } else {
+        MenuItemBuilder::with_id("restart_safe_mode", "Restart in Safe Mode").build(app)?
+    };
+    let quit = MenuItemBuilder::with_id("quit", "Quit YAOF").build(app)?;
+
+    // Build the menu
+    let mut menu = MenuBuilder::new(app)
+        .item(&show_all)
+        .item(&hide_all)
+        .item(&overlays_submenu)
+        .item(&separator1)
+        .item(&plugins_submenu);
+
+    // Development helpers, only shown with YAOF_DEV=1
+    if hot_reload::is_dev_mode() {
+        menu = menu
+            .item(&MenuItemBuilder::with_id("dev_reload_plugins", "Reload Plugins").build(app)?)
+            .item(
+                &MenuItemBuilder::with_id("dev_toggle_click_through", "Toggle Click-Through (All)")
+                    .build(app)?,
+            );
+    }
+
+    menu.item(&separator2)
+        .item(&settings)
+        .item(&safe_mode)
+        .item(&quit)
+        .build()
 }
 
-fn handle_menu_event<R: Runtime>(app: &tauri::AppHandle<R>, id: &str) {
-    match id {
-        "show_all" => {
-            // Show all overlay windows (except settings)
-            for (label, window) in app.webview_windows() {
-                if label != "settings" {
-                    let _ = window.show();
+/// Get the open overlays, sorted by ID
+fn list_overlay_windows(app: &AppHandle) -> Vec<(String, WebviewWindow)> {
+    let overlay_state = app.state::<yaof_core::OverlayState>();
+    let Ok(manager) = overlay_state.0.lock() else {
+        return Vec::new();
+    };
+
+    let mut overlays: Vec<(String, WebviewWindow)> = manager
+        .list_overlays()
+        .iter()
+        .map(|o| (o.config.id.clone(), o.window.clone()))
+        .collect();
+    overlays.sort_by(|a, b| a.0.cmp(&b.0));
+    overlays
+}
+
+/// Get the tray items of installed plugins, sorted by plugin ID.
+/// None in safe mode, where plugins aren't loaded.
+fn list_plugin_tray_items(app: &AppHandle) -> Vec<(String, yaof_core::TrayMenuItem)> {
+    if yaof_core::is_safe_mode() {
+        return Vec::new();
+    }
+
+    let plugin_state = app.state::<yaof_core::PluginState>();
+    let Ok(loader) = plugin_state.0.lock() else {
+        return Vec::new();
+    };
+
+    let mut plugins = loader.list_plugins();
+    plugins.sort_by(|a, b| a.manifest.id.cmp(&b.manifest.id));
+    plugins
+        .iter()
+        .flat_map(|p| {
+            p.manifest
+                .tray
+                .iter()
+                .map(|item| (p.manifest.id.clone(), item.clone()))
+        })
+        .collect()
+}
+
+/// Tell a plugin one of its tray items was clicked: its overlays get
+/// `yaof:plugin:{id}:tray` and its native components a `tray:<item-id>` message
+fn send_tray_click(app: &AppHandle, plugin_id: &str, item_id: &str) {
+    if let Err(e) = app.emit(&yaof_core::plugin_tray_event(plugin_id), item_id) {
+        tracing::warn!("Failed to emit tray click for {}: {}", plugin_id, e);
+    }
+
+    let app = app.clone();
+    let plugin_id = plugin_id.to_string();
+    let msg_type = format!("{}{}", yaof_core::TRAY_MESSAGE_PREFIX, item_id);
+    tauri::async_runtime::spawn(async move {
+        let native_state = app.state::<yaof_core::NativePluginState>();
+        native_state
+            .0
+            .write()
+            .await
+            .send_message_to_components(&plugin_id, &msg_type, &[]);
+    });
+}
+
+/// Rebuild the tray menu so the overlay entries match the open overlays.
+/// Runs on a separate task because lifecycle events are emitted while the
+/// overlay manager is still locked.
+fn refresh_tray_menu(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let Some(tray) = app.tray_by_id(TRAY_ID) else {
+            return;
+        };
+
+        match build_menu(&app) {
+            Ok(menu) => {
+                if let Err(e) = tray.set_menu(Some(menu)) {
+                    tracing::error!("Failed to update tray menu: {}", e);
                 }
             }
+            Err(e) => {
+                tracing::error!("Failed to build tray menu: {}", e);
+            }
         }
-        "hide_all" => {
-            // Hide all overlay windows (except settings)
-            for (label, window) in app.webview_windows() {
-                if label != "settings" {
-                    let _ = window.hide();
-                }
+    });
+}
+
+/// Rescan plugins, load new native components, re-run autostart and reload every overlay
+fn reload_plugins(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tracing::info!("Reloading plugins...");
+
+        let native_state = app.state::<yaof_core::NativePluginState>();
+        let plugin_state = app.state::<yaof_core::PluginState>();
+        {
+            let (mut native_manager, mut plugin_loader) =
+                native_state.0.write_with_loader(&plugin_state.0).await;
+            if let Err(e) = native_manager.load_from_installed_plugins(&mut plugin_loader) {
+                tracing::error!("Failed to load native plugins: {}", e);
             }
         }
+
+        if let Err(e) = yaof_core::AutostartManager::refresh_overlays(&app) {
+            tracing::error!("Failed to refresh overlays: {}", e);
+        }
+
+        hot_reload::reload_all_overlays(&app);
+        refresh_tray_menu(&app);
+    });
+}
+
+/// Enable click-through on every overlay, or disable it if all already have it
+fn toggle_click_through_all(app: &AppHandle) {
+    let overlay_state = app.state::<yaof_core::OverlayState>();
+    let Ok(mut manager) = overlay_state.0.lock() else {
+        return;
+    };
+
+    let ids: Vec<String> = manager
+        .list_overlays()
+        .iter()
+        .map(|o| o.config.id.clone())
+        .collect();
+    let enabled = !manager
+        .list_overlays()
+        .iter()
+        .all(|o| o.config.click_through);
+
+    for id in ids {
+        if let Err(e) = manager.set_click_through(&id, enabled) {
+            tracing::error!("Failed to set click-through on {}: {}", id, e);
+        }
+    }
+}
+
+/// Show or hide every overlay, skipping those excluded from bulk toggles
+fn set_visible_all(app: &AppHandle, visible: bool) {
+    let overlay_state = app.state::<yaof_core::OverlayState>();
+    let Ok(manager) = overlay_state.0.lock() else {
+        return;
+    };
+
+    if let Err(e) = manager.set_visible_all(visible) {
+        tracing::error!("Failed to set overlay visibility: {}", e);
+    }
+}
+
+/// Toggle the visibility of a single overlay from its tray entry
+fn toggle_overlay(app: &AppHandle, id: &str) {
+    let overlay_state = app.state::<yaof_core::OverlayState>();
+    let Ok(manager) = overlay_state.0.lock() else {
+        return;
+    };
+
+    let Some(overlay) = manager.get_overlay(id) else {
+        return;
+    };
+
+    let visible = overlay.window.is_visible().unwrap_or(false);
+    if let Err(e) = manager.set_visible(id, !visible) {
+        tracing::error!("Failed to toggle overlay {}: {}", id, e);
+    }
+}
+
+/// Relaunch yaof in or out of safe mode
+fn restart(app: &AppHandle, safe_mode: bool) {
+    if let Err(e) = yaof_core::relaunch(app, safe_mode) {
+        tracing::error!("Failed to restart yaof: {}", e);
+    }
+}
+
+fn handle_menu_event(app: &AppHandle, id: &str) {
+    if let Some(overlay_id) = id.strip_prefix(OVERLAY_ITEM_PREFIX) {
+        toggle_overlay(app, overlay_id);
+        return;
+    }
+
+    if let Some(item) = id.strip_prefix(PLUGIN_ITEM_PREFIX) {
+        if let Some((plugin_id, item_id)) = item.split_once(':') {
+            send_tray_click(app, plugin_id, item_id);
+        }
+        return;
+    }
+
+    match id {
+        "show_all" => {
+            set_visible_all(app, true);
+            refresh_tray_menu(app);
+        }
+        "hide_all" => {
+            set_visible_all(app, false);
+            refresh_tray_menu(app);
+        }
+        "dev_reload_plugins" => reload_plugins(app),
+        "dev_toggle_click_through" => toggle_click_through_all(app),
         "plugins_manage" | "settings" => {
             if let Err(e) = open_settings_window(app) {
-                eprintln!("Failed to open settings window: {:?}", e);
+                tracing::error!("Failed to open settings window: {:?}", e);
             }
         }
+        "restart_safe_mode" => restart(app, true),
+        "restart_normal" => restart(app, false),
         "quit" => {
             app.exit(0);
         }
@@ -1,5 +1,13 @@
 import type { PluginManifest } from "@/types";
-import { createContext, useContext, useMemo, type ReactNode } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import {
+  createContext,
+  useContext,
+  useEffect,
+  useMemo,
+  type ReactNode,
+} from "react";
 
 export interface OverlayContextValue {
   pluginId: string;
@@ -9,6 +17,48 @@ export interface OverlayContextValue {
 
 const OverlayContext = createContext<OverlayContextValue | null>(null);
 
+/** Payload of the `yaof:plugin:changed` event sent in YAOF_HMR=1 mode */
+interface PluginChangedEvent {
+  pluginId: string;
+  paths: string[];
+}
+
+/** Set while a `yaof:plugin:changed` listener is installed, checked by the runtime */
+const HMR_FLAG = "__YAOF_HMR__";
+
+/**
+ * Apply changed dist files in place: stylesheets are re-fetched without
+ * reloading, anything else (JS bundles) falls back to a full reload.
+ */
+function applyPluginChange(paths: string[]) {
+  const fileName = (path: string) => path.split(/[\\/]/).pop() ?? path;
+  const changed = paths.filter((path) => !path.endsWith(".map"));
+  const styles = changed.filter((path) => path.endsWith(".css"));
+
+  if (styles.length < changed.length) {
+    location.reload();
+    return;
+  }
+
+  const links = Array.from(
+    document.querySelectorAll<HTMLLinkElement>('link[rel="stylesheet"]')
+  );
+  for (const path of styles) {
+    const name = fileName(path);
+    const link = links.find(
+      (link) => fileName(new URL(link.href).pathname) === name
+    );
+    if (!link) {
+      location.reload();
+      return;
+    }
+
+    const url = new URL(link.href);
+    url.searchParams.set("t", Date.now().toString());
+    link.href = url.toString();
+  }
+}
+
 export interface OverlayProviderProps {
   children: ReactNode;
   manifest: PluginManifest;
@@ -29,6 +79,42 @@ export function OverlayProvider({
     [manifest, overlayId]
   );
 
+  // Overlays with a `readyTimeoutMs` stay hidden until the page has loaded,
+  // which avoids flashing an empty window on spawn. Animation frames don't run
+  // in hidden windows, so this waits on the load event instead
+  useEffect(() => {
+    const ready = () => {
+      invoke("plugin:yaof|overlay_ready", {
+        id: getCurrentWebviewWindow().label,
+      }).catch(() => {
+        // Not an overlay window (e.g. settings), nothing to show
+      });
+    };
+
+    if (document.readyState === "complete") {
+      ready();
+      return;
+    }
+    window.addEventListener("load", ready, { once: true });
+    return () => window.removeEventListener("load", ready);
+  }, []);
+
+  // YAOF_HMR=1 sends changed files instead of reloading the page
+  useEffect(() => {
+    const flags = window as unknown as Record<string, boolean>;
+    flags[HMR_FLAG] = true;
+
+    const unlisten = getCurrentWebviewWindow().listen<PluginChangedEvent>(
+      "yaof:plugin:changed",
+      (event) => applyPluginChange(event.payload.paths)
+    );
+
+    return () => {
+      delete flags[HMR_FLAG];
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   return (
     <OverlayContext.Provider value={value}>{children}</OverlayContext.Provider>
   );
@@ -0,0 +1,23 @@
+/** Error a yaof command rejects with. Branch on `code`, show `message` to the user. */
+export type CommandError = {
+  /** Stable identifier, e.g. `plugin_not_found`, `window_not_found` or `invalid_argument` */
+  code: string;
+  message: string;
+};
+
+/** Check whether a rejected `invoke` value is a {@link CommandError} */
+export function isCommandError(err: unknown): err is CommandError {
+  return (
+    typeof err === "object" &&
+    err !== null &&
+    typeof (err as CommandError).code === "string" &&
+    typeof (err as CommandError).message === "string"
+  );
+}
+
+/** Turn a rejected `invoke` value into an `Error`, keeping the command's message */
+export function toError(err: unknown): Error {
+  if (err instanceof Error) return err;
+  if (isCommandError(err)) return new Error(err.message, { cause: err });
+  return new Error(String(err));
+}
@@ -2,6 +2,7 @@ export { useOverlay } from "./use-overlay";
 export { usePluginStore } from "./use-plugin-store";
 export { usePluginConfig } from "./use-plugin-config";
 export { useService, useProvideService } from "./use-service";
+export { useOverlayMessages } from "./use-overlay-message";
 export { usePosition } from "./use-position";
 export { usePluginSettings } from "./use-settings";
 export { useOverlaySettings } from "./use-overlay-settings";
@@ -18,6 +19,10 @@ export type { UseOverlayReturn } from "./use-overlay";
 export type { UsePluginStoreReturn } from "./use-plugin-store";
 export type { UsePluginConfigReturn } from "./use-plugin-config";
 export type { UseServiceReturn, UseProvideServiceReturn } from "./use-service";
+export type {
+  UseOverlayMessagesReturn,
+  OverlayMessage,
+} from "./use-overlay-message";
 export type {
   UsePositionReturn,
   PositionPreset,
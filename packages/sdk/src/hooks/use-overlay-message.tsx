@@ -0,0 +1,56 @@
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { useCallback, useEffect, useRef } from "react";
+
+export interface OverlayMessage<T = unknown> {
+  /** Window label of the overlay that sent the message */
+  from: string;
+  type: string;
+  payload: T;
+}
+
+export interface UseOverlayMessagesReturn {
+  send: (targetOverlayId: string, type: string, payload?: unknown) => Promise<void>;
+}
+
+/**
+ * Exchange messages directly with other overlays.
+ * `handler` receives messages sent to this overlay, optionally only those of `type`.
+ * Targets are window labels (e.g. "my-plugin-panel").
+ */
+export function useOverlayMessages<T = unknown>(
+  handler?: (message: OverlayMessage<T>) => void,
+  type?: string
+): UseOverlayMessagesReturn {
+  const handlerRef = useRef(handler);
+  handlerRef.current = handler;
+
+  useEffect(() => {
+    const window = getCurrentWebviewWindow();
+    const unlisten = window.listen<OverlayMessage<T>>(
+      `yaof:overlay:${window.label}:message`,
+      (event) => {
+        if (type === undefined || event.payload.type === type) {
+          handlerRef.current?.(event.payload);
+        }
+      }
+    );
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [type]);
+
+  const send = useCallback(
+    async (targetOverlayId: string, type: string, payload: unknown = null) => {
+      await invoke("plugin:yaof|overlay_message", {
+        targetOverlayId,
+        msgType: type,
+        payload,
+      });
+    },
+    []
+  );
+
+  return { send };
+}
@@ -1,8 +1,11 @@
-import { load, Store } from "@tauri-apps/plugin-store";
+import { Store } from "@tauri-apps/plugin-store";
+import { loadPluginStore } from "@/store";
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import { useCallback, useEffect, useRef, useState } from "react";
 import { useOverlayContext } from "../context";
 import type { PositionPreset } from "./use-position";
+import type { DesktopLevel } from "@/types";
 
 export interface OverlaySettings {
   enabled: boolean;
@@ -14,6 +17,8 @@ export interface OverlaySettings {
   opacity: number;
   clickThrough: boolean;
   alwaysOnTop: boolean;
+  /** Desktop layer the overlay is pinned to, null floats it normally */
+  desktopLevel: DesktopLevel | null;
 }
 
 // Minimal fallback defaults - these are only used if overlay.json doesn't exist
@@ -27,6 +32,7 @@ const FALLBACK_DEFAULTS: OverlaySettings = {
   opacity: 100,
   clickThrough: false,
   alwaysOnTop: true,
+  desktopLevel: null,
 };
 
 // Debounce delay in milliseconds
@@ -106,7 +112,7 @@ export function useOverlaySettings(): UseOverlaySettingsReturn {
 
     async function loadSettings() {
       try {
-        const s = await load(storeName);
+        const s = await loadPluginStore(storeName);
         if (!mounted) return;
         setStore(s);
 
@@ -134,15 +140,20 @@ export function useOverlaySettings(): UseOverlaySettingsReturn {
           clickThrough:
             overlayDef?.clickThrough ?? FALLBACK_DEFAULTS.clickThrough,
           alwaysOnTop: FALLBACK_DEFAULTS.alwaysOnTop,
+          desktopLevel:
+            overlayDef?.desktopLevel ?? FALLBACK_DEFAULTS.desktopLevel,
         };
 
         setManifestDefaults(defaults);
 
         // Load stored settings, using manifest defaults as fallback
+        // An explicit null desktopLevel turns off the manifest's desktop level
         const loaded: OverlaySettings = { ...defaults };
         for (const key of Object.keys(defaults) as (keyof OverlaySettings)[]) {
           const value = await s.get<unknown>(key);
-          if (value !== null && value !== undefined) {
+          if (value === null && key === "desktopLevel") {
+            loaded.desktopLevel = null;
+          } else if (value !== null && value !== undefined) {
             (loaded as any)[key] = value;
           }
         }
@@ -169,6 +180,42 @@ export function useOverlaySettings(): UseOverlaySettingsReturn {
     };
   }, [storeName, overlayId, manifest]);
 
+  // Follow resizes made by dragging the window edges
+  // The backend has already saved the new size to the store
+  useEffect(() => {
+    let mounted = true;
+    let unlistenFn: UnlistenFn | null = null;
+
+    async function setupListener() {
+      try {
+        unlistenFn = await listen<{
+          id: string;
+          width: number;
+          height: number;
+        }>("yaof:overlay:resized", (event) => {
+          if (!mounted || event.payload.id !== overlayWindowId) return;
+
+          const { width, height } = event.payload;
+          setSettings((prev) => ({ ...prev, width, height }));
+        });
+      } catch (error) {
+        console.error(
+          "[useOverlaySettings] Failed to setup resize listener:",
+          error
+        );
+      }
+    }
+
+    setupListener();
+
+    return () => {
+      mounted = false;
+      if (unlistenFn) {
+        unlistenFn();
+      }
+    };
+  }, [overlayWindowId]);
+
   // Apply settings to the actual window using the proper Tauri commands
   // This ensures we use configure_overlay/set_unconstrained_position on the backend
   // skipClickThrough: if true, don't apply clickThrough setting (used on initial load
@@ -202,6 +249,11 @@ export function useOverlaySettings(): UseOverlaySettingsReturn {
           enabled: s.alwaysOnTop,
         });
 
+        await invoke("plugin:yaof|overlay_set_desktop_level", {
+          id: overlayWindowId,
+          level: s.desktopLevel,
+        });
+
         // Handle visibility
         await invoke("plugin:yaof|overlay_set_visible", {
           id: overlayWindowId,
@@ -1,6 +1,7 @@
 import { useOverlayContext } from "@/context";
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
-import { load, Store } from "@tauri-apps/plugin-store";
+import { Store } from "@tauri-apps/plugin-store";
+import { loadPluginStore } from "@/store";
 import { useCallback, useEffect, useRef, useState } from "react";
 import type { z } from "zod";
 
@@ -10,6 +11,13 @@ interface SettingsChangedPayload {
   values: Record<string, unknown>;
 }
 
+/** Payload of `yaof:plugin:{id}:settings-changed`, sent after the settings commands wrote */
+interface PluginSettingsChangedPayload {
+  pluginId: string;
+  /** Keys that were set or deleted, null when all settings were cleared */
+  keys: string[] | null;
+}
+
 export interface UsePluginConfigReturn<T extends z.ZodObject<z.ZodRawShape>> {
   /** Full config object with all values (reactive) */
   config: z.infer<T>;
@@ -104,7 +112,7 @@ export function usePluginConfig<T extends z.ZodObject<z.ZodRawShape>>(
     async function loadConfig() {
       try {
         // Use -settings.json to match the Tauri commands used by core-settings
-        const s = await load(`${pluginId}-settings.json`);
+        const s = await loadPluginStore(`${pluginId}-settings.json`);
         if (!mounted) return;
 
         setStore(s);
@@ -194,6 +202,58 @@ export function usePluginConfig<T extends z.ZodObject<z.ZodRawShape>>(
     };
   }, [pluginId]);
 
+  // Pick up writes made through the plugin_settings_* commands, e.g. by a
+  // native component or another window
+  useEffect(() => {
+    if (!store) return;
+
+    let mounted = true;
+    let unlistenFn: UnlistenFn | null = null;
+
+    async function setupListener(s: Store) {
+      try {
+        unlistenFn = await listen<PluginSettingsChangedPayload>(
+          `yaof:plugin:${pluginId}:settings-changed`,
+          async () => {
+            const reloaded: Record<string, unknown> = {
+              ...getDefaultsFromSchema(schemaRef.current),
+            };
+            for (const key of Object.keys(schemaRef.current.shape)) {
+              const stored = await s.get<unknown>(key);
+              if (stored !== null && stored !== undefined) {
+                reloaded[key] = stored;
+              }
+            }
+
+            try {
+              const validated = schemaRef.current.parse(reloaded);
+              if (mounted) setConfig(validated);
+            } catch (error) {
+              console.error(
+                "[usePluginConfig] Failed to validate settings update:",
+                error
+              );
+            }
+          }
+        );
+      } catch (error) {
+        console.error(
+          "[usePluginConfig] Failed to setup settings listener:",
+          error
+        );
+      }
+    }
+
+    setupListener(store);
+
+    return () => {
+      mounted = false;
+      if (unlistenFn) {
+        unlistenFn();
+      }
+    };
+  }, [pluginId, store]);
+
   /**
    * Get a specific config value in real-time from the store.
    * This fetches directly from the store, not from React state.
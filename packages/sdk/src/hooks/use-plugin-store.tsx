@@ -1,5 +1,6 @@
 import { useOverlayContext } from "@/context";
-import { load, Store } from "@tauri-apps/plugin-store";
+import { Store } from "@tauri-apps/plugin-store";
+import { loadPluginStore } from "@/store";
 import { useCallback, useEffect, useState } from "react";
 
 type SetStateAction<T> = T | ((prev: T) => T);
@@ -24,7 +25,7 @@ export function usePluginStore<T>(
 
     async function loadValue() {
       try {
-        const store = await load(`${pluginId}.json`);
+        const store = await loadPluginStore(`${pluginId}.json`);
         if (mounted && store !== null) {
           setStore(store);
           const val = await store.get<T>(key);
@@ -1,4 +1,5 @@
 import { useOverlayContext } from "@/context";
+import { toError } from "@/errors";
 import { invoke } from "@tauri-apps/api/core";
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import { useCallback, useEffect, useRef, useState } from "react";
@@ -15,7 +16,7 @@ export function useService<T = unknown>(
   const [data, setData] = useState<T | null>(null);
   const [isConnected, setIsConnected] = useState(false);
   const [error, setError] = useState<Error | null>(null);
-  const unlistenRef = useRef<UnlistenFn | null>(null);
+  const unlistenRef = useRef<UnlistenFn[]>([]);
 
   useEffect(() => {
     let mounted = true;
@@ -36,14 +37,48 @@ export function useService<T = unknown>(
           (event) => {
             if (mounted) {
               setData(event.payload);
+              setIsConnected(true);
             }
           }
         );
 
-        unlistenRef.current = unlisten;
+        // The provider went away, drop the stale value
+        const unlistenUnregistered = await listen<string>(
+          `yaof:service:${providerId}:unregistered`,
+          () => {
+            if (mounted) {
+              setData(null);
+              setIsConnected(false);
+            }
+          }
+        );
+
+        // The provider came back (e.g. its overlay reloaded), subscribe again
+        // since unregistering dropped the subscription
+        const unlistenRegistered = await listen<string>(
+          `yaof:service:${providerId}:registered`,
+          () => {
+            invoke("plugin:yaof|service_subscribe", { providerId })
+              .then(() => {
+                if (mounted) {
+                  setIsConnected(true);
+                  setError(null);
+                }
+              })
+              .catch((err) => {
+                if (mounted) setError(toError(err));
+              });
+          }
+        );
+
+        unlistenRef.current = [
+          unlisten,
+          unlistenUnregistered,
+          unlistenRegistered,
+        ];
       } catch (err) {
         if (mounted) {
-          setError(err instanceof Error ? err : new Error(String(err)));
+          setError(toError(err));
           setIsConnected(false);
         }
       }
@@ -53,9 +88,8 @@ export function useService<T = unknown>(
 
     return () => {
       mounted = false;
-      if (unlistenRef.current) {
-        unlistenRef.current();
-      }
+      unlistenRef.current.forEach((unlisten) => unlisten());
+      unlistenRef.current = [];
       // Unsubscribe from service
       invoke("plugin:yaof|service_unsubscribe", { providerId }).catch(
         console.error
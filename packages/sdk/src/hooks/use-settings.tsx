@@ -1,5 +1,6 @@
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
-import { load, Store } from "@tauri-apps/plugin-store";
+import { Store } from "@tauri-apps/plugin-store";
+import { loadPluginStore } from "@/store";
 import { useCallback, useEffect, useMemo, useRef, useState } from "react";
 import { useOverlayContext } from "../context";
 import type { SettingsSchema, SettingsValues } from "../types/settings";
@@ -10,6 +11,13 @@ interface SettingsChangedPayload {
   values: Record<string, unknown>;
 }
 
+/** Payload of `yaof:plugin:{id}:settings-changed`, sent after the settings commands wrote */
+interface PluginSettingsChangedPayload {
+  pluginId: string;
+  /** Keys that were set or deleted, null when all settings were cleared */
+  keys: string[] | null;
+}
+
 export interface UsePluginSettingsReturn<T extends SettingsSchema> {
   /** Current settings values */
   settings: SettingsValues<T>;
@@ -74,7 +82,7 @@ export function usePluginSettings<T extends SettingsSchema>(
 
     async function loadSettings() {
       try {
-        const s = await load(`${pluginId}-settings.json`);
+        const s = await loadPluginStore(`${pluginId}-settings.json`);
         if (!mounted) return;
 
         setStore(s);
@@ -145,6 +153,49 @@ export function usePluginSettings<T extends SettingsSchema>(
     };
   }, [pluginId]);
 
+  // Pick up writes made through the plugin_settings_* commands, e.g. by a
+  // native component or another window
+  useEffect(() => {
+    if (!store) return;
+
+    let mounted = true;
+    let unlistenFn: UnlistenFn | null = null;
+
+    async function setupListener(s: Store) {
+      try {
+        unlistenFn = await listen<PluginSettingsChangedPayload>(
+          `yaof:plugin:${pluginId}:settings-changed`,
+          async () => {
+            const reloaded: Record<string, unknown> = {};
+            for (const [key, field] of Object.entries(schemaRef.current)) {
+              const stored = await s.get<unknown>(key);
+              reloaded[key] =
+                stored !== null && stored !== undefined
+                  ? stored
+                  : field.default;
+            }
+
+            if (mounted) setValues(reloaded);
+          }
+        );
+      } catch (error) {
+        console.error(
+          "[usePluginSettings] Failed to setup settings listener:",
+          error
+        );
+      }
+    }
+
+    setupListener(store);
+
+    return () => {
+      mounted = false;
+      if (unlistenFn) {
+        unlistenFn();
+      }
+    };
+  }, [pluginId, store]);
+
   // Update a single setting
   const setSetting = useCallback(
     async <K extends keyof T>(key: K, value: SettingsValues<T>[K]) => {
@@ -1,5 +1,7 @@
+import { invoke } from "@tauri-apps/api/core";
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import { useEffect, useRef, useState } from "react";
+import { toError } from "@/errors";
 
 /**
  * System status data emitted by yaof-core's built-in system services.
@@ -30,7 +32,14 @@ export interface SystemStatus {
     album: string | null;
     duration_ms: number | null;
     position_ms: number | null;
+    /**
+     * When `position_ms` was read (ms since the Unix epoch). While playing, the
+     * current position is `position_ms + (Date.now() - position_updated_at_ms)`.
+     */
+    position_updated_at_ms: number | null;
     app_name: string | null;
+    /** Album art as a data URL or http(s) URL, null when the track has none */
+    artwork: string | null;
   };
 }
 
@@ -70,6 +79,8 @@ export function useSystemService<T = SystemStatus>(
   const [isConnected, setIsConnected] = useState(false);
   const [error, setError] = useState<Error | null>(null);
   const unlistenRef = useRef<UnlistenFn | null>(null);
+  // Status events only carry the artwork when it changes, keep the last one
+  const artworkRef = useRef<string | null>(null);
 
   useEffect(() => {
     let mounted = true;
@@ -81,9 +92,18 @@ export function useSystemService<T = SystemStatus>(
           "yaof:system:status",
           (event) => {
             if (mounted) {
+              const media = event.payload.media;
+              if (media.artwork !== undefined) {
+                artworkRef.current = media.artwork;
+              }
+              const status: SystemStatus = {
+                ...event.payload,
+                media: { ...media, artwork: artworkRef.current },
+              };
+
               const transformed = transform
-                ? transform(event.payload)
-                : (event.payload as unknown as T);
+                ? transform(status)
+                : (status as unknown as T);
               setData(transformed);
               setIsConnected(true);
               setError(null);
@@ -93,6 +113,14 @@ export function useSystemService<T = SystemStatus>(
 
         unlistenRef.current = unlisten;
 
+        // Artwork fetched before we subscribed isn't sent again until it changes
+        const artwork = await invoke<string | null>(
+          "plugin:yaof|system_media_artwork"
+        );
+        if (artworkRef.current === null) {
+          artworkRef.current = artwork;
+        }
+
         // Mark as connected once we've set up the listener
         // (we'll get data on the next tick from the system services)
         if (mounted) {
@@ -100,7 +128,7 @@ export function useSystemService<T = SystemStatus>(
         }
       } catch (err) {
         if (mounted) {
-          setError(err instanceof Error ? err : new Error(String(err)));
+          setError(toError(err));
           setIsConnected(false);
         }
       }
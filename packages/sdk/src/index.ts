@@ -1,5 +1,7 @@
 export * from "./hooks";
 export * from "./context";
 export * from "./components";
+export * from "./errors";
+export * from "./store";
 
 export type * from "./types";
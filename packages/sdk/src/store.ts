@@ -0,0 +1,11 @@
+import { invoke } from "@tauri-apps/api/core";
+import { load, Store } from "@tauri-apps/plugin-store";
+
+/**
+ * Open a plugin store file (e.g. `my-plugin-settings.json`) where the core keeps
+ * it, under the yaof home's `stores` directory
+ */
+export async function loadPluginStore(name: string): Promise<Store> {
+  const path = await invoke<string>("plugin:yaof|plugin_store_path", { name });
+  return load(path);
+}
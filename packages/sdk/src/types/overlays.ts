@@ -9,3 +9,9 @@ export type OverlayConfig = {
   clickThrough: boolean;
   frameless: boolean;
 };
+
+/**
+ * Desktop layer an overlay is pinned to, behind all windows.
+ * `icons` is only distinct from `wallpaper` on macOS.
+ */
+export type DesktopLevel = "wallpaper" | "icons";
@@ -1,4 +1,5 @@
 import { z } from "zod";
+import type { DesktopLevel } from "./overlays";
 import type { SettingsSchema, SettingField } from "./settings";
 
 type PluginOverlay = {
@@ -27,6 +28,13 @@ type PluginOverlay = {
    * @example "/settings", "/mini", "/dashboard"
    */
   route?: string;
+  /**
+   * Keep the window hidden until its page has loaded, for at most this many
+   * milliseconds. Shown right away when unset.
+   */
+  readyTimeoutMs?: number;
+  /** Pin the overlay to the desktop behind all windows instead of floating on top */
+  desktopLevel?: DesktopLevel;
 };
 
 type PluginProvider<T extends z.ZodType> = {
@@ -1,6 +1,7 @@
 import { useCallback, useEffect, useRef, useState } from "react";
-import { load, type Store } from "@tauri-apps/plugin-store";
+import { type Store } from "@tauri-apps/plugin-store";
 import { invoke } from "@tauri-apps/api/core";
+import { loadPluginStore, type DesktopLevel } from "@m4cs/yaof-sdk";
 import {
   Card,
   CardContent,
@@ -14,6 +15,13 @@ import { Label } from "@yaof/ui/components/ui/label";
 import { Badge } from "@yaof/ui/components/ui/badge";
 import { Separator } from "@yaof/ui/components/ui/separator";
 import { Skeleton } from "@yaof/ui/components/ui/skeleton";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@yaof/ui/components/ui/select";
 
 export interface OverlayDefinition {
   width: number;
@@ -30,6 +38,10 @@ export interface OverlayDefinition {
     | "bottom-left"
     | "bottom-center"
     | "bottom-right";
+  /** Distance from the screen edges for the default position */
+  padding?: number | { x: number; y: number };
+  /** Pin the overlay to the desktop behind all windows */
+  desktopLevel?: DesktopLevel;
   clickThrough?: boolean;
   frameless?: boolean;
   /** Optional route path for this overlay (used with HashRouter) */
@@ -44,6 +56,8 @@ export interface OverlaySettings {
   height: number;
   alwaysOnTop: boolean;
   clickThrough: boolean;
+  /** Desktop layer the overlay is pinned to, null floats it normally */
+  desktopLevel: DesktopLevel | null;
 }
 
 interface OverlaySettingsPanelProps {
@@ -59,52 +73,40 @@ interface OverlaySettingsPanelProps {
 const DEBOUNCE_DELAY = 300;
 
 /**
- * Calculate default position based on preset
+ * Calculate default position based on preset, using the same calculation
+ * as autostart so it matches where the overlay is actually placed.
+ * A stored preset and padding take priority over the manifest's, like in autostart.
  */
-function calculateDefaultPosition(
-  preset: OverlayDefinition["defaultPosition"],
-  width: number,
-  height: number
-): { x: number; y: number } {
-  // Assume a standard screen size for defaults - actual positioning
-  // will be handled by the overlay runtime
-  const screenWidth = 1920;
-  const screenHeight = 1080;
-
-  switch (preset) {
-    case "top-left":
-      return { x: 0, y: 0 };
-    case "top-center":
-      return { x: (screenWidth - width) / 2, y: 0 };
-    case "top-right":
-      return { x: screenWidth - width, y: 0 };
-    case "center-left":
-      return { x: 0, y: (screenHeight - height) / 2 };
-    case "center":
-      return { x: (screenWidth - width) / 2, y: (screenHeight - height) / 2 };
-    case "center-right":
-      return { x: screenWidth - width, y: (screenHeight - height) / 2 };
-    case "bottom-left":
-      return { x: 0, y: screenHeight - height };
-    case "bottom-center":
-      return { x: (screenWidth - width) / 2, y: screenHeight - height };
-    case "bottom-right":
-      return { x: screenWidth - width, y: screenHeight - height };
-    default:
-      return { x: 100, y: 100 };
+async function calculateDefaultPosition(
+  def: OverlayDefinition,
+  store: Store
+): Promise<{ x: number; y: number }> {
+  try {
+    const preset = await store.get<string>("positionPreset");
+    const padding = await store.get<OverlayDefinition["padding"]>("padding");
+    const [x, y] = await invoke<[number, number]>(
+      "plugin:yaof|compute_preset_position",
+      {
+        preset: preset ?? def.defaultPosition ?? "center",
+        width: def.width,
+        height: def.height,
+        padding: padding ?? def.padding,
+      }
+    );
+    return { x, y };
+  } catch (error) {
+    console.error("Failed to compute preset position:", error);
+    return { x: 100, y: 100 };
   }
 }
 
 /**
  * Get default settings from overlay definition (overlay.json)
  */
-function getDefaultSettings(def: OverlayDefinition): OverlaySettings {
-  const defaultPos = calculateDefaultPosition(
-    def.defaultPosition,
-    def.width,
-    def.height
-  );
-
+function getDefaultSettings(
+  def: OverlayDefinition,
+  defaultPos = { x: 100, y: 100 }
+): OverlaySettings {
   return {
     enabled: true,
     width: def.width,
@@ -113,6 +115,7 @@ function getDefaultSettings(def: OverlayDefinition): OverlaySettings {
     y: def.y ?? defaultPos.y,
     alwaysOnTop: true,
     clickThrough: def.clickThrough ?? false,
+    desktopLevel: def.desktopLevel ?? null,
   };
 }
 
@@ -154,18 +157,25 @@ export function OverlaySettingsPanel({
 
     async function loadSettings() {
       try {
-        const s = await load(storeName);
+        const s = await loadPluginStore(storeName);
         if (!mounted) return;
         setStore(s);
 
         // Start with defaults from overlay.json
-        const defaults = getDefaultSettings(overlayDefinition);
+        const defaults = getDefaultSettings(
+          overlayDefinition,
+          await calculateDefaultPosition(overlayDefinition, s)
+        );
+        if (!mounted) return;
         const loaded: OverlaySettings = { ...defaults };
 
         // Override with any stored user settings
+        // An explicit null desktopLevel turns off the manifest's desktop level
         for (const key of Object.keys(defaults) as (keyof OverlaySettings)[]) {
           const value = await s.get<unknown>(key);
-          if (value !== null && value !== undefined) {
+          if (value === null && key === "desktopLevel") {
+            loaded.desktopLevel = null;
+          } else if (value !== null && value !== undefined) {
             (loaded as any)[key] = value;
           }
         }
@@ -278,6 +288,14 @@ export function OverlaySettingsPanel({
             enabled: updates.alwaysOnTop,
           });
         }
+
+        // Handle desktop level changes
+        if (updates.desktopLevel !== undefined) {
+          await invoke("plugin:yaof|overlay_set_desktop_level", {
+            id: overlayWindowId,
+            level: updates.desktopLevel,
+          });
+        }
       } catch (error) {
         console.error("Failed to apply overlay settings:", error);
       }
@@ -501,6 +519,36 @@ export function OverlaySettingsPanel({
             }
           />
         </div>
+
+        {/* Desktop Level */}
+        <div className="flex items-center justify-between">
+          <div className="space-y-0.5">
+            <Label htmlFor="desktop-level" className="text-sm font-medium">
+              Pin to Desktop
+            </Label>
+            <p className="text-xs text-muted-foreground">
+              Keep overlay behind all windows, on the desktop
+            </p>
+          </div>
+          <Select
+            value={settings.desktopLevel ?? "off"}
+            onValueChange={(value: string | null) =>
+              updateSetting(
+                "desktopLevel",
+                value === "wallpaper" || value === "icons" ? value : null
+              )
+            }
+          >
+            <SelectTrigger id="desktop-level" className="w-40">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value="off">Off</SelectItem>
+              <SelectItem value="wallpaper">Wallpaper</SelectItem>
+              <SelectItem value="icons">Desktop icons</SelectItem>
+            </SelectContent>
+          </Select>
+        </div>
       </CardContent>
     </Card>
   );
@@ -1,7 +1,6 @@
 import { useEffect, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { load } from "@tauri-apps/plugin-store";
-import type { PluginManifest } from "@m4cs/yaof-sdk";
+import { loadPluginStore, type PluginManifest } from "@m4cs/yaof-sdk";
 import { PluginCard } from "../components/plugin-card";
 import { Skeleton } from "@yaof/ui/components/ui/skeleton";
 import { Card } from "@yaof/ui/components/ui/card";
@@ -40,7 +39,7 @@ export function PluginList({ onSelectPlugin }: PluginListProps) {
         const overlayId = Object.keys(plugin.overlays)[0] ?? "main";
         const storeName = `${plugin.id}-${overlayId}-overlay.json`;
         try {
-          const store = await load(storeName);
+          const store = await loadPluginStore(storeName);
           const enabled = await store.get<boolean>("enabled");
           states[plugin.id] = enabled ?? true;
         } catch {
@@ -63,7 +62,7 @@ export function PluginList({ onSelectPlugin }: PluginListProps) {
     const storeName = `${pluginId}-${overlayId}-overlay.json`;
 
     try {
-      const store = await load(storeName);
+      const store = await loadPluginStore(storeName);
       await store.set("enabled", enabled);
       await store.save();
 